@@ -0,0 +1,222 @@
+use url::Url;
+
+use crate::send_key::SendKey;
+
+/// DOM knowledge about a specific AI site, used when we need to drive its page
+/// (fill the prompt box, press send, ...) from Rust.
+pub struct Adapter {
+    pub key: &'static str,
+    pub hosts: &'static [&'static str],
+    /// Selector for the prompt input (textarea or contenteditable).
+    pub prompt_selector: &'static str,
+    /// Selector for the send button.
+    pub send_selector: &'static str,
+    /// Selector matching each assistant message; the last match is the latest answer.
+    pub response_selector: &'static str,
+    /// Path that opens a fresh conversation, relative to the site's origin.
+    pub new_chat_path: Option<&'static str>,
+    /// Path prefix of a saved conversation (`/c/`); a page under it is a
+    /// permalink to that chat, visible to the signed-in user.
+    pub conversation_path: Option<&'static str>,
+    /// How to get a public link, for sites where that takes a "share" click.
+    pub share: Option<ShareFlow>,
+    /// The site's page background, shown while a tab loads.
+    pub page_colors: Option<PageColors>,
+    /// Element only shown to signed-out visitors, for sites that don't move
+    /// to a login URL when the session ends.
+    pub signed_out_selector: Option<&'static str>,
+    /// The key combination the site's prompt box sends with.
+    pub send_key: SendKey,
+}
+
+/// `#rrggbb` page backgrounds of a site in its light and dark themes.
+#[derive(Clone, Copy)]
+pub struct PageColors {
+    pub light: &'static str,
+    pub dark: &'static str,
+}
+
+/// Buttons to click in order until the share link shows up.
+pub struct ShareFlow {
+    pub clicks: &'static [&'static str],
+    /// Input or anchor holding the link once it's created.
+    pub link: &'static str,
+}
+
+const ADAPTERS: &[Adapter] = &[
+    Adapter {
+        key: "chatgpt",
+        hosts: &["chatgpt.com", "chat.openai.com"],
+        prompt_selector: "#prompt-textarea",
+        send_selector: "button[data-testid='send-button']",
+        response_selector: "[data-message-author-role='assistant']",
+        new_chat_path: Some("/"),
+        conversation_path: Some("/c/"),
+        share: Some(ShareFlow {
+            clicks: &["button[data-testid='share-chat-button']", "button[data-testid='create-link-shared-chat-button'], button[data-testid='copy-link-button']"],
+            link: "input[readonly][value*='/share/']",
+        }),
+        page_colors: Some(PageColors { light: "#ffffff", dark: "#212121" }),
+        signed_out_selector: Some("button[data-testid='login-button']"),
+        send_key: SendKey::Enter,
+    },
+    Adapter {
+        key: "claude",
+        hosts: &["claude.ai"],
+        prompt_selector: "div.ProseMirror[contenteditable='true']",
+        send_selector: "button[aria-label='Send message']",
+        response_selector: "div.font-claude-response",
+        new_chat_path: Some("/new"),
+        conversation_path: Some("/chat/"),
+        share: Some(ShareFlow {
+            clicks: &["button[data-testid='share-button'], button[aria-label='Share']", "button[data-testid='create-share-link'], button[aria-label='Create public link']"],
+            link: "input[value*='/share/'], a[href*='/share/']",
+        }),
+        page_colors: Some(PageColors { light: "#faf9f5", dark: "#262624" }),
+        signed_out_selector: None,
+        send_key: SendKey::Enter,
+    },
+    Adapter {
+        key: "gemini",
+        hosts: &["gemini.google.com"],
+        prompt_selector: "rich-textarea .ql-editor",
+        send_selector: "button.send-button",
+        response_selector: "message-content",
+        new_chat_path: Some("/app"),
+        conversation_path: Some("/app/"),
+        share: None,
+        page_colors: Some(PageColors { light: "#ffffff", dark: "#1b1c1d" }),
+        signed_out_selector: Some("a[href*='accounts.google.com/ServiceLogin']"),
+        send_key: SendKey::Enter,
+    },
+    Adapter {
+        key: "deepseek",
+        hosts: &["chat.deepseek.com"],
+        prompt_selector: "textarea#chat-input",
+        send_selector: "div[role='button'][aria-disabled='false']",
+        response_selector: ".ds-markdown",
+        new_chat_path: Some("/"),
+        conversation_path: Some("/a/chat/s/"),
+        share: None,
+        page_colors: Some(PageColors { light: "#ffffff", dark: "#292a2d" }),
+        signed_out_selector: None,
+        send_key: SendKey::Enter,
+    },
+    Adapter {
+        key: "kimi",
+        hosts: &["kimi.moonshot.cn", "www.kimi.com", "kimi.com"],
+        prompt_selector: ".chat-input-editor[contenteditable='true']",
+        send_selector: ".send-button",
+        response_selector: ".segment-assistant .markdown",
+        new_chat_path: Some("/"),
+        conversation_path: Some("/chat/"),
+        share: None,
+        page_colors: Some(PageColors { light: "#ffffff", dark: "#1f1f1f" }),
+        signed_out_selector: None,
+        send_key: SendKey::Enter,
+    },
+    Adapter {
+        key: "qwen",
+        hosts: &["tongyi.aliyun.com", "www.tongyi.com", "chat.qwen.ai"],
+        prompt_selector: "textarea",
+        send_selector: "button[type='submit']",
+        response_selector: ".markdown-body",
+        new_chat_path: Some("/"),
+        conversation_path: None,
+        share: None,
+        page_colors: None,
+        signed_out_selector: None,
+        send_key: SendKey::Enter,
+    },
+];
+
+/// Fallback used for custom platforms we know nothing about.
+const GENERIC: Adapter = Adapter {
+    key: "generic",
+    hosts: &[],
+    prompt_selector: "textarea, [contenteditable='true']",
+    send_selector: "button[type='submit']",
+    response_selector: "article, .markdown, .prose",
+    new_chat_path: None,
+    conversation_path: None,
+    share: None,
+    page_colors: None,
+    signed_out_selector: None,
+    send_key: SendKey::Enter,
+};
+
+/// Pick the adapter matching the host of `url`, or the generic one.
+pub fn for_url(url: &str) -> &'static Adapter {
+    let host = match Url::parse(url) {
+        Ok(u) => u.host_str().unwrap_or_default().to_string(),
+        Err(_) => return &GENERIC,
+    };
+    ADAPTERS
+        .iter()
+        .find(|a| a.hosts.iter().any(|h| host == *h || host.ends_with(&format!(".{}", h))))
+        .unwrap_or(&GENERIC)
+}
+
+/// Encode a Rust string as a JS string literal.
+pub fn js_string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// Build a script that writes `text` into the prompt box and optionally submits it.
+/// Textareas go through the native value setter so React-controlled inputs notice
+/// the change; contenteditable editors (ProseMirror, Quill) get `insertText`.
+pub fn insert_prompt_js(adapter: &Adapter, text: &str, submit: bool) -> String {
+    format!(
+        r#"
+        (function() {{
+            var attempts = 0;
+            (function tryInsert() {{
+                var el = document.querySelector({prompt});
+                if (!el) {{
+                    // SPA editors mount after load; keep looking for ~10s
+                    if (++attempts < 40) {{ setTimeout(tryInsert, 250); }} else {{ console.warn('[anybrain] prompt box not found'); }}
+                    return;
+                }}
+                el.focus();
+                var text = {text};
+                if (el.tagName === 'TEXTAREA' || el.tagName === 'INPUT') {{
+                    var proto = el.tagName === 'TEXTAREA' ? HTMLTextAreaElement.prototype : HTMLInputElement.prototype;
+                    var setter = Object.getOwnPropertyDescriptor(proto, 'value').set;
+                    setter.call(el, el.value + text);
+                    el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                }} else {{
+                    document.execCommand('insertText', false, text);
+                }}
+                if ({submit}) {{
+                    setTimeout(function() {{
+                        var btn = document.querySelector({send});
+                        if (btn) {{
+                            btn.click();
+                        }} else {{
+                            var mod = {mod_enter}, mac = /Mac|iP(hone|ad|od)/.test(navigator.platform);
+                            el.dispatchEvent(new KeyboardEvent('keydown', {{ key: 'Enter', code: 'Enter', keyCode: 13, bubbles: true, metaKey: mod && mac, ctrlKey: mod && !mac }}));
+                        }}
+                    }}, 150);
+                }}
+            }})();
+        }})();
+        "#,
+        prompt = js_string(adapter.prompt_selector),
+        send = js_string(adapter.send_selector),
+        text = js_string(text),
+        submit = submit,
+        mod_enter = adapter.send_key == SendKey::ModEnter,
+    )
+}
+
+/// Expression evaluating to the text of the latest assistant message, or `null`.
+pub fn last_response_js(adapter: &Adapter) -> String {
+    format!(
+        r#"(function() {{
+            var nodes = document.querySelectorAll({response});
+            if (!nodes.length) return null;
+            return nodes[nodes.length - 1].innerText;
+        }})()"#,
+        response = js_string(adapter.response_selector),
+    )
+}
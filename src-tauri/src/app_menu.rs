@@ -0,0 +1,157 @@
+use tauri::menu::{Menu, MenuEvent, MenuItem, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_window_manager;
+use crate::platforms;
+use crate::shortcuts;
+
+const MENU_PREFIX: &str = "menu:";
+
+fn item_with(app: &AppHandle, id: &str, text: &str, accelerator: Option<&str>) -> tauri::Result<MenuItem<tauri::Wry>> {
+    MenuItem::with_id(app, format!("{}{}", MENU_PREFIX, id), text, true, accelerator)
+}
+
+/// A menu item bound to whatever the user mapped `id` to in the shortcuts settings.
+fn item(app: &AppHandle, id: &str, text: &str) -> tauri::Result<MenuItem<tauri::Wry>> {
+    item_with(app, id, text, shortcuts::accelerator(app, id).as_deref())
+}
+
+/// Build the native menu bar. Accelerators live here rather than in the
+/// frontend so they fire even when a child webview has keyboard focus.
+pub fn build(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+
+    #[cfg(target_os = "macos")]
+    menu.append(
+        &SubmenuBuilder::new(app, "Brainer")
+            .about(None)
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .separator()
+            .quit()
+            .build()?,
+    )?;
+
+    menu.append(
+        &SubmenuBuilder::new(app, "File")
+            .item(&item(app, "quick_capture", "Quick Capture")?)
+            .item(&item(app, "broadcast", "Broadcast Prompt")?)
+            .separator()
+            .item(&item(app, "lock", "Lock")?)
+            .separator()
+            .close_window()
+            .build()?,
+    )?;
+
+    menu.append(
+        &SubmenuBuilder::new(app, "Edit")
+            .undo()
+            .redo()
+            .separator()
+            .cut()
+            .copy()
+            .paste()
+            .select_all()
+            .separator()
+            .item(&item(app, "find", "Find…")?)
+            .build()?,
+    )?;
+
+    menu.append(
+        &SubmenuBuilder::new(app, "View")
+            .item(&item(app, "reload", "Reload")?)
+            .separator()
+            .item(&item(app, "zoom_in", "Zoom In")?)
+            .item(&item(app, "zoom_out", "Zoom Out")?)
+            .item(&item(app, "zoom_reset", "Actual Size")?)
+            .separator()
+            .item(&item(app, "toggle_zen", "Zen Mode")?)
+            .build()?,
+    )?;
+
+    let mut tabs = SubmenuBuilder::new(app, "Tabs")
+        .item(&item(app, "next_tab", "Next Tab")?)
+        .item(&item(app, "prev_tab", "Previous Tab")?)
+        .item(&item(app, "reopen_closed_tab", "Reopen Closed Tab")?)
+        .separator();
+    for n in 1..=9 {
+        tabs = tabs.item(&item_with(app, &format!("tab_{}", n), &format!("Tab {}", n), Some(&format!("CmdOrCtrl+{}", n)))?);
+    }
+    menu.append(&tabs.build()?)?;
+
+    Ok(menu)
+}
+
+/// Switch to the n-th visible saved platform (1-based), matching the tab bar order.
+fn activate_nth(app: &AppHandle, n: usize) {
+    let visible: Vec<_> = platforms::load(app).into_iter().filter(|p| !p.hidden).collect();
+    if let Some(platform) = visible.get(n - 1) {
+        let _ = app.emit("activate_platform", platform.id.clone());
+    }
+}
+
+const FIND_JS: &str = r#"
+(function() {
+    var q = window.prompt('Find in page');
+    if (q) { window.find(q, false, false, true); }
+})();
+"#;
+
+pub fn handle_event(app: &AppHandle, event: &MenuEvent) {
+    let Some(action) = event.id().as_ref().strip_prefix(MENU_PREFIX) else { return };
+    tracing::debug!("{}", action);
+    let active = ai_window_manager::active_platform(app).and_then(|id| ai_window_manager::get_webview(app, &id));
+    match action {
+        "reload" => {
+            if let Some(webview) = active {
+                let _ = webview.eval("window.location.reload()");
+            }
+        }
+        "zoom_in" => ai_window_manager::zoom_active(app, Some(0.1)),
+        "zoom_out" => ai_window_manager::zoom_active(app, Some(-0.1)),
+        "zoom_reset" => ai_window_manager::zoom_active(app, None),
+        "toggle_zen" => {
+            if let Err(e) = crate::titlebar::set_chrome_visible(app.clone(), !crate::titlebar::chrome_visible()) {
+                tracing::warn!("toggling zen mode failed: {}", e);
+            }
+        }
+        "find" => {
+            if let Some(webview) = active {
+                let _ = webview.eval(FIND_JS);
+            }
+        }
+        "reopen_closed_tab" => {
+            if let Err(e) = ai_window_manager::reopen_last_closed(app.clone()) {
+                tracing::warn!("reopen closed tab failed: {}", e);
+            }
+        }
+        // Tab order includes frontend-only temporary tabs, so the frontend resolves these.
+        "lock" => crate::lock::lock(app, "manual"),
+        "broadcast" if !crate::flags::enabled(app, crate::flags::BROADCAST, None) => {
+            tracing::info!("broadcast is disabled by feature flag");
+        }
+        "next_tab" | "prev_tab" | "quick_capture" | "broadcast" => {
+            let _ = app.emit("menu_action", action.to_string());
+        }
+        other => {
+            if let Some(n) = other.strip_prefix("tab_").and_then(|n| n.parse::<usize>().ok()) {
+                activate_nth(app, n);
+            }
+        }
+    }
+}
+
+/// Rebuild the menu bar after shortcuts were remapped.
+pub fn refresh(app: &AppHandle) -> tauri::Result<()> {
+    app.set_menu(build(app)?)?;
+    Ok(())
+}
+
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build(app)?;
+    app.set_menu(menu)?;
+    app.on_menu_event(|app, event| handle_event(app, &event));
+    Ok(())
+}
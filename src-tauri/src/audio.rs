@@ -0,0 +1,118 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewBuilder, Wry};
+
+use crate::ai_window_manager;
+
+/// Watches `<audio>`/`<video>` elements and Web Audio contexts (voice modes
+/// play through either) and reports when the page starts or stops making
+/// sound. Media events don't bubble, so they're caught in the capture phase.
+const AUDIO_JS: &str = r#"
+(function() {
+    var contexts = [];
+    var last = false;
+    var playing = function() {
+        var media = Array.prototype.some.call(document.querySelectorAll('audio, video'), function(m) {
+            return !m.paused && !m.ended && !m.muted && m.volume > 0 && m.readyState > 2;
+        });
+        return media || contexts.some(function(c) { return c.state === 'running'; });
+    };
+    var timer = null;
+    var check = function() {
+        clearTimeout(timer);
+        // Debounced so buffering hiccups don't flicker the indicator.
+        timer = setTimeout(function() {
+            var now = playing();
+            if (now === last) return;
+            last = now;
+            window.__TAURI_INTERNALS__.invoke('report_audio_state', { playing: now }).catch(function() {});
+        }, 300);
+    };
+    ['play', 'playing', 'pause', 'ended', 'emptied', 'volumechange'].forEach(function(type) {
+        document.addEventListener(type, check, true);
+    });
+    ['AudioContext', 'webkitAudioContext'].forEach(function(name) {
+        var Original = window[name];
+        if (!Original) return;
+        var Wrapped = function() {
+            var ctx = new (Function.prototype.bind.apply(Original, [null].concat(Array.prototype.slice.call(arguments))))();
+            contexts.push(ctx);
+            ctx.addEventListener('statechange', function() {
+                if (ctx.state === 'closed') contexts.splice(contexts.indexOf(ctx), 1);
+                check();
+            });
+            check();
+            return ctx;
+        };
+        Wrapped.prototype = Original.prototype;
+        window[name] = Wrapped;
+    });
+    window.__anybrainStopAudio = function() {
+        document.querySelectorAll('audio, video').forEach(function(m) { m.pause(); });
+        contexts.forEach(function(c) { if (c.state === 'running') c.suspend(); });
+        if (window.speechSynthesis) window.speechSynthesis.cancel();
+        check();
+    };
+})();
+"#;
+
+/// Labels of child webviews currently playing sound.
+#[derive(Default)]
+pub struct AudioState {
+    playing: Mutex<HashSet<String>>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AudioStateChanged {
+    platform_id: String,
+    playing: bool,
+}
+
+pub fn configure_builder(builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(AUDIO_JS)
+}
+
+fn set_playing(app: &AppHandle, label: &str, playing: bool) {
+    let changed = {
+        let mut set = app.state::<AudioState>().playing.lock().unwrap();
+        if playing { set.insert(label.to_string()) } else { set.remove(label) }
+    };
+    if changed {
+        let platform_id = ai_window_manager::platform_for_label(app, label);
+        tracing::debug!("'{}' {} audio", platform_id, if playing { "started" } else { "stopped" });
+        let _ = app.emit("audio_state_changed", AudioStateChanged { platform_id, playing });
+    }
+}
+
+/// Called by the injected watcher when a page starts or stops making sound.
+#[tauri::command]
+pub fn report_audio_state(app: AppHandle, webview: tauri::Webview, playing: bool) {
+    set_playing(&app, webview.label(), playing);
+}
+
+/// A closed webview can't report that it went quiet.
+pub fn forget(app: &AppHandle, label: &str) {
+    if app.try_state::<AudioState>().is_some() {
+        set_playing(app, label, false);
+    }
+}
+
+/// Platforms currently playing sound.
+#[tauri::command]
+pub fn list_playing_audio(app: AppHandle) -> Vec<String> {
+    let labels: Vec<String> = app.state::<AudioState>().playing.lock().unwrap().iter().cloned().collect();
+    labels.iter().map(|label| ai_window_manager::platform_for_label(&app, label)).collect()
+}
+
+/// Silence every tab, plus our own text-to-speech.
+#[tauri::command]
+pub fn stop_all_audio(app: AppHandle) -> Result<(), String> {
+    for webview in app.webviews().values() {
+        if webview.label() != "main" {
+            let _ = webview.eval("window.__anybrainStopAudio && window.__anybrainStopAudio()");
+        }
+    }
+    crate::tts::stop_speaking(app)
+}
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, WebviewBuilder, Wry};
+use url::Url;
+
+use crate::{adapters, ai_window_manager, keychain, platforms, settings};
+
+/// `"autofill"` section of settings.json. Off until the user opts in.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct AutofillSettings {
+    enabled: bool,
+    /// Per-platform overrides for login pages the default selectors miss.
+    platforms: HashMap<String, LoginSelectors>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct LoginSelectors {
+    username: String,
+    password: String,
+    /// Hosts besides the one the login was saved for that it may be filled
+    /// on, e.g. a separate identity provider.
+    hosts: Vec<String>,
+}
+
+impl Default for LoginSelectors {
+    fn default() -> Self {
+        Self {
+            username: "input[autocomplete='username'], input[type='email'], input[name='username'], input[name='email']"
+                .to_string(),
+            password: "input[type='password']".to_string(),
+            hosts: Vec::new(),
+        }
+    }
+}
+
+/// A saved login. Stored as JSON in a single keychain entry per platform.
+#[derive(Serialize, Deserialize)]
+struct Login {
+    username: String,
+    password: String,
+    /// Host the login was saved for. Logins saved before this was recorded
+    /// belong to the host of the platform's home URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    host: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AutofillAvailable {
+    platform_id: String,
+    host: String,
+    username: String,
+}
+
+/// Reports login forms to Rust once per page. Nothing is filled from here:
+/// the frontend asks the user first and then calls `fill_login`.
+const DETECT_JS: &str = r#"
+(function(selectors) {
+    var reported = null;
+    var check = function() {
+        if (reported === location.href) return;
+        if (!document.querySelector(selectors.password) && !document.querySelector(selectors.username)) return;
+        reported = location.href;
+        window.__TAURI_INTERNALS__.invoke('login_form_detected', {}).catch(function() {});
+    };
+    var start = function() {
+        check();
+        new MutationObserver(check).observe(document.documentElement, { childList: true, subtree: true });
+    };
+    if (document.documentElement) start();
+    else document.addEventListener('DOMContentLoaded', start);
+})(__SELECTORS__);
+"#;
+
+fn account(platform_id: &str) -> String {
+    format!("login:{}", platform_id)
+}
+
+fn selectors(cfg: &AutofillSettings, platform_id: &str) -> LoginSelectors {
+    cfg.platforms.get(platform_id).cloned().unwrap_or_default()
+}
+
+fn load_login(platform_id: &str) -> Option<Login> {
+    serde_json::from_str(&keychain::get_secret(&account(platform_id))?).ok()
+}
+
+fn current_host(app: &AppHandle, platform_id: &str) -> Option<String> {
+    let url = ai_window_manager::get_webview(app, platform_id)?.url().ok()?;
+    url.host_str().map(str::to_string)
+}
+
+fn home_host(app: &AppHandle, platform_id: &str) -> Option<String> {
+    let platform = platforms::load(app).into_iter().find(|p| p.id == platform_id)?;
+    Url::parse(&platform.url).ok()?.host_str().map(str::to_string)
+}
+
+/// Whether `login` may be offered or filled on `host`: the host it was saved
+/// for, or one the user listed for the platform. Anything else the tab
+/// reaches (a page linked from the chat) never sees it.
+fn allowed_on(app: &AppHandle, platform_id: &str, login: &Login, sel: &LoginSelectors, host: &str) -> bool {
+    let origin = login.host.clone().or_else(|| home_host(app, platform_id));
+    origin.as_deref().is_some_and(|o| o.eq_ignore_ascii_case(host))
+        || sel.hosts.iter().any(|h| h.eq_ignore_ascii_case(host))
+}
+
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let cfg: AutofillSettings = settings::section(app, "autofill");
+    if !cfg.enabled {
+        return builder;
+    }
+    let sel = selectors(&cfg, platform_id);
+    let json = serde_json::json!({ "username": sel.username, "password": sel.password }).to_string();
+    builder.initialization_script(&DETECT_JS.replace("__SELECTORS__", &json))
+}
+
+/// Called by the detector in child webviews. Offers the saved login to the
+/// frontend if there is one for this tab and host.
+#[tauri::command]
+pub fn login_form_detected(app: AppHandle, webview: tauri::Webview) {
+    let cfg: AutofillSettings = settings::section(&app, "autofill");
+    if !cfg.enabled {
+        return;
+    }
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    let Some(host) = webview.url().ok().and_then(|u| u.host_str().map(str::to_string)) else { return };
+    let sel = selectors(&cfg, &platform_id);
+    let Some(login) = load_login(&platform_id) else { return };
+    if !allowed_on(&app, &platform_id, &login, &sel, &host) {
+        tracing::debug!("not offering the login for '{}' on {}", platform_id, host);
+        return;
+    }
+    tracing::debug!("login form on {} for '{}'", host, platform_id);
+    let _ = app.emit("autofill_available", AutofillAvailable { platform_id, host, username: login.username });
+}
+
+/// Fill the saved login into the tab's login form. `host` is the host the
+/// user agreed to fill on; if the tab has navigated elsewhere since, nothing
+/// is filled.
+#[tauri::command]
+pub fn fill_login(app: AppHandle, platform_id: String, host: String) -> Result<(), String> {
+    let cfg: AutofillSettings = settings::section(&app, "autofill");
+    if !cfg.enabled {
+        return Err("Autofill is disabled".to_string());
+    }
+    if current_host(&app, &platform_id).as_deref() != Some(host.as_str()) {
+        return Err(format!("'{}' is no longer on {}", platform_id, host));
+    }
+    let login = load_login(&platform_id).ok_or_else(|| format!("No saved login for '{}'", platform_id))?;
+    let sel = selectors(&cfg, &platform_id);
+    if !allowed_on(&app, &platform_id, &login, &sel, &host) {
+        return Err(format!("The login for '{}' wasn't saved for {}", platform_id, host));
+    }
+    let webview = ai_window_manager::get_webview(&app, &platform_id).ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    webview.eval(&fill_js(&sel, &login)).map_err(|e| e.to_string())
+}
+
+/// Fields go through the native value setter so React-controlled inputs
+/// notice the change. Two-step logins only show one field at a time; only the
+/// fields present are filled.
+fn fill_js(sel: &LoginSelectors, login: &Login) -> String {
+    format!(
+        r#"
+        (function() {{
+            var fill = function(selector, value) {{
+                var el = document.querySelector(selector);
+                if (!el) return;
+                el.focus();
+                var setter = Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set;
+                setter.call(el, value);
+                el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                el.dispatchEvent(new Event('change', {{ bubbles: true }}));
+            }};
+            fill({user_sel}, {user});
+            fill({pass_sel}, {pass});
+        }})();
+        "#,
+        user_sel = adapters::js_string(&sel.username),
+        user = adapters::js_string(&login.username),
+        pass_sel = adapters::js_string(&sel.password),
+        pass = adapters::js_string(&login.password),
+    )
+}
+
+/// Save (or replace) the login for a platform in the keychain, tied to
+/// `host`, or else the host the tab is on, or the platform's home host.
+#[tauri::command]
+pub fn save_login(
+    app: AppHandle,
+    platform_id: String,
+    username: String,
+    password: String,
+    host: Option<String>,
+) -> Result<(), String> {
+    let host = host
+        .or_else(|| current_host(&app, &platform_id))
+        .or_else(|| home_host(&app, &platform_id))
+        .ok_or_else(|| format!("Can't tell which site the login for '{}' belongs to", platform_id))?;
+    let json = serde_json::to_string(&Login { username, password, host: Some(host) }).map_err(|e| e.to_string())?;
+    keychain::set_secret(&account(&platform_id), &json)
+}
+
+#[tauri::command]
+pub fn delete_login(platform_id: String) -> Result<(), String> {
+    keychain::delete_secret(&account(&platform_id))
+}
+
+/// The username saved for a platform, if any. The password never leaves Rust
+/// except into the login form.
+#[tauri::command]
+pub fn saved_login_username(platform_id: String) -> Option<String> {
+    load_login(&platform_id).map(|l| l.username)
+}
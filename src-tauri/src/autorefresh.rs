@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{ai_window_manager, platforms};
+
+const TICK: Duration = Duration::from_secs(15);
+/// Shorter intervals are raised to this; a page reloading faster than it can
+/// be read only burns CPU.
+const MIN_INTERVAL: Duration = Duration::from_secs(30);
+/// A visible tab counts as unattended once the user has been idle this long.
+#[cfg(desktop)]
+const IDLE_AFTER_SECONDS: u64 = 60;
+
+/// platform id -> when its webview was last reloaded (or first seen).
+static LAST_RELOAD: Mutex<Option<HashMap<String, Instant>>> = Mutex::new(None);
+
+/// Whether `webview` is on screen with someone looking at it; those are
+/// never reloaded out from under the user.
+fn in_use(app: &AppHandle, platform_id: &str, webview: &tauri::Webview) -> bool {
+    #[cfg(desktop)]
+    if crate::idle::system_idle_seconds().is_some_and(|idle| idle >= IDLE_AFTER_SECONDS) {
+        return false;
+    }
+    if !webview.window().is_visible().unwrap_or(false) || webview.window().is_minimized().unwrap_or(false) {
+        return false;
+    }
+    // Detached and picture-in-picture tabs stay on screen regardless of the active tab.
+    !ai_window_manager::is_main_child(webview)
+        || ai_window_manager::active_platform(app).as_deref() == Some(platform_id)
+}
+
+fn tick(app: &AppHandle) {
+    if crate::flight::active(app) || crate::lock::is_locked(app) {
+        return;
+    }
+    let mut last = LAST_RELOAD.lock().unwrap();
+    let last = last.get_or_insert_with(HashMap::new);
+    let mut seen = Vec::new();
+    for platform in platforms::load(app) {
+        let Some(seconds) = platform.refresh_interval.filter(|s| *s > 0) else { continue };
+        let Some(webview) = ai_window_manager::get_webview(app, &platform.id) else { continue };
+        seen.push(platform.id.clone());
+        let interval = Duration::from_secs(seconds).max(MIN_INTERVAL);
+        let since = *last.entry(platform.id.clone()).or_insert_with(Instant::now);
+        if since.elapsed() < interval || in_use(app, &platform.id, &webview) {
+            continue;
+        }
+        tracing::debug!("auto-refreshing '{}'", platform.id);
+        let _ = webview.eval("window.location.reload()");
+        last.insert(platform.id, Instant::now());
+    }
+    // Closed tabs and cleared intervals start a fresh countdown next time.
+    last.retain(|id, _| seen.contains(id));
+}
+
+/// Reload webviews of platforms with a refresh interval while nobody is
+/// looking at them. The platform list is re-read each tick, so interval
+/// changes apply without a restart.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(TICK);
+        tick(&app);
+    });
+}
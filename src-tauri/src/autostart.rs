@@ -0,0 +1,27 @@
+use tauri::AppHandle;
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+
+/// Launch-at-login entries start the app with `--hidden` so sessions load in
+/// the background without a window popping up.
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec!["--hidden"]))
+}
+
+#[tauri::command]
+pub fn enable_autostart(app: AppHandle) -> Result<(), String> {
+    app.autolaunch().enable().map_err(|e| e.to_string())?;
+    tracing::info!("enabled");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn disable_autostart(app: AppHandle) -> Result<(), String> {
+    app.autolaunch().disable().map_err(|e| e.to_string())?;
+    tracing::info!("disabled");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_autostart_enabled(app: AppHandle) -> Result<bool, String> {
+    app.autolaunch().is_enabled().map_err(|e| e.to_string())
+}
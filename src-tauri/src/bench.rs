@@ -0,0 +1,98 @@
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::{ai_window_manager, bridge, platforms};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Resolves with the page URL once the document has loaded and a frame has been
+/// painted; rejects while it's still loading so the caller retries.
+const PAINTED_JS: &str = "document.readyState === 'complete' \
+    ? new Promise(function(r) { requestAnimationFrame(function() { requestAnimationFrame(function() { r(location.href); }); }); }) \
+    : Promise.reject('loading')";
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TabSwitchSample {
+    pub platform_id: String,
+    /// Whether the webview had to be created rather than re-shown.
+    pub cold: bool,
+    /// Time spent inside `create_or_show_webview`.
+    pub show_ms: u64,
+    /// Invocation to loaded-and-painted.
+    pub total_ms: u64,
+    pub url: Option<String>,
+    pub app_version: String,
+    pub recorded_at: u64,
+}
+
+/// Samples are appended to `benchmarks/tab_switch.jsonl` so runs from different
+/// releases can be compared.
+fn record(app: &AppHandle, sample: &TabSwitchSample) {
+    if crate::guest::active() {
+        return;
+    }
+    let Ok(dir) = app.path().app_local_data_dir().map(|d| d.join("benchmarks")) else { return };
+    let _ = std::fs::create_dir_all(&dir);
+    let line = match serde_json::to_string(sample) {
+        Ok(line) => line,
+        Err(_) => return,
+    };
+    match OpenOptions::new().create(true).append(true).open(dir.join("tab_switch.jsonl")) {
+        Ok(mut file) => {
+            let _ = writeln!(file, "{}", line);
+        }
+        Err(e) => tracing::warn!("failed to record benchmark: {}", e),
+    }
+}
+
+/// Switch to `platform_id` and time it until the page has loaded and painted.
+/// Internal tooling for tracking webview manager performance across releases.
+#[tauri::command]
+pub async fn measure_tab_switch(app: AppHandle, platform_id: String) -> Result<TabSwitchSample, String> {
+    let platform = platforms::load(&app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .ok_or_else(|| format!("Unknown platform '{}'", platform_id))?;
+    let cold = ai_window_manager::get_webview(&app, &platform_id).is_none();
+
+    let started = Instant::now();
+    ai_window_manager::create_or_show_webview(app.clone(), platform_id.clone(), platform.url, 0.0)?;
+    let show_ms = started.elapsed().as_millis() as u64;
+
+    let url = loop {
+        match bridge::eval_with_result(&app, &platform_id, PAINTED_JS, ATTEMPT_TIMEOUT).await {
+            Ok(url) => break url.as_str().map(str::to_string),
+            Err(_) if started.elapsed() < TIMEOUT => sleep(Duration::from_millis(50)).await,
+            Err(e) => return Err(format!("Tab switch did not finish: {}", e)),
+        }
+    };
+
+    let sample = TabSwitchSample {
+        platform_id,
+        cold,
+        show_ms,
+        total_ms: started.elapsed().as_millis() as u64,
+        url,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        recorded_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0),
+    };
+    tracing::info!(
+        "tab switch to '{}' ({}): show {}ms, painted {}ms",
+        sample.platform_id,
+        if sample.cold { "cold" } else { "warm" },
+        sample.show_ms,
+        sample.total_ms
+    );
+    record(&app, &sample);
+    Ok(sample)
+}
+
+/// Off-thread sleep so polling doesn't block an async runtime worker.
+async fn sleep(duration: Duration) {
+    let _ = tauri::async_runtime::spawn_blocking(move || std::thread::sleep(duration)).await;
+}
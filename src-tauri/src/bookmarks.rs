@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_window_manager;
+
+/// A saved conversation permalink (or any page) in one of the platforms.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Bookmark {
+    pub id: String,
+    pub platform_id: String,
+    pub url: String,
+    pub title: String,
+    /// `None` keeps the bookmark at the top level.
+    pub folder_id: Option<String>,
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BookmarkFolder {
+    pub id: String,
+    pub name: String,
+}
+
+/// Contents of `bookmarks.json`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Bookmarks {
+    pub folders: Vec<BookmarkFolder>,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Loaded lazily from disk.
+#[derive(Default)]
+pub struct BookmarksState {
+    data: Mutex<Option<Bookmarks>>,
+}
+
+fn bookmarks_file_path(app: &AppHandle) -> PathBuf {
+    let dir = crate::workspaces::dir(app);
+    dir.join("bookmarks.json")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn new_id() -> String {
+    use rand::distributions::Alphanumeric;
+    use rand::Rng;
+    rand::thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect()
+}
+
+fn with_bookmarks<R>(app: &AppHandle, f: impl FnOnce(&mut Bookmarks) -> R) -> R {
+    let state = app.state::<BookmarksState>();
+    let mut data = state.data.lock().unwrap();
+    let data = data.get_or_insert_with(|| {
+        crate::persist::read_to_string(&bookmarks_file_path(app))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    });
+    f(data)
+}
+
+/// Write to disk and let the frontend refresh its bookmark list.
+fn save(app: &AppHandle) {
+    let Ok(json) = with_bookmarks(app, |data| serde_json::to_string_pretty(data)) else { return };
+    if crate::persist::write(bookmarks_file_path(app), json) {
+        let _ = app.emit("bookmarks_changed", ());
+    }
+}
+
+/// Drop the loaded bookmarks so the next access reads the active workspace's file.
+pub(crate) fn unload(app: &AppHandle) {
+    *app.state::<BookmarksState>().data.lock().unwrap() = None;
+}
+
+fn check_folder(data: &Bookmarks, folder_id: Option<&str>) -> Result<(), String> {
+    match folder_id {
+        Some(id) if !data.folders.iter().any(|f| f.id == id) => Err(format!("Folder '{}' not found", id)),
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn list_bookmarks(app: AppHandle) -> Bookmarks {
+    with_bookmarks(&app, |data| data.clone())
+}
+
+/// Bookmark `url` in `platform_id`. Bookmarking the same page twice returns
+/// the existing bookmark.
+#[tauri::command]
+pub fn add_bookmark(
+    app: AppHandle,
+    platform_id: String,
+    url: String,
+    title: String,
+    folder_id: Option<String>,
+) -> Result<Bookmark, String> {
+    url::Url::parse(&url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    let bookmark = with_bookmarks(&app, |data| {
+        check_folder(data, folder_id.as_deref())?;
+        if let Some(existing) = data.bookmarks.iter().find(|b| b.platform_id == platform_id && b.url == url) {
+            return Ok(existing.clone());
+        }
+        let title = if title.trim().is_empty() { url.clone() } else { title.trim().to_string() };
+        let bookmark = Bookmark { id: new_id(), platform_id, url, title, folder_id, created_at: now_millis() };
+        data.bookmarks.push(bookmark.clone());
+        Ok::<_, String>(bookmark)
+    })?;
+    save(&app);
+    Ok(bookmark)
+}
+
+fn update_bookmark(app: &AppHandle, id: &str, f: impl FnOnce(&mut Bookmark)) -> Result<(), String> {
+    with_bookmarks(app, |data| {
+        let bookmark = data.bookmarks.iter_mut().find(|b| b.id == id).ok_or_else(|| format!("Bookmark '{}' not found", id))?;
+        f(bookmark);
+        Ok::<_, String>(())
+    })?;
+    save(app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn rename_bookmark(app: AppHandle, id: String, title: String) -> Result<(), String> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Bookmark title is empty".to_string());
+    }
+    update_bookmark(&app, &id, |b| b.title = title)
+}
+
+/// Move a bookmark into `folder_id`, or to the top level when it's omitted.
+#[tauri::command]
+pub fn move_bookmark(app: AppHandle, id: String, folder_id: Option<String>) -> Result<(), String> {
+    with_bookmarks(&app, |data| check_folder(data, folder_id.as_deref()))?;
+    update_bookmark(&app, &id, |b| b.folder_id = folder_id)
+}
+
+#[tauri::command]
+pub fn remove_bookmark(app: AppHandle, id: String) -> Result<(), String> {
+    with_bookmarks(&app, |data| {
+        let before = data.bookmarks.len();
+        data.bookmarks.retain(|b| b.id != id);
+        (data.bookmarks.len() != before).then_some(()).ok_or_else(|| format!("Bookmark '{}' not found", id))
+    })?;
+    save(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn create_bookmark_folder(app: AppHandle, name: String) -> Result<BookmarkFolder, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Folder name is empty".to_string());
+    }
+    let folder = BookmarkFolder { id: new_id(), name };
+    with_bookmarks(&app, |data| data.folders.push(folder.clone()));
+    save(&app);
+    Ok(folder)
+}
+
+#[tauri::command]
+pub fn rename_bookmark_folder(app: AppHandle, id: String, name: String) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Folder name is empty".to_string());
+    }
+    with_bookmarks(&app, |data| {
+        let folder = data.folders.iter_mut().find(|f| f.id == id).ok_or_else(|| format!("Folder '{}' not found", id))?;
+        folder.name = name;
+        Ok::<_, String>(())
+    })?;
+    save(&app);
+    Ok(())
+}
+
+/// Delete a folder. Its bookmarks move to the top level rather than being lost.
+#[tauri::command]
+pub fn delete_bookmark_folder(app: AppHandle, id: String) -> Result<(), String> {
+    with_bookmarks(&app, |data| {
+        let before = data.folders.len();
+        data.folders.retain(|f| f.id != id);
+        if data.folders.len() == before {
+            return Err(format!("Folder '{}' not found", id));
+        }
+        for bookmark in data.bookmarks.iter_mut().filter(|b| b.folder_id.as_deref() == Some(id.as_str())) {
+            bookmark.folder_id = None;
+        }
+        Ok(())
+    })?;
+    save(&app);
+    Ok(())
+}
+
+/// Open a bookmark in its platform's tab. Bookmarks whose platform has since
+/// been removed open in a new temporary tab.
+#[tauri::command]
+pub fn open_bookmark(app: AppHandle, id: String) -> Result<(), String> {
+    let bookmark = with_bookmarks(&app, |data| data.bookmarks.iter().find(|b| b.id == id).cloned())
+        .ok_or_else(|| format!("Bookmark '{}' not found", id))?;
+    let saved = crate::platforms::load(&app).iter().any(|p| p.id == bookmark.platform_id);
+    if !saved {
+        return app.emit("new_tab_request", bookmark.url).map_err(|e| e.to_string());
+    }
+    ai_window_manager::show_url(&app, &bookmark.platform_id, &bookmark.url)
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// `Webview::eval` is fire-and-forget, so scripts that need to hand a value back
+/// to Rust call the `bridge_reply` command with the request id they were given.
+/// Each id is bound to the label of the webview it was sent to, so one site
+/// can't answer (or guess) another's requests.
+#[derive(Default)]
+pub struct BridgeState {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, (String, mpsc::Sender<Result<serde_json::Value, String>>)>>,
+}
+
+/// Evaluate `expr` (a JS expression, possibly a Promise) in `platform_id`'s webview
+/// and wait up to `timeout` for its JSON-serializable result.
+pub async fn eval_with_result(
+    app: &AppHandle,
+    platform_id: &str,
+    expr: &str,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    let webview = crate::ai_window_manager::get_webview(app, platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    let state = app.state::<BridgeState>();
+    let id = state.next_id.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = mpsc::channel();
+    state.pending.lock().unwrap().insert(id, (webview.label().to_string(), tx));
+
+    let script = format!(
+        r#"
+        (function() {{
+            var reply = function(value, error) {{
+                window.__TAURI_INTERNALS__.invoke('bridge_reply', {{ id: {id}, value: value === undefined ? null : value, error: error || null }});
+            }};
+            Promise.resolve()
+                .then(function() {{ return {expr}; }})
+                .then(function(v) {{ reply(v, null); }}, function(e) {{ reply(null, String(e)); }});
+        }})();
+        "#,
+        id = id,
+        expr = expr,
+    );
+    if let Err(e) = webview.eval(&script) {
+        state.pending.lock().unwrap().remove(&id);
+        return Err(e.to_string());
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || rx.recv_timeout(timeout))
+        .await
+        .map_err(|e| e.to_string())?;
+    state.pending.lock().unwrap().remove(&id);
+    match result {
+        Ok(reply) => reply,
+        Err(_) => Err(format!("Timed out waiting for '{}'", platform_id)),
+    }
+}
+
+#[tauri::command]
+pub fn bridge_reply(
+    app: AppHandle,
+    webview: tauri::Webview,
+    id: u64,
+    value: serde_json::Value,
+    error: Option<String>,
+) -> Result<(), String> {
+    let state = app.state::<BridgeState>();
+    let tx = {
+        let mut pending = state.pending.lock().unwrap();
+        match pending.get(&id) {
+            Some((label, _)) if label != webview.label() => {
+                tracing::warn!("'{}' replied to request {} sent to '{}'", webview.label(), id, label);
+                return Err(format!("Request {} was not sent to this webview", id));
+            }
+            Some(_) => pending.remove(&id).map(|(_, tx)| tx),
+            None => None,
+        }
+    };
+    if let Some(tx) = tx {
+        let _ = tx.send(match error {
+            Some(e) => Err(e),
+            None => Ok(value),
+        });
+    }
+    Ok(())
+}
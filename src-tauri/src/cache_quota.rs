@@ -0,0 +1,144 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+use crate::{ai_window_manager, platforms, settings};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Directory names the engines use for disposable HTTP/code/GPU caches.
+/// Cookies, local storage and IndexedDB live elsewhere and are never touched.
+const CACHE_DIR_NAMES: &[&str] = &[
+    "Cache",
+    "Code Cache",
+    "GPUCache",
+    "GrShaderCache",
+    "ShaderCache",
+    "CacheStorage",
+    "ScriptCache",
+    "WebKitCache",
+    "NetworkCache",
+];
+
+/// `"cacheQuota"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct CacheQuotaSettings {
+    /// Per-store cache limit in megabytes; 0 disables trimming.
+    max_mb: u64,
+    /// Platform id -> limit in megabytes, overriding `max_mb`.
+    overrides: HashMap<String, u64>,
+}
+
+impl Default for CacheQuotaSettings {
+    fn default() -> Self {
+        Self { max_mb: 1024, overrides: HashMap::new() }
+    }
+}
+
+fn is_cache_path(store: &Path, path: &Path) -> bool {
+    path.strip_prefix(store)
+        .map(|rel| rel.components().any(|c| CACHE_DIR_NAMES.iter().any(|n| c.as_os_str() == *n)))
+        .unwrap_or(false)
+}
+
+/// Every cache file under `store` with its size and modification time.
+fn cache_files(store: &Path, dir: &Path, out: &mut Vec<(PathBuf, u64, SystemTime)>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else { continue };
+        if meta.is_dir() {
+            cache_files(store, &path, out);
+        } else if is_cache_path(store, &path) {
+            out.push((path, meta.len(), meta.modified().unwrap_or(SystemTime::UNIX_EPOCH)));
+        }
+    }
+}
+
+/// Delete the least recently modified cache files in `store` until it fits in
+/// `limit` bytes. Returns the number of bytes freed.
+fn trim_store(store: &Path, limit: u64) -> u64 {
+    let mut files = Vec::new();
+    cache_files(store, store, &mut files);
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= limit {
+        return 0;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    let mut freed = 0;
+    for (path, len, _) in files {
+        if total <= limit {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= len;
+            freed += len;
+        }
+    }
+    freed
+}
+
+/// Trim every store under `webdata/` to its quota, skipping any that back a
+/// live webview (tabs, hidden or not, detached windows, picture-in-picture).
+/// Returns the number of bytes freed.
+fn trim_all(app: &AppHandle) -> u64 {
+    let cfg: CacheQuotaSettings = settings::section(app, "cacheQuota");
+    let root = crate::workspaces::dir(app).join("webdata");
+    // Several platforms can share a store (same host); the largest override wins.
+    let mut limits: HashMap<String, u64> = HashMap::new();
+    for platform in platforms::load(app) {
+        let key = ai_window_manager::data_store_key(&platform.url);
+        if let Some(mb) = cfg.overrides.get(&platform.id) {
+            let limit = limits.entry(key).or_insert(0);
+            *limit = (*limit).max(*mb);
+        }
+    }
+
+    let live: HashSet<String> = app
+        .webviews()
+        .values()
+        .filter(|w| w.label() != "main")
+        .filter_map(|w| w.url().ok())
+        .map(|u| ai_window_manager::data_store_key(u.as_str()))
+        .collect();
+
+    let Ok(entries) = fs::read_dir(&root) else { return 0 };
+    let mut freed = 0;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let key = entry.file_name().to_string_lossy().into_owned();
+        if live.contains(&key) {
+            continue;
+        }
+        let mb = limits.get(&key).copied().unwrap_or(cfg.max_mb);
+        if mb == 0 {
+            continue;
+        }
+        let store_freed = trim_store(&entry.path(), mb * 1024 * 1024);
+        if store_freed > 0 {
+            tracing::info!("trimmed {} KB of cache from webdata/{}", store_freed / 1024, key);
+        }
+        freed += store_freed;
+    }
+    freed
+}
+
+/// Enforce cache quotas now and then hourly.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        trim_all(&app);
+        std::thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+/// Trim caches immediately, e.g. after the user lowers a quota. Returns bytes freed.
+#[tauri::command]
+pub async fn trim_web_caches(app: AppHandle) -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(move || trim_all(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
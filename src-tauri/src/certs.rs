@@ -0,0 +1,191 @@
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Webview};
+
+use crate::{ai_window_manager, persist};
+
+/// Accepted certificates, host -> SHA-256 fingerprints of the leaf certificate.
+type Overrides = HashMap<String, Vec<String>>;
+
+fn overrides_file_path(app: &AppHandle) -> PathBuf {
+    let dir = crate::workspaces::dir(app);
+    dir.join("cert_overrides.json")
+}
+
+fn load_overrides(app: &AppHandle) -> Overrides {
+    persist::read_to_string(&overrides_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_overrides(app: &AppHandle, overrides: &Overrides) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(overrides).map_err(|e| e.to_string())?;
+    persist::write(overrides_file_path(app), json);
+    Ok(())
+}
+
+/// Lowercase hex SHA-256 of the DER certificate inside `pem`.
+fn fingerprint(pem: &str) -> Option<String> {
+    let body: String = pem
+        .lines()
+        .skip_while(|l| !l.starts_with("-----BEGIN CERTIFICATE"))
+        .skip(1)
+        .take_while(|l| !l.starts_with("-----END CERTIFICATE"))
+        .collect();
+    let der = base64::engine::general_purpose::STANDARD.decode(body.trim()).ok()?;
+    Some(Sha256::digest(der).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn is_accepted(app: &AppHandle, host: &str, fingerprint: &str) -> bool {
+    load_overrides(app).get(host).is_some_and(|fps| fps.iter().any(|f| f == fingerprint))
+}
+
+/// The tab's platform opted into self-signed certificates and `host` is the
+/// platform's own, not some third party it loads from.
+fn allows_self_signed(app: &AppHandle, label: &str, host: &str) -> bool {
+    let platform_id = ai_window_manager::platform_for_label(app, label);
+    crate::platforms::load(app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .is_some_and(|p| p.allow_self_signed && host_of(&p.url) == host)
+}
+
+/// Tell the frontend a child webview hit a certificate it doesn't trust, with
+/// enough detail for the user to decide whether to accept it.
+fn report(app: &AppHandle, label: &str, url: &str, host: &str, fingerprint: Option<&str>, error: &str) {
+    let platform_id = ai_window_manager::platform_for_label(app, label);
+    tracing::warn!("certificate error on '{}' for {}: {}", platform_id, host, error);
+    let _ = app.emit(
+        "certificate_error",
+        serde_json::json!({
+            "platformId": platform_id,
+            "url": url,
+            "host": host,
+            "fingerprint": fingerprint,
+            "error": error,
+        }),
+    );
+}
+
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Hook the engine's TLS failure callback on a newly created child webview.
+/// WKWebView's authentication challenges belong to wry's navigation delegate,
+/// so macOS keeps the engine's default behaviour.
+pub fn watch(app: &AppHandle, webview: &Webview) {
+    #[cfg(target_os = "linux")]
+    watch_webkitgtk(app, webview);
+    #[cfg(windows)]
+    watch_webview2(app, webview);
+    #[cfg(not(any(target_os = "linux", windows)))]
+    let _ = (app, webview);
+}
+
+#[cfg(target_os = "linux")]
+fn watch_webkitgtk(app: &AppHandle, webview: &Webview) {
+    let app = app.clone();
+    let label = webview.label().to_string();
+    webview
+        .with_webview(move |wv| {
+            use gio::prelude::TlsCertificateExt;
+            use webkit2gtk::{WebContextExt, WebViewExt};
+            wv.inner().connect_load_failed_with_tls_errors(move |view, uri, certificate, errors| {
+                let host = host_of(uri);
+                let fp = certificate.certificate_pem().and_then(|pem| fingerprint(&pem));
+                let allowed = |fp: &&str| is_accepted(&app, &host, fp) || allows_self_signed(&app, &label, &host);
+                if let Some(fp) = fp.as_deref().filter(allowed) {
+                    tracing::info!("allowing accepted certificate {} for {}", fp, host);
+                    if let Some(context) = view.context() {
+                        context.allow_tls_certificate_for_host(certificate, &host);
+                    }
+                    view.load_uri(uri);
+                    return true;
+                }
+                report(&app, &label, uri, &host, fp.as_deref(), &format!("{:?}", errors));
+                false
+            });
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+#[cfg(windows)]
+fn watch_webview2(app: &AppHandle, webview: &Webview) {
+    let app = app.clone();
+    let label = webview.label().to_string();
+    webview
+        .with_webview(move |wv| unsafe {
+            use webview2_com::Microsoft::Web::WebView2::Win32::*;
+            use webview2_com::{take_pwstr, ServerCertificateErrorDetectedEventHandler};
+            use windows_core::{Interface, PWSTR};
+
+            let Ok(core) = wv.controller().CoreWebView2() else { return };
+            let Ok(core) = core.cast::<ICoreWebView2_14>() else {
+                tracing::warn!("WebView2 runtime too old for certificate error events");
+                return;
+            };
+            let handler = ServerCertificateErrorDetectedEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else { return Ok(()) };
+                let mut uri = PWSTR::null();
+                args.RequestUri(&mut uri)?;
+                let uri = take_pwstr(uri);
+                let host = host_of(&uri);
+                let mut pem = PWSTR::null();
+                args.ServerCertificate()?.ToPemEncoding(&mut pem)?;
+                let fp = fingerprint(&take_pwstr(pem));
+                if fp.as_deref().is_some_and(|fp| is_accepted(&app, &host, fp)) || allows_self_signed(&app, &label, &host) {
+                    args.SetAction(COREWEBVIEW2_SERVER_CERTIFICATE_ERROR_ACTION_ALWAYS_ALLOW)?;
+                    return Ok(());
+                }
+                let mut status = COREWEBVIEW2_WEB_ERROR_STATUS::default();
+                args.ErrorStatus(&mut status)?;
+                report(&app, &label, &uri, &host, fp.as_deref(), &format!("web error status {}", status.0));
+                Ok(())
+            }));
+            let mut token = Default::default();
+            if let Err(e) = core.add_ServerCertificateErrorDetected(&handler, &mut token) {
+                tracing::warn!("failed to watch certificate errors: {}", e);
+            }
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+/// Trust `fingerprint` for `host` from now on. The frontend reloads the tab
+/// afterwards; the override is checked when the engine reports the error again.
+#[tauri::command]
+pub fn accept_certificate(app: AppHandle, host: String, fingerprint: String) -> Result<(), String> {
+    let fingerprint = fingerprint.replace(':', "").to_lowercase();
+    if fingerprint.len() != 64 || !fingerprint.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Fingerprint must be a SHA-256 hex digest".to_string());
+    }
+    let mut overrides = load_overrides(&app);
+    let entry = overrides.entry(host.clone()).or_default();
+    if !entry.contains(&fingerprint) {
+        entry.push(fingerprint.clone());
+    }
+    save_overrides(&app, &overrides)?;
+    tracing::info!("accepted certificate {} for {}", fingerprint, host);
+    Ok(())
+}
+
+/// Drop every accepted certificate for `host`.
+#[tauri::command]
+pub fn revoke_certificate(app: AppHandle, host: String) -> Result<(), String> {
+    let mut overrides = load_overrides(&app);
+    if overrides.remove(&host).is_some() {
+        save_overrides(&app, &overrides)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_certificate_overrides(app: AppHandle) -> Overrides {
+    load_overrides(&app)
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+/// What happened to the platform list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Edited,
+    Removed,
+    Reordered,
+}
+
+/// One line of platform_changes.jsonl. `before`/`after` hold the whole
+/// platform entry (or, for reorders, the list of ids), so any change can be
+/// inspected or reverted from the log alone.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub at: u64,
+    pub kind: ChangeKind,
+    /// Absent for reorders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_id: Option<String>,
+    /// Window label that saved the list, or `"backend"` / `"onboarding"`.
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+/// Append-only; lives next to the platforms.json it describes.
+fn log_file_path(app: &AppHandle) -> PathBuf {
+    crate::workspaces::dir(app).join("platform_changes.jsonl")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn entries(data: Option<&str>) -> Vec<Value> {
+    data.and_then(|d| serde_json::from_str::<Vec<Value>>(d).ok()).unwrap_or_default()
+}
+
+fn id_of(entry: &Value) -> Option<&str> {
+    entry.get("id").and_then(Value::as_str)
+}
+
+/// Compare two versions of platforms.json.
+fn diff(before: Option<&str>, after: &str, source: &str) -> Vec<Change> {
+    let (old, new) = (entries(before), entries(Some(after)));
+    let at = now_millis();
+    let change = |kind, platform_id: &str, before: Option<&Value>, after: Option<&Value>| Change {
+        at,
+        kind,
+        platform_id: Some(platform_id.to_string()),
+        source: source.to_string(),
+        before: before.cloned(),
+        after: after.cloned(),
+    };
+    let find = |list: &'_ [Value], id: &str| list.iter().position(|e| id_of(e) == Some(id));
+
+    let mut changes = Vec::new();
+    for entry in &new {
+        let Some(id) = id_of(entry) else { continue };
+        match find(&old, id) {
+            None => changes.push(change(ChangeKind::Added, id, None, Some(entry))),
+            Some(i) if old[i] != *entry => changes.push(change(ChangeKind::Edited, id, Some(&old[i]), Some(entry))),
+            Some(_) => {}
+        }
+    }
+    for entry in &old {
+        let Some(id) = id_of(entry) else { continue };
+        if find(&new, id).is_none() {
+            changes.push(change(ChangeKind::Removed, id, Some(entry), None));
+        }
+    }
+    // Order among the platforms present in both versions.
+    let kept = |list: &[Value], other: &[Value]| -> Vec<Value> {
+        list.iter()
+            .filter_map(id_of)
+            .filter(|id| find(other, id).is_some())
+            .map(|id| Value::String(id.to_string()))
+            .collect()
+    };
+    let (old_order, new_order) = (kept(&old, &new), kept(&new, &old));
+    if old_order != new_order {
+        changes.push(Change {
+            at,
+            kind: ChangeKind::Reordered,
+            platform_id: None,
+            source: source.to_string(),
+            before: Some(Value::Array(old_order)),
+            after: Some(Value::Array(new_order)),
+        });
+    }
+    changes
+}
+
+/// Append whatever changed between two versions of platforms.json. Called
+/// from inside the persistence writer, so entries land in write order.
+pub fn record(app: &AppHandle, before: Option<&str>, after: &str, source: &str) {
+    if crate::guest::active() {
+        return;
+    }
+    let changes = diff(before, after, source);
+    if changes.is_empty() {
+        return;
+    }
+    let path = log_file_path(app);
+    let appended = std::fs::OpenOptions::new().create(true).append(true).open(&path).and_then(|mut file| {
+        let mut lines = String::new();
+        for change in &changes {
+            lines.push_str(&serde_json::to_string(change).unwrap_or_default());
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes())
+    });
+    if let Err(e) = appended {
+        tracing::warn!("failed to append to {}: {}", path.display(), e);
+    }
+}
+
+/// Platform list changes, newest first, optionally for one platform (which
+/// leaves out reorders).
+#[tauri::command]
+pub fn get_change_history(app: AppHandle, platform_id: Option<String>, limit: Option<usize>) -> Vec<Change> {
+    let Ok(file) = std::fs::File::open(log_file_path(&app)) else {
+        return Vec::new();
+    };
+    let mut changes: Vec<Change> = std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .filter(|c: &Change| platform_id.is_none() || c.platform_id == platform_id)
+        .collect();
+    changes.reverse();
+    changes.truncate(limit.unwrap_or(200));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn kinds(changes: &[Change]) -> Vec<(ChangeKind, Option<&str>)> {
+        changes.iter().map(|c| (c.kind.clone(), c.platform_id.as_deref())).collect()
+    }
+
+    #[test]
+    fn first_save_adds_everything() {
+        let after = json!([{ "id": "a" }, { "id": "b" }]).to_string();
+        let changes = diff(None, &after, "main");
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Added, Some("a")), (ChangeKind::Added, Some("b"))]);
+        assert!(changes.iter().all(|c| c.source == "main" && c.before.is_none()));
+        assert_eq!(changes[0].after, Some(json!({ "id": "a" })));
+    }
+
+    #[test]
+    fn unchanged_list_has_no_changes() {
+        let list = json!([{ "id": "a", "name": "A" }, { "id": "b" }]).to_string();
+        assert!(diff(Some(&list), &list, "main").is_empty());
+    }
+
+    #[test]
+    fn edit_keeps_both_versions() {
+        let before = json!([{ "id": "a", "name": "Old" }]).to_string();
+        let after = json!([{ "id": "a", "name": "New" }]).to_string();
+        let changes = diff(Some(&before), &after, "backend");
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Edited, Some("a"))]);
+        assert_eq!(changes[0].before, Some(json!({ "id": "a", "name": "Old" })));
+        assert_eq!(changes[0].after, Some(json!({ "id": "a", "name": "New" })));
+    }
+
+    #[test]
+    fn removal_keeps_the_old_entry() {
+        let before = json!([{ "id": "a" }, { "id": "b", "url": "https://b" }]).to_string();
+        let after = json!([{ "id": "a" }]).to_string();
+        let changes = diff(Some(&before), &after, "main");
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Removed, Some("b"))]);
+        assert_eq!(changes[0].before, Some(json!({ "id": "b", "url": "https://b" })));
+        assert_eq!(changes[0].after, None);
+    }
+
+    #[test]
+    fn reorder_lists_ids_in_both_orders() {
+        let before = json!([{ "id": "a" }, { "id": "b" }, { "id": "c" }]).to_string();
+        let after = json!([{ "id": "c" }, { "id": "a" }, { "id": "b" }]).to_string();
+        let changes = diff(Some(&before), &after, "main");
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Reordered, None)]);
+        assert_eq!(changes[0].before, Some(json!(["a", "b", "c"])));
+        assert_eq!(changes[0].after, Some(json!(["c", "a", "b"])));
+    }
+
+    #[test]
+    fn additions_and_removals_alone_are_not_a_reorder() {
+        let before = json!([{ "id": "a" }, { "id": "b" }, { "id": "c" }]).to_string();
+        let after = json!([{ "id": "new" }, { "id": "a" }, { "id": "c" }]).to_string();
+        let changes = diff(Some(&before), &after, "main");
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Added, Some("new")), (ChangeKind::Removed, Some("b"))]);
+    }
+
+    #[test]
+    fn skips_entries_without_id_and_treats_malformed_json_as_empty() {
+        let after = json!([{ "name": "no id" }, { "id": "a" }]).to_string();
+        assert_eq!(kinds(&diff(Some("not json"), &after, "main")), vec![(ChangeKind::Added, Some("a"))]);
+        assert_eq!(kinds(&diff(Some(&after), "{}", "main")), vec![(ChangeKind::Removed, Some("a"))]);
+    }
+}
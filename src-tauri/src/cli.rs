@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+
+use crate::deeplink::{self, DeepLink};
+use crate::mcp;
+
+/// Must match `identifier` in tauri.conf.json; used to find the data directory
+/// for commands that run without starting the app.
+pub(crate) const APP_IDENTIFIER: &str = "com.yingzhang.brainer";
+
+const USAGE: &str = "\
+Usage: anybrain [OPTIONS] [anybrain://...]
+
+Options:
+  --platform <ID|NAME>   Open (or focus) a platform
+  --prompt <TEXT>        Type TEXT into the platform's prompt box
+  --send                 Submit the prompt after typing it
+  --share-file <PATH>    Share a text file's contents (used by Send To)
+  --hidden               Start without showing the main window
+  --guest                Browse with throwaway sessions and save nothing to disk
+  --list-platforms       Print saved platforms and exit
+  --export-config        Print platforms and settings as JSON and exit
+  --mcp                  Serve the Model Context Protocol on stdio (needs the HTTP API enabled)
+  -h, --help             Print this help and exit";
+
+#[derive(Debug, Default, Clone)]
+pub struct CliArgs {
+    pub platform: Option<String>,
+    pub prompt: Option<String>,
+    pub send: bool,
+    pub share_file: Option<PathBuf>,
+    pub hidden: bool,
+    pub guest: bool,
+    pub list_platforms: bool,
+    pub export_config: bool,
+    pub mcp: bool,
+    pub help: bool,
+    pub deep_link: Option<String>,
+}
+
+/// Parse arguments (without the program name). Unknown flags are ignored with a
+/// warning because the OS sometimes appends its own (e.g. `-psn_*` on macOS).
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<CliArgs, String> {
+    let mut out = CliArgs::default();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| iter.next())
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match flag.as_str() {
+            "--platform" => out.platform = Some(value("--platform")?),
+            "--prompt" => out.prompt = Some(value("--prompt")?),
+            "--send" => out.send = true,
+            "--share-file" => out.share_file = Some(PathBuf::from(value("--share-file")?)),
+            "--hidden" => out.hidden = true,
+            "--guest" => out.guest = true,
+            "--list-platforms" => out.list_platforms = true,
+            "--export-config" => out.export_config = true,
+            "--mcp" => out.mcp = true,
+            "-h" | "--help" => out.help = true,
+            _ if deeplink::is_deep_link_arg(&arg) => out.deep_link = Some(arg),
+            _ => tracing::warn!("ignoring unknown argument '{}'", arg),
+        }
+    }
+    if out.prompt.is_some() && out.platform.is_none() {
+        return Err("--prompt requires --platform".to_string());
+    }
+    Ok(out)
+}
+
+pub(crate) fn data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+}
+
+/// Read a JSON file (platforms.json, settings.json) of the active workspace.
+pub(crate) fn read_json(name: &str, fallback: serde_json::Value) -> serde_json::Value {
+    std::fs::read_to_string(crate::workspaces::dir_in(&data_dir()).join(name))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or(fallback)
+}
+
+/// Handle the informational flags that print and exit without a window.
+/// Returns true when the process should exit.
+pub fn run_standalone(args: &CliArgs) -> bool {
+    if args.help {
+        println!("{}", USAGE);
+        return true;
+    }
+    if args.list_platforms {
+        let platforms = read_json("platforms.json", serde_json::json!([]));
+        for p in platforms.as_array().into_iter().flatten() {
+            let field = |k: &str| p.get(k).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let hidden = if p.get("hidden").and_then(|v| v.as_bool()).unwrap_or(false) { " (hidden)" } else { "" };
+            println!("{}\t{}\t{}{}", field("id"), field("name"), field("url"), hidden);
+        }
+        return true;
+    }
+    if args.export_config {
+        let config = serde_json::json!({
+            "platforms": read_json("platforms.json", serde_json::json!([])),
+            "settings": read_json("settings.json", serde_json::json!({})),
+        });
+        println!("{}", serde_json::to_string_pretty(&config).unwrap_or_default());
+        return true;
+    }
+    if args.mcp {
+        let settings = read_json("settings.json", serde_json::json!({}));
+        let port = settings["httpApi"]["port"].as_u64().unwrap_or(47821) as u16;
+        match std::fs::read_to_string(data_dir().join("api_token")) {
+            Ok(token) => mcp::run_stdio(port, token.trim().to_string()),
+            Err(_) => tracing::warn!("no API token found; enable the HTTP API in AnyBrain first"),
+        }
+        return true;
+    }
+    false
+}
+
+/// Apply window-level arguments, either at startup or when forwarded from a
+/// second launch by the single-instance handler.
+pub fn apply(app: &tauri::AppHandle, args: &CliArgs) {
+    if args.guest {
+        crate::guest::enter_guest_mode(app.clone());
+    }
+    if let Some(link) = &args.deep_link {
+        deeplink::handle(app, link);
+    }
+    if let Some(path) = &args.share_file {
+        let result = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+            .and_then(|text| crate::share::receive(app, text, args.platform.clone()));
+        if let Err(e) = result {
+            tracing::warn!("{}", e);
+        }
+        return;
+    }
+    if args.platform.is_some() {
+        let link = DeepLink::Open {
+            platform: args.platform.clone(),
+            prompt: args.prompt.clone(),
+            send: args.send,
+        };
+        if let Err(e) = deeplink::dispatch(app, link) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<CliArgs, String> {
+        parse(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_separate_and_inline_values() {
+        let parsed = args(&["--platform", "claude", "--prompt=hello world", "--send", "--share-file", "/tmp/a.txt"]).unwrap();
+        assert_eq!(parsed.platform.as_deref(), Some("claude"));
+        assert_eq!(parsed.prompt.as_deref(), Some("hello world"));
+        assert!(parsed.send);
+        assert_eq!(parsed.share_file, Some(PathBuf::from("/tmp/a.txt")));
+    }
+
+    #[test]
+    fn inline_value_keeps_later_equals_signs() {
+        let parsed = args(&["--platform=chatgpt", "--prompt=a=b"]).unwrap();
+        assert_eq!(parsed.prompt.as_deref(), Some("a=b"));
+    }
+
+    #[test]
+    fn parses_boolean_flags() {
+        let parsed = args(&["--hidden", "--guest", "--list-platforms", "--export-config", "--mcp", "-h"]).unwrap();
+        assert!(parsed.hidden && parsed.guest && parsed.list_platforms && parsed.export_config && parsed.mcp && parsed.help);
+        assert!(args(&["--help"]).unwrap().help);
+    }
+
+    #[test]
+    fn missing_value_is_an_error() {
+        assert_eq!(args(&["--platform"]).unwrap_err(), "--platform requires a value");
+    }
+
+    #[test]
+    fn prompt_requires_platform() {
+        assert!(args(&["--prompt", "hi"]).is_err());
+    }
+
+    #[test]
+    fn recognizes_deep_links() {
+        let link = "anybrain://open?platform=claude&prompt=x";
+        assert_eq!(args(&[link]).unwrap().deep_link.as_deref(), Some(link));
+        assert!(args(&["\"AnyBrain://open\""]).unwrap().deep_link.is_some());
+        assert!(args(&["https://example.com"]).unwrap().deep_link.is_none());
+    }
+
+    #[test]
+    fn ignores_unknown_arguments() {
+        let parsed = args(&["-psn_0_12345", "--verbose", "--platform", "kimi"]).unwrap();
+        assert_eq!(parsed.platform.as_deref(), Some("kimi"));
+        assert!(parsed.deep_link.is_none());
+    }
+}
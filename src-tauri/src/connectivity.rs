@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{ai_window_manager, platforms};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Anycast resolvers reached by IP so a broken local DNS doesn't read as offline.
+const PROBES: &[&str] = &["1.1.1.1:443", "8.8.8.8:443", "9.9.9.9:443"];
+
+/// `online` starts true so nothing is flagged before the first probe.
+pub struct ConnectivityState {
+    online: AtomicBool,
+    /// Platforms whose last navigation happened while offline, reloaded once
+    /// the network comes back.
+    failed: Mutex<HashSet<String>>,
+}
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self { online: AtomicBool::new(true), failed: Mutex::new(HashSet::new()) }
+    }
+}
+
+pub fn is_online(app: &AppHandle) -> bool {
+    match app.try_state::<ConnectivityState>() {
+        Some(state) => state.online.load(Ordering::Relaxed),
+        None => true,
+    }
+}
+
+/// Behind a proxy, only the proxy has to be reachable.
+fn probe_addrs() -> Vec<SocketAddr> {
+    let proxy = crate::proxy::current().proxy.and_then(|p| url::Url::parse(&p).ok());
+    if let Some(url) = proxy {
+        if let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) {
+            return (host, port).to_socket_addrs().map(|a| a.collect()).unwrap_or_default();
+        }
+    }
+    PROBES.iter().filter_map(|p| p.parse().ok()).collect()
+}
+
+fn probe() -> bool {
+    probe_addrs().iter().any(|addr| TcpStream::connect_timeout(addr, PROBE_TIMEOUT).is_ok())
+}
+
+/// Called from the page-load handler. A load that starts or finishes while
+/// offline almost certainly ended on an error page.
+pub fn on_page_load(app: &AppHandle, platform_id: &str) {
+    let Some(state) = app.try_state::<ConnectivityState>() else { return };
+    let mut failed = state.failed.lock().unwrap();
+    if state.online.load(Ordering::Relaxed) {
+        failed.remove(platform_id);
+    } else {
+        failed.insert(platform_id.to_string());
+    }
+}
+
+fn reload_failed(app: &AppHandle) {
+    let failed: Vec<String> = app.state::<ConnectivityState>().failed.lock().unwrap().drain().collect();
+    for platform_id in failed {
+        let Some(webview) = ai_window_manager::get_webview(app, &platform_id) else { continue };
+        // A load that never got anywhere may have left the webview on a blank page.
+        let on_blank = webview.url().map(|u| u.scheme() == "about").unwrap_or(true);
+        let home = platforms::load(app).into_iter().find(|p| p.id == platform_id).map(|p| p.url);
+        match (on_blank, home.and_then(|u| u.parse().ok())) {
+            (true, Some(url)) => {
+                let _ = webview.navigate(url);
+            }
+            _ => {
+                let _ = webview.eval("window.location.reload()");
+            }
+        }
+        tracing::info!("reloading '{}' after reconnect", platform_id);
+    }
+}
+
+/// Probe connectivity periodically, emit `network_online` / `network_offline`
+/// on changes, and reload tabs that failed while offline once it's back.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        // Flight mode keeps the last known state rather than probing.
+        if crate::flight::active(&app) {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        let online = probe();
+        let state = app.state::<ConnectivityState>();
+        if state.online.swap(online, Ordering::Relaxed) != online {
+            tracing::info!("network {}", if online { "online" } else { "offline" });
+            let _ = app.emit(if online { "network_online" } else { "network_offline" }, serde_json::json!({}));
+            if online {
+                reload_failed(&app);
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_connectivity(app: AppHandle) -> bool {
+    is_online(&app)
+}
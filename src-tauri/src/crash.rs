@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::settings;
+
+const LOG_TAIL_LINES: usize = 200;
+
+/// `"crashReporting"` section of settings.json. Off by default; nothing is
+/// captured, let alone sent, until the user opts in.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct CrashSettings {
+    enabled: bool,
+    /// Where `send_crash_report` POSTs reports.
+    endpoint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    /// `"panic"` or `"native"`.
+    pub kind: String,
+    pub created_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: Option<String>,
+    pub log_tail: String,
+    /// File name of the minidump next to the report, for native crashes.
+    pub minidump: Option<String>,
+    #[serde(default)]
+    pub sent: bool,
+}
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn crash_dir() -> PathBuf {
+    CRASH_DIR
+        .get_or_init(|| crate::cli::data_dir().join("crashes"))
+        .clone()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn new_report(kind: &str, id: String, message: String) -> CrashReport {
+    CrashReport {
+        id,
+        kind: kind.to_string(),
+        created_at: now_millis(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        message,
+        location: None,
+        thread: None,
+        backtrace: None,
+        log_tail: crate::logging::get_recent_logs(Some(LOG_TAIL_LINES)).unwrap_or_default(),
+        minidump: None,
+        sent: false,
+    }
+}
+
+fn report_path(id: &str) -> PathBuf {
+    crash_dir().join(format!("{}.json", id))
+}
+
+fn write_report(report: &CrashReport) -> std::io::Result<()> {
+    fs::create_dir_all(crash_dir())?;
+    let json = serde_json::to_string_pretty(report).map_err(std::io::Error::other)?;
+    fs::write(report_path(&report.id), json)
+}
+
+fn read_report(id: &str) -> Result<CrashReport, String> {
+    let data = fs::read_to_string(report_path(id)).map_err(|e| format!("No crash report '{}': {}", id, e))?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let mut report = new_report("panic", format!("panic-{}", now_millis()), message);
+        report.location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        report.thread = std::thread::current().name().map(str::to_string);
+        report.backtrace = Some(std::backtrace::Backtrace::force_capture().to_string());
+        tracing::error!("panic: {} at {:?}", report.message, report.location);
+        if let Err(e) = write_report(&report) {
+            tracing::error!("failed to write crash report: {}", e);
+        }
+        previous(info);
+    }));
+}
+
+#[cfg(target_os = "linux")]
+fn dump(context: &crash_handler::CrashContext, file: &mut fs::File) -> bool {
+    minidump_writer::minidump_writer::MinidumpWriter::new(context.pid, context.tid)
+        .set_crash_context(minidump_writer::crash_context::CrashContext { inner: context.clone() })
+        .dump(file)
+        .is_ok()
+}
+
+#[cfg(target_os = "macos")]
+fn dump(context: &crash_handler::CrashContext, file: &mut fs::File) -> bool {
+    minidump_writer::minidump_writer::MinidumpWriter::with_crash_context(context.clone())
+        .dump(file)
+        .is_ok()
+}
+
+#[cfg(windows)]
+fn dump(context: &crash_handler::CrashContext, file: &mut fs::File) -> bool {
+    minidump_writer::minidump_writer::MinidumpWriter::dump_crash_context(context, None, file).is_ok()
+}
+
+/// Runs inside the signal/exception handler, so it only writes the minidump;
+/// the JSON report is assembled on the next launch by `collect_native_crashes`.
+#[cfg(desktop)]
+fn write_minidump(context: &crash_handler::CrashContext) -> bool {
+    let path = crash_dir().join(format!("native-{}.dmp", now_millis()));
+    match fs::File::create(&path) {
+        Ok(mut file) => dump(context, &mut file),
+        Err(_) => false,
+    }
+}
+
+#[cfg(desktop)]
+fn install_native_handler() {
+    let _ = fs::create_dir_all(crash_dir());
+    let handler = crash_handler::CrashHandler::attach(unsafe {
+        crash_handler::make_crash_event(|context: &crash_handler::CrashContext| {
+            crash_handler::CrashEventResult::Handled(write_minidump(context))
+        })
+    });
+    match handler {
+        // Dropping the handler detaches it; keep it for the life of the process.
+        Ok(handler) => std::mem::forget(handler),
+        Err(e) => tracing::warn!("failed to install native crash handler: {}", e),
+    }
+}
+
+/// Turn minidumps left by a previous run into reports.
+fn collect_native_crashes() {
+    let Ok(entries) = fs::read_dir(crash_dir()) else {
+        return;
+    };
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        if path.extension().and_then(|e| e.to_str()) != Some("dmp") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if report_path(&id).exists() {
+            continue;
+        }
+        let mut report = new_report("native", id, "native crash".to_string());
+        report.minidump = path.file_name().and_then(|n| n.to_str()).map(str::to_string);
+        match write_report(&report) {
+            Ok(()) => tracing::warn!("found minidump from previous run: {}", path.display()),
+            Err(e) => tracing::warn!("failed to write report for {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Install the crash handlers if the user opted in. Runs at the top of `run()`
+/// (right after logging) so early crashes are caught too, which is why it reads
+/// settings.json directly instead of through the app handle.
+pub fn init() {
+    let cfg: CrashSettings = serde_json::from_value(crate::cli::read_json("settings.json", serde_json::json!({}))["crashReporting"].clone())
+        .unwrap_or_default();
+    if !cfg.enabled {
+        return;
+    }
+    install_panic_hook();
+    #[cfg(desktop)]
+    install_native_handler();
+    collect_native_crashes();
+    tracing::info!("crash reporting enabled, reports in {}", crash_dir().display());
+}
+
+fn list_reports() -> Vec<CrashReport> {
+    let mut reports: Vec<CrashReport> = fs::read_dir(crash_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    reports
+}
+
+#[tauri::command]
+pub fn list_crash_reports() -> Vec<CrashReport> {
+    list_reports()
+}
+
+fn upload(endpoint: &str, report: &CrashReport, dir: &Path) -> Result<(), String> {
+    let agent = crate::proxy::agent_for(endpoint);
+    agent
+        .post(endpoint)
+        .send_json(report)
+        .map_err(|e| format!("Failed to send report: {}", e))?;
+    if let Some(name) = &report.minidump {
+        let bytes = fs::read(dir.join(name)).map_err(|e| e.to_string())?;
+        agent
+            .post(endpoint)
+            .set("Content-Type", "application/octet-stream")
+            .set("X-Crash-Report-Id", &report.id)
+            .send_bytes(&bytes)
+            .map_err(|e| format!("Failed to send minidump: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Upload a report (and its minidump) to the configured endpoint. Nothing is
+/// ever sent without this explicit call.
+#[tauri::command]
+pub async fn send_crash_report(app: AppHandle, id: String) -> Result<(), String> {
+    let cfg: CrashSettings = settings::section(&app, "crashReporting");
+    let endpoint = cfg.endpoint.ok_or("No crash report endpoint configured")?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut report = read_report(&id)?;
+        upload(&endpoint, &report, &crash_dir())?;
+        report.sent = true;
+        write_report(&report).map_err(|e| e.to_string())?;
+        tracing::info!("sent crash report {}", id);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn delete_crash_report(id: String) -> Result<(), String> {
+    let report = read_report(&id)?;
+    if let Some(name) = &report.minidump {
+        let _ = fs::remove_file(crash_dir().join(name));
+    }
+    fs::remove_file(report_path(&id)).map_err(|e| e.to_string())
+}
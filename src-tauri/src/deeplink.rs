@@ -0,0 +1,147 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use url::Url;
+
+use crate::platforms;
+use crate::prompt;
+
+pub const SCHEME: &str = "anybrain";
+
+/// Windows can deliver the same protocol activation twice in quick succession
+/// (e.g. a browser retrying the launch), so repeats inside this window are dropped.
+const DUPLICATE_WINDOW: Duration = Duration::from_secs(2);
+
+static LAST_HANDLED: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// Undo what shells do to the URL on its way through argv: surrounding quotes
+/// and whitespace, and the trailing slash Windows adds after the host.
+fn normalize(raw: &str) -> String {
+    let trimmed = raw.trim().trim_matches('"');
+    match trimmed.split_once('?') {
+        Some((head, query)) => format!("{}?{}", head.trim_end_matches('/'), query),
+        None => trimmed.trim_end_matches('/').to_string(),
+    }
+}
+
+fn is_duplicate(url: &str) -> bool {
+    let mut last = LAST_HANDLED.lock().unwrap();
+    let now = Instant::now();
+    let duplicate = matches!(&*last, Some((prev, at)) if prev == url && now.duration_since(*at) < DUPLICATE_WINDOW);
+    *last = Some((url.to_string(), now));
+    duplicate
+}
+
+/// Whether a command-line argument is one of our deep links. Windows passes
+/// the URL as typed, so the scheme may not be lowercase.
+pub fn is_deep_link_arg(arg: &str) -> bool {
+    arg.trim_matches('"')
+        .get(..SCHEME.len() + 1)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{}:", SCHEME)))
+}
+
+/// A parsed `anybrain://` request.
+#[derive(Debug)]
+pub enum DeepLink {
+    /// `anybrain://open?platform=claude&prompt=...`
+    Open {
+        platform: Option<String>,
+        prompt: Option<String>,
+        /// Press send after filling the prompt. Only set by local callers (CLI,
+        /// HTTP API); links can come from any web page or email.
+        send: bool,
+    },
+    /// `anybrain://open?url=https://...` — open an arbitrary page as a temporary tab.
+    OpenUrl(String),
+    /// `anybrain://share?text=...&platform=...` — hand text to the share flow.
+    Share {
+        text: String,
+        platform: Option<String>,
+    },
+}
+
+pub fn parse(raw: &str) -> Result<DeepLink, String> {
+    let url = Url::parse(raw).map_err(|e| format!("Invalid deep link '{}': {}", raw, e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Unsupported scheme '{}'", url.scheme()));
+    }
+    // `anybrain://open?...` puts the action in the host; `anybrain:open?...` in the path.
+    let action = url
+        .host_str()
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| url.path().trim_matches('/').to_string());
+
+    let param = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+            .filter(|v| !v.is_empty())
+    };
+
+    match action.as_str() {
+        "open" => {
+            if let Some(target) = param("url") {
+                return Ok(DeepLink::OpenUrl(target));
+            }
+            // Any page can open a link, so it only fills the prompt in; a
+            // `send` parameter is ignored and the user presses send themselves.
+            if param("send").is_some() {
+                tracing::info!("ignoring send in deep link; the prompt is filled in only");
+            }
+            Ok(DeepLink::Open {
+                platform: param("platform"),
+                prompt: param("prompt"),
+                send: false,
+            })
+        }
+        "share" => Ok(DeepLink::Share {
+            text: param("text").ok_or("share link is missing text")?,
+            platform: param("platform"),
+        }),
+        other => Err(format!("Unknown deep link action '{}'", other)),
+    }
+}
+
+/// Parse and dispatch a deep link. Errors are logged rather than returned since
+/// callers are OS event handlers with nobody to report to.
+pub fn handle(app: &AppHandle, raw: &str) {
+    let raw = normalize(raw);
+    if is_duplicate(&raw) {
+        tracing::debug!("ignoring repeated {}", raw);
+        return;
+    }
+    tracing::info!("received {}", raw);
+    match parse(&raw) {
+        Ok(link) => {
+            if let Err(e) = dispatch(app, link) {
+                tracing::warn!("{}", e);
+            }
+        }
+        Err(e) => tracing::warn!("{}", e),
+    }
+}
+
+pub fn dispatch(app: &AppHandle, link: DeepLink) -> Result<(), String> {
+    crate::show_main_window(app);
+    match link {
+        DeepLink::Share { text, platform } => {
+            crate::share::receive(app, text, platform)?;
+        }
+        DeepLink::OpenUrl(target) => {
+            app.emit("new_tab_request", target).map_err(|e| e.to_string())?;
+        }
+        DeepLink::Open { platform, prompt, send } => {
+            let Some(query) = platform else {
+                return Ok(());
+            };
+            let platform = platforms::find(app, &query)
+                .ok_or_else(|| format!("No platform matches '{}'", query))?;
+            // The frontend owns tab selection; it will call create_or_show_webview.
+            app.emit("activate_platform", platform.id.clone()).map_err(|e| e.to_string())?;
+            if let Some(text) = prompt {
+                prompt::inject_or_queue(app, &platform.id, &text, send)?;
+            }
+        }
+    }
+    Ok(())
+}
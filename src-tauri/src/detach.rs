@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::window::WindowBuilder;
+use tauri::{AppHandle, Emitter, LogicalPosition, Manager, PhysicalPosition, Window, WindowEvent};
+
+use crate::{ai_window_manager, platforms};
+
+/// Platform id -> label of the standalone window its webview was moved into.
+#[derive(Default)]
+pub struct DetachState {
+    windows: Mutex<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TabDetached {
+    platform_id: String,
+    window_label: String,
+}
+
+/// The standalone window holding `platform_id`'s webview, if it was dragged out.
+pub fn window_for(app: &AppHandle, platform_id: &str) -> Option<Window> {
+    let label = app.try_state::<DetachState>()?.windows.lock().unwrap().get(platform_id).cloned()?;
+    app.get_window(&label)
+}
+
+/// Forget that `platform_id` is detached, returning its window.
+pub fn take(app: &AppHandle, platform_id: &str) -> Option<Window> {
+    let label = app.try_state::<DetachState>()?.windows.lock().unwrap().remove(platform_id)?;
+    app.get_window(&label)
+}
+
+/// Window labels only allow a restricted character set.
+fn window_label(platform_id: &str) -> String {
+    let id: String = platform_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("detached-{}", id)
+}
+
+/// Move a tab out of the tab bar into its own window at `screen_position`
+/// (logical screen coordinates of the drop point). The webview itself is
+/// re-parented, so the page, its session and its data store come along.
+#[tauri::command]
+pub fn detach_tab(app: AppHandle, platform_id: String, screen_position: LogicalPosition<f64>) -> Result<(), String> {
+    if let Some(window) = window_for(&app, &platform_id) {
+        let _ = window.set_position(screen_position);
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    let webview = ai_window_manager::get_webview(&app, &platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    let main = app.get_window("main").ok_or("Main window not found")?;
+    let scale = main.scale_factor().map_err(|e| e.to_string())?;
+    let size = webview.size().map_err(|e| e.to_string())?.to_logical::<f64>(scale);
+    let title = platforms::load(&app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .map(|p| p.name)
+        .unwrap_or_else(|| platform_id.clone());
+
+    let label = window_label(&platform_id);
+    let window = WindowBuilder::new(&app, &label)
+        .title(title)
+        .inner_size(size.width.max(400.0), size.height.max(300.0))
+        .position(screen_position.x, screen_position.y)
+        .build()
+        .map_err(|e| e.to_string())?;
+    webview.reparent(&window).map_err(|e| e.to_string())?;
+    let _ = webview.set_position(PhysicalPosition::new(0, 0));
+    if let Ok(inner) = window.inner_size() {
+        let _ = webview.set_size(inner);
+    }
+    let _ = webview.set_auto_resize(true);
+    let _ = webview.show();
+    crate::throttle::on_shown(&webview);
+
+    // Closing the window closes the tab along with it.
+    let handle = app.clone();
+    let id = platform_id.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            // Already forgotten when the tab was closed from the tab bar.
+            if take(&handle, &id).is_none() {
+                return;
+            }
+            ai_window_manager::remember_closed(&handle, &id);
+            let _ = ai_window_manager::close_webview(&handle, &id);
+            let _ = handle.emit("detached_window_closed", serde_json::json!({ "platformId": id }));
+        }
+    });
+
+    app.state::<DetachState>().windows.lock().unwrap().insert(platform_id.clone(), label.clone());
+    if ai_window_manager::active_platform(&app).as_deref() == Some(platform_id.as_str()) {
+        ai_window_manager::set_active_platform(&app, None);
+    }
+    tracing::info!("detached '{}' into window '{}'", platform_id, label);
+    let _ = app.emit("tab_detached", TabDetached { platform_id, window_label: label });
+    Ok(())
+}
+
+/// Move a detached tab back into the main window and close its standalone
+/// window. The tab comes back hidden; `activate_platform` has the frontend
+/// select it, which sizes and shows it as usual.
+#[tauri::command]
+pub fn attach_tab(app: AppHandle, platform_id: String) -> Result<(), String> {
+    let window = take(&app, &platform_id).ok_or_else(|| format!("'{}' is not detached", platform_id))?;
+    let main = app.get_window("main").ok_or("Main window not found")?;
+    if let Some(webview) = ai_window_manager::get_webview(&app, &platform_id) {
+        let _ = webview.set_auto_resize(false);
+        let _ = webview.hide();
+        webview.reparent(&main).map_err(|e| e.to_string())?;
+        crate::throttle::on_hidden(&app, &webview);
+    }
+    let _ = window.close();
+    tracing::info!("attached '{}' back to the main window", platform_id);
+    let _ = app.emit("tab_attached", serde_json::json!({ "platformId": platform_id }));
+    crate::show_main_window(&app);
+    let _ = app.emit("activate_platform", platform_id);
+    Ok(())
+}
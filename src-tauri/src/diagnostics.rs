@@ -0,0 +1,120 @@
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::ai_window_manager;
+use crate::settings;
+
+/// Total size in bytes of everything under `path`.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn window_snapshot(app: &AppHandle) -> Value {
+    let Some(window) = app.get_window("main") else {
+        return Value::Null;
+    };
+    let monitor = window.current_monitor().ok().flatten().map(|m| {
+        json!({
+            "name": m.name(),
+            "size": { "width": m.size().width, "height": m.size().height },
+            "position": { "x": m.position().x, "y": m.position().y },
+            "scaleFactor": m.scale_factor(),
+        })
+    });
+    let (child_position, child_size) = ai_window_manager::compute_child_bounds(&window);
+    json!({
+        "outerPosition": window.outer_position().ok().map(|p| json!({ "x": p.x, "y": p.y })),
+        "innerSize": window.inner_size().ok().map(|s| json!({ "width": s.width, "height": s.height })),
+        "scaleFactor": window.scale_factor().ok(),
+        "visible": window.is_visible().ok(),
+        "minimized": window.is_minimized().ok(),
+        "focused": window.is_focused().ok(),
+        "monitor": monitor,
+        "expectedChildBounds": {
+            "x": child_position.x,
+            "y": child_position.y,
+            "width": child_size.width,
+            "height": child_size.height,
+        },
+    })
+}
+
+fn webviews_snapshot(app: &AppHandle) -> Vec<Value> {
+    let active = ai_window_manager::active_platform(app);
+    let hidden_by_hide_all: std::collections::HashMap<String, std::time::Duration> =
+        ai_window_manager::hidden_by_hide_all(app).into_iter().collect();
+    let mut webviews: Vec<Value> = app
+        .webviews()
+        .values()
+        .filter(|w| w.label() != "main")
+        .map(|webview| {
+            let label = webview.label().to_string();
+            json!({
+                "label": label,
+                "url": webview.url().ok().map(|u| u.to_string()),
+                "position": webview.position().ok().map(|p| json!({ "x": p.x, "y": p.y })),
+                "size": webview.size().ok().map(|s| json!({ "width": s.width, "height": s.height })),
+                // Only the active child is ever shown; the rest are hidden by create_or_show_webview.
+                "visible": active.as_deref() == Some(label.as_str()),
+                // Set while hide_all_webviews is the last thing that touched it.
+                "hiddenByHideAllSeconds": hidden_by_hide_all.get(&label).map(|d| d.as_secs()),
+            })
+        })
+        .collect();
+    webviews.sort_by(|a, b| a["label"].as_str().cmp(&b["label"].as_str()));
+    webviews
+}
+
+fn data_dir_snapshot(app: &AppHandle) -> Value {
+    let Ok(root) = app.path().app_local_data_dir() else {
+        return Value::Null;
+    };
+    let webdata: serde_json::Map<String, Value> = fs::read_dir(crate::workspaces::dir(app).join("webdata"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|entry| (entry.file_name().to_string_lossy().into_owned(), json!(dir_size(&entry.path()))))
+        .collect();
+    json!({
+        "path": root.display().to_string(),
+        "totalBytes": dir_size(&root),
+        "webdataBytes": webdata,
+        "logsBytes": dir_size(&crate::logging::log_dir()),
+    })
+}
+
+/// Snapshot of everything support needs to debug layout and loading issues.
+/// Walking the data directory can be slow, so it runs off the main thread.
+#[tauri::command]
+pub async fn get_diagnostics(app: AppHandle) -> Result<Value, String> {
+    let window = window_snapshot(&app);
+    let webviews = webviews_snapshot(&app);
+    let data_dirs = {
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || data_dir_snapshot(&app))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    Ok(json!({
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "activePlatform": ai_window_manager::active_platform(&app),
+        "window": window,
+        "webviews": webviews,
+        "dataDirs": data_dirs,
+        "settings": settings::read_value(&app),
+    }))
+}
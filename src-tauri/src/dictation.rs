@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::prompt;
+use crate::settings;
+
+/// `"dictation"` section of settings.json.
+///
+/// Transcription is delegated to whisper.cpp's streaming example
+/// (`whisper-stream`), which captures the default microphone and prints
+/// transcribed segments to stdout as they are recognised.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct DictationSettings {
+    command: String,
+    model: Option<String>,
+    language: Option<String>,
+    extra_args: Vec<String>,
+}
+
+impl Default for DictationSettings {
+    fn default() -> Self {
+        Self {
+            command: "whisper-stream".to_string(),
+            model: None,
+            language: None,
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DictationState {
+    child: Mutex<Option<Child>>,
+}
+
+#[derive(Serialize, Clone)]
+struct DictationText {
+    text: String,
+    injected: bool,
+}
+
+/// Strip the ANSI line-clearing sequences whisper-stream uses to redraw its output,
+/// and drop non-speech markers such as `[BLANK_AUDIO]` or `[Start speaking]`.
+fn clean_line(line: &str) -> Option<String> {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip CSI sequence: ESC [ ... final-byte
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                while let Some(&n) = chars.peek() {
+                    chars.next();
+                    if n.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if c == '\r' {
+            out.clear();
+            continue;
+        }
+        out.push(c);
+    }
+    let text = out.trim();
+    if text.is_empty() || (text.starts_with('[') && text.ends_with(']')) || (text.starts_with('(') && text.ends_with(')')) {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+#[tauri::command]
+pub fn start_dictation(app: AppHandle, inject: bool) -> Result<(), String> {
+    let state = app.state::<DictationState>();
+    let mut guard = state.child.lock().unwrap();
+    if guard.is_some() {
+        return Err("Dictation already running".to_string());
+    }
+
+    let cfg: DictationSettings = settings::section(&app, "dictation");
+    let mut cmd = Command::new(&cfg.command);
+    if let Some(model) = &cfg.model {
+        cmd.arg("-m").arg(model);
+    }
+    if let Some(language) = &cfg.language {
+        cmd.arg("-l").arg(language);
+    }
+    cmd.args(&cfg.extra_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start '{}': {}", cfg.command, e))?;
+    let stdout = child.stdout.take().ok_or("No stdout from recognizer")?;
+    let pid = child.id();
+    tracing::info!("started '{}' pid={} (inject={})", cfg.command, pid, inject);
+    *guard = Some(child);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let Some(text) = clean_line(&line) else { continue };
+            let injected = inject && prompt::inject_active(&app_handle, &format!("{} ", text), false).is_ok();
+            let _ = app_handle.emit("dictation_text", DictationText { text, injected });
+        }
+        // Recognizer exited (either stopped by us or crashed): clear the slot
+        // unless a newer session has already replaced it.
+        if let Some(state) = app_handle.try_state::<DictationState>() {
+            let mut slot = state.child.lock().unwrap();
+            if slot.as_ref().map(|c| c.id()) == Some(pid) {
+                slot.take();
+            }
+        }
+        tracing::info!("recognizer exited");
+        let _ = app_handle.emit("dictation_stopped", ());
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_dictation(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<DictationState>();
+    if let Some(mut child) = state.child.lock().unwrap().take() {
+        child.kill().map_err(|e| e.to_string())?;
+        let _ = child.wait();
+        tracing::info!("stopped");
+    }
+    Ok(())
+}
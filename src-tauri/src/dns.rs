@@ -0,0 +1,126 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::settings;
+
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+const MIN_TTL: Duration = Duration::from_secs(60);
+
+/// `"dns"` section of settings.json. Only affects the Rust HTTP clients
+/// (icons, preconnect, crash uploads, ...); webviews use the OS resolver.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct DnsSettings {
+    /// `"system"` or `"doh"`.
+    mode: String,
+    /// A DoH endpoint speaking the JSON API (Cloudflare, Google).
+    doh_url: String,
+}
+
+impl Default for DnsSettings {
+    fn default() -> Self {
+        Self { mode: "system".to_string(), doh_url: "https://cloudflare-dns.com/dns-query".to_string() }
+    }
+}
+
+/// The DoH endpoint in use, if DoH is on.
+static DOH_URL: Mutex<Option<String>> = Mutex::new(None);
+/// host -> (addresses, expiry)
+static CACHE: Mutex<Option<HashMap<String, (Vec<IpAddr>, Instant)>>> = Mutex::new(None);
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    kind: u16,
+    data: String,
+    #[serde(rename = "TTL", default)]
+    ttl: u64,
+}
+
+/// Re-read the settings. Called at startup and whenever settings are saved.
+pub fn apply_settings(app: &AppHandle) {
+    let cfg: DnsSettings = settings::section(app, "dns");
+    let url = (cfg.mode == "doh").then_some(cfg.doh_url);
+    let mut current = DOH_URL.lock().unwrap();
+    if *current != url {
+        tracing::info!("backend DNS: {}", url.as_deref().unwrap_or("system resolver"));
+        *current = url;
+        *CACHE.lock().unwrap() = None;
+    }
+}
+
+/// Resolver for `ureq` agents when DoH is on.
+pub fn resolver() -> Option<DohResolver> {
+    DOH_URL.lock().unwrap().clone().map(|url| DohResolver { url })
+}
+
+pub struct DohResolver {
+    url: String,
+}
+
+impl DohResolver {
+    fn query(&self, host: &str, record: &str) -> io::Result<(Vec<IpAddr>, u64)> {
+        // The DoH server's own name goes through the system resolver; that's
+        // the only lookup that does. The query itself goes through the proxy
+        // like any other request.
+        let response: DohResponse = crate::proxy::agent_without_doh(&self.url)
+            .get(&self.url)
+            .query("name", host)
+            .query("type", record)
+            .set("Accept", "application/dns-json")
+            .timeout(QUERY_TIMEOUT)
+            .call()
+            .map_err(|e| io::Error::other(format!("DoH query failed: {}", e)))?
+            .into_json()?;
+        let wanted = if record == "A" { 1 } else { 28 };
+        let answers: Vec<&DohAnswer> = response.answer.iter().filter(|a| a.kind == wanted).collect();
+        let ttl = answers.iter().map(|a| a.ttl).min().unwrap_or(0);
+        Ok((answers.iter().filter_map(|a| a.data.parse().ok()).collect(), ttl))
+    }
+
+    fn lookup(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        if let Some((ips, expires)) = CACHE.lock().unwrap().as_ref().and_then(|c| c.get(host)) {
+            if *expires > Instant::now() {
+                return Ok(ips.clone());
+            }
+        }
+        let (mut ips, ttl) = self.query(host, "A")?;
+        if ips.is_empty() {
+            ips = self.query(host, "AAAA")?.0;
+        }
+        if ips.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("no DoH answer for {}", host)));
+        }
+        let expires = Instant::now() + Duration::from_secs(ttl).max(MIN_TTL);
+        CACHE
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(host.to_string(), (ips.clone(), expires));
+        Ok(ips)
+    }
+}
+
+impl ureq::Resolver for DohResolver {
+    fn resolve(&self, netloc: &str) -> io::Result<Vec<SocketAddr>> {
+        let (host, port) = netloc
+            .rsplit_once(':')
+            .and_then(|(h, p)| Some((h.trim_matches(['[', ']']), p.parse::<u16>().ok()?)))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("bad address {}", netloc)))?;
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![SocketAddr::new(ip, port)]);
+        }
+        Ok(self.lookup(host)?.into_iter().map(|ip| SocketAddr::new(ip, port)).collect())
+    }
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Error returned by commands, serialized as `{ "kind": "notFound", "message": "..." }`
+/// so the frontend can branch on `kind` and still show `message`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum AppError {
+    /// A platform, webview or window that doesn't exist.
+    NotFound(String),
+    InvalidUrl(String),
+    /// Arguments or stored data that don't parse.
+    InvalidInput(String),
+    Io(String),
+    /// The engine refused to create a webview.
+    WebviewCreation(String),
+    /// The app lock is engaged.
+    Locked(String),
+    /// Turned off by settings, guest mode or a feature flag.
+    Unavailable(String),
+    Other(String),
+}
+
+impl AppError {
+    pub fn not_found(what: &str, id: &str) -> Self {
+        Self::NotFound(format!("{} '{}' not found", what, id))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(m)
+            | Self::InvalidUrl(m)
+            | Self::InvalidInput(m)
+            | Self::Io(m)
+            | Self::WebviewCreation(m)
+            | Self::Locked(m)
+            | Self::Unavailable(m)
+            | Self::Other(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Helpers that still return `String` errors become `Other`.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::InvalidInput(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        Self::Other(e.to_string())
+    }
+}
+
+/// Lets `String`-error code call commands that return `AppError` with `?`.
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
@@ -0,0 +1,189 @@
+use serde::Deserialize;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tauri::{AppHandle, EventId, Listener, Manager};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::Message;
+
+use crate::http_api;
+use crate::settings;
+
+/// Events mirrored to WebSocket subscribers. Keep in sync with what the
+/// backend emits to the frontend.
+const FORWARDED_EVENTS: &[&str] = &[
+    "activate_platform",
+    "new_tab_request",
+    "webview_navigated",
+    "download_started",
+    "download_finished",
+    "dictation_text",
+    "tts_started",
+    "tts_finished",
+    "recent_platforms_changed",
+    "webview_suspended",
+    "on_idle",
+    "on_active",
+    "memory_pressure",
+    "battery_saver_changed",
+    "network_online",
+    "network_offline",
+    "flight_mode_changed",
+    "notification_action",
+    "tab_detached",
+    "detached_window_closed",
+    "tab_attached",
+    "theme_changed",
+    "pip_started",
+    "pip_ended",
+    "tab_reopened",
+    "bookmarks_changed",
+    "audio_state_changed",
+    "workspace_changed",
+    "onboarding",
+    "platforms_changed",
+    "sync_conflict",
+    "guest_mode_changed",
+    "webview_destroyed",
+    "webview_shown",
+    "webview_create_failed",
+    "webview_health",
+    "chrome_visibility_changed",
+    "session_expired",
+    "session_restored",
+];
+
+/// `"eventBridge"` section of settings.json. Off by default.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct EventBridgeSettings {
+    enabled: bool,
+    port: u16,
+}
+
+impl Default for EventBridgeSettings {
+    fn default() -> Self {
+        Self { enabled: false, port: 47822 }
+    }
+}
+
+#[derive(Default)]
+pub struct EventBridgeState {
+    subscribers: Mutex<Vec<Sender<String>>>,
+    /// Port, stop flag and accept thread of the running server.
+    server: Mutex<Option<(u16, Arc<AtomicBool>, JoinHandle<()>)>>,
+    listeners: Mutex<Vec<EventId>>,
+}
+
+fn broadcast(app: &AppHandle, event: &str, payload: &str) {
+    let payload: serde_json::Value = serde_json::from_str(payload).unwrap_or(serde_json::Value::Null);
+    let message = serde_json::json!({ "event": event, "payload": payload }).to_string();
+    let state = app.state::<EventBridgeState>();
+    // Senders whose client went away fail here and are dropped.
+    state.subscribers.lock().unwrap().retain(|tx| tx.send(message.clone()).is_ok());
+}
+
+/// Clients authenticate with the HTTP API token: `ws://127.0.0.1:<port>/?token=...`.
+fn check_token(expected: &str, request: &Request) -> bool {
+    request
+        .uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .any(|(k, v)| k == "token" && http_api::token_matches(expected, v))
+}
+
+fn serve(app: AppHandle, listener: TcpListener, token: String, stop: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(stream) = stream else { continue };
+        let app = app.clone();
+        let token = token.clone();
+        std::thread::spawn(move || {
+            let callback = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+                if check_token(&token, request) {
+                    Ok(response)
+                } else {
+                    let mut denied = ErrorResponse::new(Some("invalid token".to_string()));
+                    *denied.status_mut() = tungstenite::http::StatusCode::UNAUTHORIZED;
+                    Err(denied)
+                }
+            };
+            let mut socket = match tungstenite::accept_hdr(stream, callback) {
+                Ok(socket) => socket,
+                Err(e) => {
+                    tracing::warn!("handshake failed: {}", e);
+                    return;
+                }
+            };
+            let (tx, rx) = mpsc::channel::<String>();
+            app.state::<EventBridgeState>().subscribers.lock().unwrap().push(tx);
+            tracing::debug!("subscriber connected");
+            for message in rx {
+                if socket.send(Message::text(message)).is_err() {
+                    break;
+                }
+            }
+            tracing::debug!("subscriber disconnected");
+        });
+    }
+}
+
+/// Register event listeners and start the WebSocket server if enabled.
+pub fn init(app: &AppHandle) {
+    let cfg: EventBridgeSettings = settings::section(app, "eventBridge");
+    if !cfg.enabled {
+        return;
+    }
+    if crate::guest::active() {
+        tracing::info!("event bridge is off in guest mode");
+        return;
+    }
+    let token = match http_api::api_token(app) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+    let listener = match TcpListener::bind(("127.0.0.1", cfg.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("failed to bind port {}: {}", cfg.port, e);
+            return;
+        }
+    };
+    let state = app.state::<EventBridgeState>();
+    let mut listeners = state.listeners.lock().unwrap();
+    for name in FORWARDED_EVENTS {
+        let handle = app.clone();
+        listeners.push(app.listen_any(*name, move |event| broadcast(&handle, name, event.payload())));
+    }
+    tracing::info!("listening on ws://127.0.0.1:{}", cfg.port);
+    let app_handle = app.clone();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopping = stop.clone();
+    let thread = std::thread::spawn(move || serve(app_handle, listener, token, stopping));
+    *state.server.lock().unwrap() = Some((cfg.port, stop, thread));
+}
+
+/// Stop the server, disconnect subscribers and drop the event listeners, so
+/// `init` can start over with other settings.
+pub(crate) fn stop(app: &AppHandle) {
+    let state = app.state::<EventBridgeState>();
+    for id in state.listeners.lock().unwrap().drain(..) {
+        app.unlisten(id);
+    }
+    // Dropping a subscriber's sender ends its connection.
+    state.subscribers.lock().unwrap().clear();
+    let Some((port, stop, thread)) = state.server.lock().unwrap().take() else { return };
+    stop.store(true, Ordering::Relaxed);
+    // The accept loop only sees the flag once a connection wakes it.
+    let _ = TcpStream::connect(("127.0.0.1", port));
+    let _ = thread.join();
+}
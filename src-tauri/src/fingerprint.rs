@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, WebviewBuilder, Wry};
+
+use crate::settings;
+
+/// `"fingerprint"` section of settings.json: per-platform overrides for the
+/// properties that give an embedded webview away, keyed by platform id.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct FingerprintSettings {
+    enabled: bool,
+    platforms: HashMap<String, PlatformOverrides>,
+}
+
+impl Default for FingerprintSettings {
+    fn default() -> Self {
+        Self { enabled: true, platforms: HashMap::new() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct PlatformOverrides {
+    /// IANA zone (`America/New_York`) reported by `Intl` and `Date`.
+    timezone: Option<String>,
+    /// Report `navigator.webdriver` as false.
+    hide_webdriver: bool,
+    /// Report the PDF viewer plugins a desktop Chrome has instead of none.
+    plugins: bool,
+}
+
+impl PlatformOverrides {
+    fn is_empty(&self) -> bool {
+        self.timezone.is_none() && !self.hide_webdriver && !self.plugins
+    }
+}
+
+const FINGERPRINT_JS: &str = r#"
+(function(cfg) {
+    var define = function(obj, name, get) {
+        try { Object.defineProperty(obj, name, { get: get, configurable: true }); } catch (e) {}
+    };
+    if (cfg.hideWebdriver) {
+        define(Navigator.prototype, 'webdriver', function() { return false; });
+    }
+    if (cfg.plugins) {
+        var names = ['PDF Viewer', 'Chrome PDF Viewer', 'Chromium PDF Viewer', 'Microsoft Edge PDF Viewer', 'WebKit built-in PDF'];
+        var mime = { type: 'application/pdf', suffixes: 'pdf', description: 'Portable Document Format' };
+        var plugins = names.map(function(name) {
+            return { name: name, filename: 'internal-pdf-viewer', description: 'Portable Document Format', length: 1, 0: mime,
+                     item: function(i) { return i === 0 ? mime : null; }, namedItem: function(t) { return t === mime.type ? mime : null; } };
+        });
+        plugins.item = function(i) { return plugins[i] || null; };
+        plugins.namedItem = function(n) { return plugins.filter(function(p) { return p.name === n; })[0] || null; };
+        plugins.refresh = function() {};
+        var mimeTypes = [mime];
+        mimeTypes.item = function(i) { return mimeTypes[i] || null; };
+        mimeTypes.namedItem = function(t) { return t === mime.type ? mime : null; };
+        define(Navigator.prototype, 'plugins', function() { return plugins; });
+        define(Navigator.prototype, 'mimeTypes', function() { return mimeTypes; });
+        define(Navigator.prototype, 'pdfViewerEnabled', function() { return true; });
+    }
+    if (cfg.timezone) {
+        var tz = cfg.timezone, OrigDTF = Intl.DateTimeFormat;
+        try { new OrigDTF('en-US', { timeZone: tz }); } catch (e) { return; }
+        var parts = new OrigDTF('en-US', { timeZone: tz, hourCycle: 'h23', year: 'numeric', month: 'numeric', day: 'numeric',
+                                           hour: 'numeric', minute: 'numeric', second: 'numeric' });
+        var withZone = function(options) { var o = Object.assign({}, options || {}); if (!o.timeZone) o.timeZone = tz; return o; };
+        var DTF = function(locales, options) { return new OrigDTF(locales, withZone(options)); };
+        DTF.prototype = OrigDTF.prototype;
+        DTF.supportedLocalesOf = OrigDTF.supportedLocalesOf;
+        Intl.DateTimeFormat = DTF;
+        Date.prototype.getTimezoneOffset = function() {
+            if (isNaN(this.getTime())) return NaN;
+            var p = {};
+            parts.formatToParts(this).forEach(function(x) { p[x.type] = x.value; });
+            var wall = Date.UTC(+p.year, +p.month - 1, +p.day, +p.hour % 24, +p.minute, +p.second);
+            return Math.round((this.getTime() - this.getUTCMilliseconds() - wall) / 60000);
+        };
+        ['toLocaleString', 'toLocaleDateString', 'toLocaleTimeString'].forEach(function(name) {
+            var orig = Date.prototype[name];
+            Date.prototype[name] = function(locales, options) { return orig.call(this, locales, withZone(options)); };
+        });
+    }
+})(__CONFIG__);
+"#;
+
+/// Add the platform's fingerprint overrides as an init script so they're in
+/// place before the site's own scripts look.
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let mut cfg: FingerprintSettings = settings::section(app, "fingerprint");
+    if !cfg.enabled {
+        return builder;
+    }
+    let Some(overrides) = cfg.platforms.remove(platform_id).filter(|o| !o.is_empty()) else {
+        return builder;
+    };
+    tracing::debug!("creating '{}' with fingerprint overrides {:?}", platform_id, overrides);
+    let config = serde_json::to_string(&overrides).unwrap_or_else(|_| "{}".to_string());
+    builder.initialization_script(&FINGERPRINT_JS.replace("__CONFIG__", &config))
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::settings;
+
+/// A switch for a subsystem that may misbehave on some sites. New scraping
+/// code registers here with `default: false` and ships dark until enabled.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct Flag {
+    pub key: &'static str,
+    pub description: &'static str,
+    pub default: bool,
+}
+
+pub const ADAPTER_EXTRACTION: &str = "adapterExtraction";
+pub const BROADCAST: &str = "broadcast";
+pub const API_MODE: &str = "apiMode";
+
+const FLAGS: &[Flag] = &[
+    Flag {
+        key: ADAPTER_EXTRACTION,
+        description: "Read answers with the site adapter's selectors instead of the generic heuristic",
+        default: true,
+    },
+    Flag { key: BROADCAST, description: "Send one prompt to several platforms at once", default: true },
+    Flag { key: API_MODE, description: "Drive the platform from the local HTTP API", default: true },
+];
+
+/// One entry of the `"featureFlags"` settings section. A per-platform value
+/// beats `enabled`, which beats the flag's default.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct FlagSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    platforms: HashMap<String, bool>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagState {
+    #[serde(flatten)]
+    pub flag: Flag,
+    /// Effective value: for the requested platform, else globally.
+    pub enabled: bool,
+    /// Platforms that override the global value.
+    pub platforms: HashMap<String, bool>,
+}
+
+fn find(key: &str) -> Result<&'static Flag, String> {
+    FLAGS.iter().find(|f| f.key == key).ok_or_else(|| format!("Unknown feature flag '{}'", key))
+}
+
+fn resolve(flag: &Flag, cfg: Option<&FlagSettings>, platform_id: Option<&str>) -> bool {
+    let Some(cfg) = cfg else { return flag.default };
+    platform_id
+        .and_then(|id| cfg.platforms.get(id).copied())
+        .or(cfg.enabled)
+        .unwrap_or(flag.default)
+}
+
+/// Whether `key` is on, for `platform_id` if given. Unknown keys are off.
+pub fn enabled(app: &AppHandle, key: &str, platform_id: Option<&str>) -> bool {
+    let Ok(flag) = find(key) else { return false };
+    let all: HashMap<String, FlagSettings> = settings::section(app, "featureFlags");
+    resolve(flag, all.get(key), platform_id)
+}
+
+#[tauri::command]
+pub fn get_flags(app: AppHandle, platform_id: Option<String>) -> Vec<FlagState> {
+    let all: HashMap<String, FlagSettings> = settings::section(&app, "featureFlags");
+    FLAGS
+        .iter()
+        .map(|flag| {
+            let cfg = all.get(flag.key);
+            FlagState {
+                flag: *flag,
+                enabled: resolve(flag, cfg, platform_id.as_deref()),
+                platforms: cfg.map(|c| c.platforms.clone()).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Turn a flag on or off globally, or for one platform. `enabled: null`
+/// drops the override and falls back to the next level.
+#[tauri::command]
+pub fn set_flag(app: AppHandle, key: String, enabled: Option<bool>, platform_id: Option<String>) -> Result<(), String> {
+    find(&key)?;
+    let mut all: HashMap<String, FlagSettings> = settings::section(&app, "featureFlags");
+    let cfg = all.entry(key.clone()).or_default();
+    match (platform_id, enabled) {
+        (Some(id), Some(on)) => {
+            cfg.platforms.insert(id, on);
+        }
+        (Some(id), None) => {
+            cfg.platforms.remove(&id);
+        }
+        (None, on) => cfg.enabled = on,
+    }
+    if cfg.enabled.is_none() && cfg.platforms.is_empty() {
+        all.remove(&key);
+    }
+    settings::write_section(&app, "featureFlags", &all)?;
+    tracing::info!("feature flag '{}' set to {:?}", key, enabled);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::settings;
+
+/// `"flightMode"` section of settings.json: the state to start in. The
+/// frontend saves it alongside `set_flight_mode` to keep it across restarts.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct FlightModeSettings {
+    enabled: bool,
+}
+
+/// Flight mode pauses every network request the backend makes on its own
+/// (preconnecting, connectivity probes, the update check, icon refreshes).
+/// Webviews and explicit user actions are unaffected.
+#[derive(Default)]
+pub struct FlightState {
+    enabled: AtomicBool,
+}
+
+/// Whether background network work should skip itself.
+pub fn active(app: &AppHandle) -> bool {
+    app.try_state::<FlightState>().is_some_and(|s| s.enabled.load(Ordering::Relaxed))
+}
+
+/// Pick up the saved state. Called in setup, before any background work starts.
+pub fn init(app: &AppHandle) {
+    let cfg: FlightModeSettings = settings::section(app, "flightMode");
+    if cfg.enabled {
+        app.state::<FlightState>().enabled.store(true, Ordering::Relaxed);
+        tracing::info!("starting in flight mode");
+    }
+}
+
+#[tauri::command]
+pub fn set_flight_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    if app.state::<FlightState>().enabled.swap(enabled, Ordering::Relaxed) != enabled {
+        tracing::info!("flight mode {}", if enabled { "on" } else { "off" });
+        let _ = app.emit("flight_mode_changed", serde_json::json!({ "enabled": enabled }));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_flight_mode(app: AppHandle) -> bool {
+    active(&app)
+}
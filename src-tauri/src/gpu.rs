@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Webview};
+
+use crate::settings;
+
+/// `"hardwareAcceleration"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct GpuSettings {
+    /// Off turns GPU compositing off for every webview. On Linux this needs a
+    /// restart since WebKitGTK reads it from the environment at startup.
+    enabled: bool,
+    /// Platform ids rendered in software even while `enabled` is on.
+    disabled_platforms: Vec<String>,
+}
+
+impl Default for GpuSettings {
+    fn default() -> Self {
+        Self { enabled: true, disabled_platforms: Vec::new() }
+    }
+}
+
+impl GpuSettings {
+    fn accelerated(&self, platform_id: &str) -> bool {
+        self.enabled && !self.disabled_platforms.iter().any(|id| id == platform_id)
+    }
+}
+
+/// Apply the global switch to the process environment. Runs at the top of
+/// `run()`, before any webview exists, so it reads settings.json directly.
+pub fn apply_process_flags() {
+    let cfg: GpuSettings = serde_json::from_value(
+        crate::cli::read_json("settings.json", serde_json::json!({}))["hardwareAcceleration"].clone(),
+    )
+    .unwrap_or_default();
+    if cfg.enabled {
+        return;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // Some drivers show black child webviews with the DMA-BUF renderer or
+        // accelerated compositing; respect values the user set themselves.
+        for var in ["WEBKIT_DISABLE_COMPOSITING_MODE", "WEBKIT_DISABLE_DMABUF_RENDERER"] {
+            if std::env::var_os(var).is_none() {
+                std::env::set_var(var, "1");
+            }
+        }
+    }
+    tracing::info!("hardware acceleration disabled in settings");
+}
+
+/// WebView2 arguments for a platform rendered in software, combined with the
+/// rest by `webview2::configure_builder`. Each platform has its own data
+/// directory, hence its own WebView2 environment, so they don't clash.
+#[cfg(windows)]
+pub fn browser_args(app: &AppHandle, platform_id: &str) -> Option<String> {
+    let cfg: GpuSettings = settings::section(app, "hardwareAcceleration");
+    if cfg.accelerated(platform_id) {
+        return None;
+    }
+    tracing::debug!("creating '{}' without GPU acceleration", platform_id);
+    Some("--disable-gpu --disable-gpu-compositing".to_string())
+}
+
+/// Per-platform settings applied to the native webview after creation.
+pub fn configure_webview(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    let cfg: GpuSettings = settings::section(app, "hardwareAcceleration");
+    if cfg.accelerated(platform_id) {
+        return;
+    }
+    #[cfg(target_os = "linux")]
+    webview
+        .with_webview(|wv| {
+            use webkit2gtk::{HardwareAccelerationPolicy, SettingsExt, WebViewExt};
+            if let Some(settings) = WebViewExt::settings(&wv.inner()) {
+                settings.set_hardware_acceleration_policy(HardwareAccelerationPolicy::Never);
+            }
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+    #[cfg(not(target_os = "linux"))]
+    let _ = webview;
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+
+use crate::ai_window_manager;
+
+/// Set for the rest of the process once guest mode starts; leaving it means
+/// quitting, which is also what throws the guest's sessions away.
+static GUEST: AtomicBool = AtomicBool::new(false);
+
+/// In guest mode tabs use throwaway in-memory web data, the storage layer
+/// keeps saves in memory only, and nothing else (logs, crash reports, icon
+/// cache, API token) is written to disk.
+pub fn active() -> bool {
+    GUEST.load(Ordering::SeqCst)
+}
+
+/// Called before anything else in `run()` when launched with `--guest`.
+pub fn enable() {
+    GUEST.store(true, Ordering::SeqCst);
+}
+
+/// Switch a running app into guest mode. Open tabs are closed so none keeps
+/// using the owner's signed-in web data.
+#[tauri::command]
+pub fn enter_guest_mode(app: AppHandle) {
+    if active() {
+        return;
+    }
+    ai_window_manager::close_all_tabs(&app);
+    crate::persist::flush();
+    enable();
+    crate::http_api::stop(&app);
+    tracing::info!("entered guest mode");
+    let _ = app.emit("guest_mode_changed", true);
+}
+
+#[tauri::command]
+pub fn is_guest_mode() -> bool {
+    active()
+}
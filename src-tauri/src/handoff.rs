@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::{ai_window_manager, settings};
+
+/// `"handoff"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct HandoffSettings {
+    /// Offer the active tab to other Apple devices (Safari picks it up).
+    enabled: bool,
+}
+
+impl Default for HandoffSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// The page to offer: the active tab's current URL. Handoff only takes web URLs.
+fn active_url(app: &AppHandle) -> Option<String> {
+    let cfg: HandoffSettings = settings::section(app, "handoff");
+    if !cfg.enabled {
+        return None;
+    }
+    let platform_id = ai_window_manager::active_platform(app)?;
+    let url = ai_window_manager::get_webview(app, &platform_id)?.url().ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+/// Re-publish the Handoff activity. Called when the active tab changes or navigates.
+pub fn refresh(app: &AppHandle) {
+    let url = active_url(app);
+    let _ = app.run_on_main_thread(move || crate::macos::set_browsing_activity(url.as_deref()));
+}
@@ -0,0 +1,133 @@
+use tauri::{AppHandle, Webview};
+use url::Url;
+
+use crate::platforms::{self, PlatformHeader};
+
+fn secret_account(platform_id: &str, name: &str) -> String {
+    format!("header:{}:{}", platform_id, name.to_ascii_lowercase())
+}
+
+/// The platform's extra headers with secret values filled in from the keychain.
+/// Headers whose secret is missing are left out rather than sent empty.
+fn resolve(app: &AppHandle, platform_id: &str) -> Vec<(String, String)> {
+    let Some(platform) = platforms::load(app).into_iter().find(|p| p.id == platform_id) else {
+        return Vec::new();
+    };
+    let mut headers: Vec<(String, String)> = platform
+        .headers
+        .into_iter()
+        .filter(|h| !h.name.trim().is_empty())
+        .filter_map(|PlatformHeader { name, value, secret }| {
+            let value = if secret { secret_value(platform_id, &name) } else { value };
+            value.map(|v| (name, v))
+        })
+        .collect();
+    // An explicit Accept-Language header wins over the language setting.
+    if let Some(value) = platform.language.as_deref().and_then(crate::locale::accept_language) {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("accept-language")) {
+            headers.push(("Accept-Language".to_string(), value));
+        }
+    }
+    headers
+}
+
+#[cfg(desktop)]
+fn secret_value(platform_id: &str, name: &str) -> Option<String> {
+    crate::keychain::get_secret(&secret_account(platform_id, name))
+}
+
+#[cfg(mobile)]
+fn secret_value(_platform_id: &str, _name: &str) -> Option<String> {
+    None
+}
+
+/// Attach the platform's extra headers to a newly created webview. Only
+/// requests to the platform's own host carry them, so gateway credentials
+/// never reach third-party origins. WebView2 rewrites every such request;
+/// WKWebView and WebKitGTK have no request hook, so there the initial page
+/// load is re-issued with the headers.
+pub fn apply(app: &AppHandle, platform_id: &str, webview: &Webview, url: &str) {
+    let headers = resolve(app, platform_id);
+    if headers.is_empty() {
+        return;
+    }
+    let Some(host) = Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)) else {
+        return;
+    };
+    tracing::debug!("adding {} header(s) to '{}' requests for {}", headers.len(), platform_id, host);
+    #[cfg(windows)]
+    intercept_webview2(webview, host, headers);
+    #[cfg(target_os = "linux")]
+    reload_webkitgtk(webview, url.to_string(), headers);
+    #[cfg(target_os = "macos")]
+    reload_wkwebview(webview, url.to_string(), headers);
+    #[cfg(mobile)]
+    let _ = (webview, host);
+}
+
+#[cfg(windows)]
+fn intercept_webview2(webview: &Webview, host: String, headers: Vec<(String, String)>) {
+    webview
+        .with_webview(move |wv| unsafe {
+            use webview2_com::Microsoft::Web::WebView2::Win32::*;
+            use webview2_com::WebResourceRequestedEventHandler;
+            use windows_core::HSTRING;
+
+            let Ok(core) = wv.controller().CoreWebView2() else { return };
+            let filter = HSTRING::from(format!("*://{}/*", host));
+            if let Err(e) = core.AddWebResourceRequestedFilter(&filter, COREWEBVIEW2_WEB_RESOURCE_CONTEXT_ALL) {
+                tracing::warn!("failed to add request filter: {}", e);
+                return;
+            }
+            let handler = WebResourceRequestedEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else { return Ok(()) };
+                let request_headers = args.Request()?.Headers()?;
+                for (name, value) in &headers {
+                    request_headers.SetHeader(&HSTRING::from(name.as_str()), &HSTRING::from(value.as_str()))?;
+                }
+                Ok(())
+            }));
+            let mut token = Default::default();
+            if let Err(e) = core.add_WebResourceRequested(&handler, &mut token) {
+                tracing::warn!("failed to intercept requests: {}", e);
+            }
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+#[cfg(target_os = "linux")]
+fn reload_webkitgtk(webview: &Webview, url: String, headers: Vec<(String, String)>) {
+    webview
+        .with_webview(move |wv| {
+            use webkit2gtk::{URIRequestExt, WebViewExt};
+            let request = webkit2gtk::URIRequest::new(&url);
+            if let Some(request_headers) = request.http_headers() {
+                for (name, value) in &headers {
+                    request_headers.append(name, value);
+                }
+            }
+            wv.inner().load_request(&request);
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+#[cfg(target_os = "macos")]
+fn reload_wkwebview(webview: &Webview, url: String, headers: Vec<(String, String)>) {
+    webview
+        .with_webview(move |wv| unsafe { crate::macos::load_request_with_headers(wv.inner(), &url, &headers) })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+/// Store a header's secret value in the keychain; the platform entry only
+/// records that the header is secret.
+#[cfg(desktop)]
+#[tauri::command]
+pub fn set_header_secret(platform_id: String, name: String, value: String) -> Result<(), String> {
+    crate::keychain::set_secret(&secret_account(&platform_id, &name), &value)
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+pub fn delete_header_secret(platform_id: String, name: String) -> Result<(), String> {
+    crate::keychain::delete_secret(&secret_account(&platform_id, &name))
+}
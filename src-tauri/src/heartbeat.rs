@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::{ai_window_manager, settings};
+
+const TICK: Duration = Duration::from_secs(5);
+
+/// `"heartbeat"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct HeartbeatSettings {
+    enabled: bool,
+    /// How often each tab is pinged.
+    interval_seconds: u64,
+    /// A tab that takes longer than this to answer is flagged.
+    timeout_seconds: u64,
+}
+
+impl Default for HeartbeatSettings {
+    fn default() -> Self {
+        Self { enabled: true, interval_seconds: 15, timeout_seconds: 5 }
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct WebviewHealth<'a> {
+    platform_id: &'a str,
+    responsive: bool,
+}
+
+#[derive(Default)]
+struct Health {
+    /// Platforms whose last ping timed out.
+    unresponsive: HashSet<String>,
+    /// Platforms with a ping still waiting for an answer; a frozen page isn't
+    /// pinged again until the first one gives up.
+    in_flight: HashSet<String>,
+}
+
+static HEALTH: Mutex<Option<Health>> = Mutex::new(None);
+
+fn with_health<R>(f: impl FnOnce(&mut Health) -> R) -> R {
+    f(HEALTH.lock().unwrap().get_or_insert_with(Health::default))
+}
+
+/// Platforms of live tabs, in the main window or their own. Parked webviews
+/// belong to no tab.
+fn live_tabs(app: &AppHandle) -> Vec<String> {
+    app.webviews()
+        .keys()
+        .filter(|label| label.as_str() != "main" && !crate::recycle::is_pooled(app, label))
+        .map(|label| ai_window_manager::platform_for_label(app, label))
+        .collect()
+}
+
+async fn ping(app: AppHandle, platform_id: String, timeout: Duration) {
+    let responsive = crate::bridge::eval_with_result(&app, &platform_id, "1", timeout).await.is_ok();
+    // Closed while the ping was out: nothing to report.
+    let closed = ai_window_manager::get_webview(&app, &platform_id).is_none();
+    let changed = with_health(|h| {
+        h.in_flight.remove(&platform_id);
+        if closed {
+            return false;
+        }
+        if responsive { h.unresponsive.remove(&platform_id) } else { h.unresponsive.insert(platform_id.clone()) }
+    });
+    if changed {
+        if responsive {
+            tracing::info!("'{}' is responding again", platform_id);
+        } else {
+            tracing::warn!("'{}' did not answer within {:?}", platform_id, timeout);
+        }
+        let _ = app.emit("webview_health", WebviewHealth { platform_id: &platform_id, responsive });
+    }
+}
+
+fn tick(app: &AppHandle, cfg: &HeartbeatSettings) {
+    let tabs = live_tabs(app);
+    let timeout = Duration::from_secs(cfg.timeout_seconds.max(1));
+    let due: Vec<String> = with_health(|h| {
+        h.unresponsive.retain(|id| tabs.contains(id));
+        tabs.into_iter().filter(|id| h.in_flight.insert(id.clone())).collect()
+    });
+    for platform_id in due {
+        tauri::async_runtime::spawn(ping(app.clone(), platform_id, timeout));
+    }
+}
+
+/// Platforms whose tab stopped answering and hasn't recovered.
+#[tauri::command]
+pub fn list_unresponsive_webviews() -> Vec<String> {
+    let mut ids: Vec<String> = with_health(|h| h.unresponsive.iter().cloned().collect());
+    ids.sort();
+    ids
+}
+
+/// Ping every live tab with a trivial script and report when one stops or
+/// starts answering again as `webview_health`. Settings are re-read every tick.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut waited = Duration::ZERO;
+        loop {
+            std::thread::sleep(TICK);
+            waited += TICK;
+            let cfg: HeartbeatSettings = settings::section(&app, "heartbeat");
+            if !cfg.enabled || waited < Duration::from_secs(cfg.interval_seconds) {
+                continue;
+            }
+            waited = Duration::ZERO;
+            tick(&app, &cfg);
+        }
+    });
+}
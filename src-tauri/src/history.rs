@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, WebviewBuilder, Wry};
+
+use crate::ai_window_manager;
+
+/// Oldest entries beyond this are dropped, per platform.
+const MAX_ENTRIES_PER_PLATFORM: usize = 1000;
+
+/// AI sites switch conversations with `history.pushState`, which never fires
+/// a page load, so in-page navigations are reported back to Rust. The title
+/// is read a moment later, once the page has had a chance to update it.
+const HISTORY_JS: &str = r#"
+(function() {
+    var last = location.href;
+    var report = function() {
+        if (location.href === last) return;
+        last = location.href;
+        setTimeout(function() {
+            window.__TAURI_INTERNALS__.invoke('record_navigation', { url: location.href, title: document.title })
+                .catch(function() {});
+        }, 1000);
+    };
+    ['pushState', 'replaceState'].forEach(function(name) {
+        var original = history[name];
+        history[name] = function() {
+            var result = original.apply(this, arguments);
+            report();
+            return result;
+        };
+    });
+    window.addEventListener('popstate', report);
+})();
+"#;
+
+/// One visited URL. Repeat visits update the existing entry.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: Option<String>,
+    pub last_visited: u64,
+    pub visits: u64,
+}
+
+/// A history entry along with the platform it was visited in.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryItem {
+    pub platform_id: String,
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+}
+
+/// platform id -> entries, newest first. Loaded lazily from `history.json`.
+#[derive(Default)]
+pub struct HistoryState {
+    entries: Mutex<Option<HashMap<String, Vec<HistoryEntry>>>>,
+}
+
+fn history_file_path(app: &AppHandle) -> PathBuf {
+    let dir = crate::workspaces::dir(app);
+    dir.join("history.json")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn with_entries<R>(app: &AppHandle, f: impl FnOnce(&mut HashMap<String, Vec<HistoryEntry>>) -> R) -> R {
+    let state = app.state::<HistoryState>();
+    let mut entries = state.entries.lock().unwrap();
+    let entries = entries.get_or_insert_with(|| {
+        crate::persist::read_to_string(&history_file_path(app))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    });
+    f(entries)
+}
+
+fn save(app: &AppHandle) {
+    if let Ok(json) = with_entries(app, |entries| serde_json::to_string_pretty(entries)) {
+        crate::persist::write(history_file_path(app), json);
+    }
+}
+
+/// Drop the loaded history so the next access reads the active workspace's file.
+pub(crate) fn unload(app: &AppHandle) {
+    *app.state::<HistoryState>().entries.lock().unwrap() = None;
+}
+
+pub fn configure_builder(builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(HISTORY_JS)
+}
+
+/// Record a visit to `url` in `platform_id`'s history.
+pub fn record(app: &AppHandle, platform_id: &str, url: &str, title: Option<&str>) {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return;
+    }
+    let title = title.map(str::trim).filter(|t| !t.is_empty()).map(str::to_string);
+    with_entries(app, |entries| {
+        let list = entries.entry(platform_id.to_string()).or_default();
+        let mut entry = match list.iter().position(|e| e.url == url) {
+            Some(i) => list.remove(i),
+            None => HistoryEntry { url: url.to_string(), ..Default::default() },
+        };
+        entry.last_visited = now_millis();
+        entry.visits += 1;
+        if title.is_some() {
+            entry.title = title;
+        }
+        list.insert(0, entry);
+        list.truncate(MAX_ENTRIES_PER_PLATFORM);
+    });
+    save(app);
+}
+
+/// Called by the in-page navigation hook in child webviews.
+#[tauri::command]
+pub fn record_navigation(app: AppHandle, webview: tauri::Webview, url: String, title: Option<String>) {
+    if crate::recycle::is_pooled(&app, webview.label()) {
+        return;
+    }
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    if let Some(title) = title.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        ai_window_manager::set_title(&app, &platform_id, title);
+    }
+    record(&app, &platform_id, &url, title.as_deref());
+}
+
+/// Visited URLs, newest first, for one platform or all of them. `query`
+/// matches case-insensitively against the URL and title.
+#[tauri::command]
+pub fn get_history(
+    app: AppHandle,
+    platform_id: Option<String>,
+    query: Option<String>,
+    limit: Option<usize>,
+) -> Vec<HistoryItem> {
+    let query = query.map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty());
+    let mut items: Vec<HistoryItem> = with_entries(&app, |entries| {
+        entries
+            .iter()
+            .filter(|(id, _)| platform_id.is_none() || platform_id.as_ref() == Some(*id))
+            .flat_map(|(id, list)| list.iter().map(move |e| (id, e)))
+            .filter(|(_, e)| match &query {
+                Some(q) => {
+                    e.url.to_lowercase().contains(q) || e.title.as_ref().is_some_and(|t| t.to_lowercase().contains(q))
+                }
+                None => true,
+            })
+            .map(|(id, e)| HistoryItem { platform_id: id.clone(), entry: e.clone() })
+            .collect()
+    });
+    items.sort_by(|a, b| b.entry.last_visited.cmp(&a.entry.last_visited));
+    items.truncate(limit.unwrap_or(200));
+    items
+}
+
+/// Forget one platform's history, or everything when `platform_id` is omitted.
+#[tauri::command]
+pub fn clear_history(app: AppHandle, platform_id: Option<String>) {
+    with_entries(&app, |entries| match platform_id {
+        Some(id) => {
+            entries.remove(&id);
+        }
+        None => entries.clear(),
+    });
+    save(&app);
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::shortcuts::{self, Scope};
+
+/// Registered global shortcuts by action.
+#[derive(Default)]
+pub struct HotkeyState {
+    bindings: Mutex<HashMap<String, Shortcut>>,
+}
+
+fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))
+}
+
+/// Swap the shortcut registered for `action`, unregistering the previous one.
+pub fn apply(app: &AppHandle, action: &str, accelerator: Option<&str>) -> Result<(), String> {
+    let next = accelerator.map(parse).transpose()?;
+    let state = app.state::<HotkeyState>();
+    let mut bindings = state.bindings.lock().unwrap();
+    if let Some(old) = bindings.remove(action) {
+        let _ = app.global_shortcut().unregister(old);
+    }
+    if let Some(shortcut) = next {
+        app.global_shortcut()
+            .register(shortcut)
+            .map_err(|e| format!("Failed to register shortcut: {}", e))?;
+        tracing::info!("{} bound to {}", action, accelerator.unwrap_or_default());
+        bindings.insert(action.to_string(), shortcut);
+    }
+    Ok(())
+}
+
+/// Unregister every global shortcut, e.g. before binding another workspace's.
+pub fn clear(app: &AppHandle) {
+    let state = app.state::<HotkeyState>();
+    for (_, shortcut) in state.bindings.lock().unwrap().drain() {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
+
+fn run(app: &AppHandle, action: &str) {
+    match action {
+        "toggle_window" => crate::toggle_main_window(app),
+        other => tracing::warn!("no global handler for '{}'", other),
+    }
+}
+
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    app.plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(|app, shortcut, event| {
+                if event.state() != ShortcutState::Pressed {
+                    return;
+                }
+                let action = app.try_state::<HotkeyState>().and_then(|s| {
+                    s.bindings
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .find(|(_, bound)| *bound == shortcut)
+                        .map(|(action, _)| action.clone())
+                });
+                if let Some(action) = action {
+                    run(app, &action);
+                }
+            })
+            .build(),
+    )?;
+
+    for (action, accelerator) in shortcuts::bound(app, Scope::Global) {
+        if let Err(e) = apply(app, action, Some(&accelerator)) {
+            tracing::warn!("{}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Rebind the show/hide shortcut at runtime. Same as `set_shortcut("toggle_window", ...)`.
+#[tauri::command]
+pub fn set_toggle_window_shortcut(app: AppHandle, accelerator: Option<String>) -> Result<(), String> {
+    shortcuts::set_shortcut(app, "toggle_window".to_string(), accelerator)
+}
@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::adapters;
+use crate::bridge;
+use crate::deeplink::{self, DeepLink};
+use crate::platforms;
+use crate::settings;
+
+/// `"httpApi"` section of settings.json. The server is off unless explicitly enabled.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct HttpApiSettings {
+    enabled: bool,
+    port: u16,
+}
+
+impl Default for HttpApiSettings {
+    fn default() -> Self {
+        Self { enabled: false, port: 47821 }
+    }
+}
+
+/// The running server and the thread serving it, which holds the port until it exits.
+#[derive(Default)]
+pub struct HttpApiState {
+    server: Mutex<Option<(Arc<Server>, JoinHandle<()>)>>,
+}
+
+#[derive(Serialize)]
+pub struct HttpApiStatus {
+    running: bool,
+    port: Option<u16>,
+}
+
+fn token_file_path(app: &AppHandle) -> PathBuf {
+    let dir = app.path().app_local_data_dir().unwrap();
+    dir.join("api_token")
+}
+
+fn generate_token() -> String {
+    use rand::distributions::Alphanumeric;
+    use rand::Rng;
+    rand::thread_rng().sample_iter(&Alphanumeric).take(40).map(char::from).collect()
+}
+
+/// Write the token readable by the current user only.
+fn write_token(path: &std::path::Path, token: &str) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .map_err(|e| e.to_string())?;
+        // `mode` only applies when the file is created; tighten older ones too.
+        file.set_permissions(fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+        file.write_all(token.as_bytes()).map_err(|e| e.to_string())
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, token).map_err(|e| e.to_string())
+    }
+}
+
+/// The bearer token clients must send; created on first use.
+pub(crate) fn api_token(app: &AppHandle) -> Result<String, String> {
+    let path = token_file_path(app);
+    if let Ok(token) = fs::read_to_string(&path) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+    let token = generate_token();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    write_token(&path, &token)?;
+    Ok(token)
+}
+
+/// Compare without short-circuiting so response time doesn't leak the token prefix.
+pub(crate) fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected.bytes().zip(given.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn json_response(status: u16, body: serde_json::Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header)
+}
+
+fn error_response(status: u16, msg: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    json_response(status, serde_json::json!({ "error": msg }))
+}
+
+#[derive(Deserialize)]
+struct OpenBody {
+    platform: String,
+}
+
+#[derive(Deserialize)]
+struct PromptBody {
+    platform: String,
+    text: String,
+    #[serde(default)]
+    send: bool,
+}
+
+fn read_body<T: for<'de> Deserialize<'de>>(request: &mut Request) -> Result<T, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(1024 * 1024)
+        .read_to_string(&mut body)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|e| format!("Invalid JSON body: {}", e))
+}
+
+fn query_param(url: &str, key: &str) -> Option<String> {
+    let parsed = url::Url::parse(&format!("http://localhost{}", url)).ok()?;
+    for (k, v) in parsed.query_pairs() {
+        if k == key {
+            return Some(v.into_owned());
+        }
+    }
+    None
+}
+
+/// `query` is an id or name, as the API accepts either.
+fn api_mode(app: &AppHandle, query: &str) -> bool {
+    let id = platforms::find(app, query).map(|p| p.id);
+    crate::flags::enabled(app, crate::flags::API_MODE, id.as_deref())
+}
+
+fn route(app: &AppHandle, request: &mut Request) -> Response<std::io::Cursor<Vec<u8>>> {
+    let method = request.method().clone();
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    match (&method, path.as_str()) {
+        (Method::Get, "/platforms") => {
+            let list: Vec<_> = platforms::load(app)
+                .into_iter()
+                .map(|p| serde_json::json!({ "id": p.id, "name": p.name, "url": p.url, "hidden": p.hidden }))
+                .collect();
+            json_response(200, serde_json::json!(list))
+        }
+        (Method::Post, "/open") => match read_body::<OpenBody>(request) {
+            Ok(body) => {
+                let link = DeepLink::Open { platform: Some(body.platform), prompt: None, send: false };
+                match deeplink::dispatch(app, link) {
+                    Ok(()) => json_response(200, serde_json::json!({ "ok": true })),
+                    Err(e) => error_response(404, &e),
+                }
+            }
+            Err(e) => error_response(400, &e),
+        },
+        (Method::Post, "/prompt") => match read_body::<PromptBody>(request) {
+            Ok(body) => {
+                if !api_mode(app, &body.platform) {
+                    return error_response(403, "API mode is off for this platform");
+                }
+                let link = DeepLink::Open { platform: Some(body.platform), prompt: Some(body.text), send: body.send };
+                match deeplink::dispatch(app, link) {
+                    Ok(()) => json_response(200, serde_json::json!({ "ok": true })),
+                    Err(e) => error_response(404, &e),
+                }
+            }
+            Err(e) => error_response(400, &e),
+        },
+        (Method::Get, "/response") => {
+            let Some(query) = query_param(request.url(), "platform") else {
+                return error_response(400, "Missing 'platform' query parameter");
+            };
+            let Some(platform) = platforms::find(app, &query) else {
+                return error_response(404, &format!("No platform matches '{}'", query));
+            };
+            if !crate::flags::enabled(app, crate::flags::API_MODE, Some(&platform.id)) {
+                return error_response(403, "API mode is off for this platform");
+            }
+            let Some(webview) = crate::ai_window_manager::get_webview(app, &platform.id) else {
+                return error_response(409, "Platform is not open");
+            };
+            let current_url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+            let js = adapters::last_response_js(adapters::for_url(&current_url));
+            let result = tauri::async_runtime::block_on(bridge::eval_with_result(app, &platform.id, &js, Duration::from_secs(5)));
+            match result {
+                Ok(text) => json_response(200, serde_json::json!({ "platform": platform.id, "text": text })),
+                Err(e) => error_response(502, &e),
+            }
+        }
+        _ => error_response(404, "Not found"),
+    }
+}
+
+fn serve(app: AppHandle, server: Arc<Server>, token: String) {
+    for mut request in server.incoming_requests() {
+        let authorized = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Authorization"))
+            .and_then(|h| h.value.as_str().strip_prefix("Bearer ").map(|t| token_matches(&token, t.trim())))
+            .unwrap_or(false);
+        let response = if authorized {
+            route(&app, &mut request)
+        } else {
+            error_response(401, "Missing or invalid bearer token")
+        };
+        tracing::debug!("{} {} (authorized={})", request.method(), request.url(), authorized);
+        let _ = request.respond(response);
+    }
+    tracing::info!("stopped");
+}
+
+fn start(app: &AppHandle, port: u16) -> Result<(), String> {
+    if crate::guest::active() {
+        return Err("The HTTP API is off in guest mode".to_string());
+    }
+    let state = app.state::<HttpApiState>();
+    let mut slot = state.server.lock().unwrap();
+    if slot.is_some() {
+        return Ok(());
+    }
+    let token = api_token(app)?;
+    // Loopback only: the API is for local tools, never the network.
+    let server = Arc::new(Server::http(("127.0.0.1", port)).map_err(|e| format!("Failed to bind port {}: {}", port, e))?);
+    tracing::info!("listening on 127.0.0.1:{}", port);
+    let app_handle = app.clone();
+    let serving = server.clone();
+    let thread = std::thread::spawn(move || serve(app_handle, serving, token));
+    *slot = Some((server, thread));
+    Ok(())
+}
+
+/// Stop the server and wait for its thread to let go of the port, so it can
+/// be bound again straight away.
+pub(crate) fn stop(app: &AppHandle) {
+    let Some((server, thread)) = app.state::<HttpApiState>().server.lock().unwrap().take() else { return };
+    server.unblock();
+    // A request that turns the API off is handled on the serve thread itself,
+    // which exits once it has answered.
+    if thread.thread().id() != std::thread::current().id() {
+        let _ = thread.join();
+    }
+    drop(server);
+}
+
+/// Start the server at launch if the user enabled it.
+pub fn init(app: &AppHandle) {
+    let cfg: HttpApiSettings = settings::section(app, "httpApi");
+    if cfg.enabled {
+        if let Err(e) = start(app, cfg.port) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn set_http_api_enabled(app: AppHandle, enabled: bool) -> Result<HttpApiStatus, String> {
+    let cfg: HttpApiSettings = settings::section(&app, "httpApi");
+    if enabled {
+        start(&app, cfg.port)?;
+    } else {
+        stop(&app);
+    }
+    http_api_status(app)
+}
+
+#[tauri::command]
+pub fn http_api_status(app: AppHandle) -> Result<HttpApiStatus, String> {
+    let state = app.state::<HttpApiState>();
+    let server = state.server.lock().unwrap();
+    Ok(HttpApiStatus {
+        running: server.is_some(),
+        port: server.as_ref().and_then(|(s, _)| s.server_addr().to_ip().map(|a| a.port())),
+    })
+}
+
+#[tauri::command]
+pub fn get_http_api_token(app: AppHandle) -> Result<String, String> {
+    api_token(&app)
+}
+
+/// Issue a new token; existing clients must be reconfigured.
+#[tauri::command]
+pub fn rotate_http_api_token(app: AppHandle) -> Result<String, String> {
+    let token = generate_token();
+    write_token(&token_file_path(&app), &token)?;
+    let cfg: HttpApiSettings = settings::section(&app, "httpApi");
+    if app.state::<HttpApiState>().server.lock().unwrap().is_some() {
+        stop(&app);
+        start(&app, cfg.port)?;
+    }
+    Ok(token)
+}
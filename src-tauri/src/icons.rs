@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use url::Url;
+
+use crate::platforms;
+
+/// Cached icons are used without touching the network for this long; after
+/// that they're revalidated with ETag / Last-Modified.
+const FRESH_FOR: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_ICON_BYTES: u64 = 1024 * 1024;
+const MAX_PAGE_BYTES: u64 = 512 * 1024;
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; AnyBrain icon fetcher)";
+
+/// Sidecar stored as `<platform id>.json` next to the icon file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct IconMeta {
+    source_url: String,
+    file: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: u64,
+}
+
+fn icons_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_local_data_dir().map_err(|e| e.to_string())?.join("icons");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Platform ids become file names, so keep them to a safe character set.
+fn file_stem(platform_id: &str) -> String {
+    platform_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let start = lower.find(&format!("{}=", name))? + name.len() + 1;
+    let rest = &tag[start..];
+    let quote = rest.chars().next()?;
+    if quote == '"' || quote == '\'' {
+        rest[1..].split(quote).next()
+    } else {
+        rest.split(|c: char| c.is_whitespace() || c == '>').next()
+    }
+}
+
+/// Score `<link rel=icon>` candidates: apple-touch-icons are large and square,
+/// then the biggest declared size wins, then SVG.
+fn score(rel: &str, sizes: Option<&str>, href: &str) -> u32 {
+    let size = sizes
+        .and_then(|s| s.split_whitespace().filter_map(|d| d.split(['x', 'X']).next()?.parse::<u32>().ok()).max())
+        .unwrap_or(0);
+    let mut score = size.min(512);
+    if rel.contains("apple-touch-icon") {
+        score += 1000;
+    }
+    if href.ends_with(".svg") {
+        score += 500;
+    }
+    score
+}
+
+/// Find the best icon advertised by the page, falling back to `/favicon.ico`.
+fn discover_icon_url(page: &Url) -> Url {
+    let fallback = page.join("/favicon.ico").unwrap_or_else(|_| page.clone());
+    let Ok(response) = crate::proxy::agent_for(page.as_str()).get(page.as_str()).set("User-Agent", USER_AGENT).timeout(Duration::from_secs(10)).call() else {
+        return fallback;
+    };
+    let base = Url::parse(response.get_url()).unwrap_or_else(|_| page.clone());
+    let mut html = String::new();
+    let _ = response.into_reader().take(MAX_PAGE_BYTES).read_to_string(&mut html);
+
+    let mut best: Option<(u32, Url)> = None;
+    for chunk in html.split('<').skip(1) {
+        if !chunk.get(..5).is_some_and(|t| t.eq_ignore_ascii_case("link ")) {
+            continue;
+        }
+        let tag = chunk.split('>').next().unwrap_or_default();
+        let rel = attr(tag, "rel").unwrap_or_default().to_ascii_lowercase();
+        if !rel.split_whitespace().any(|r| r == "icon" || r.starts_with("apple-touch-icon")) {
+            continue;
+        }
+        let Some(href) = attr(tag, "href") else { continue };
+        let Ok(url) = base.join(href) else { continue };
+        let candidate = score(&rel, attr(tag, "sizes"), href);
+        let better = match &best {
+            Some((current, _)) => candidate > *current,
+            None => true,
+        };
+        if better {
+            best = Some((candidate, url));
+        }
+    }
+    best.map(|(_, url)| url).unwrap_or(fallback)
+}
+
+fn extension_for(content_type: &str, url: &Url) -> &'static str {
+    match content_type.split(';').next().unwrap_or_default().trim() {
+        "image/png" => "png",
+        "image/svg+xml" => "svg",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        _ => match Path::new(url.path()).extension().and_then(|e| e.to_str()) {
+            Some("png") => "png",
+            Some("svg") => "svg",
+            Some("jpg") | Some("jpeg") => "jpg",
+            _ => "ico",
+        },
+    }
+}
+
+fn read_meta(dir: &Path, stem: &str) -> Option<IconMeta> {
+    let data = fs::read_to_string(dir.join(format!("{}.json", stem))).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn write_meta(dir: &Path, stem: &str, meta: &IconMeta) {
+    if let Ok(json) = serde_json::to_string_pretty(meta) {
+        let _ = fs::write(dir.join(format!("{}.json", stem)), json);
+    }
+}
+
+/// The platform's favicon if it's already cached, without going to the network.
+pub fn cached_path(app: &AppHandle, platform_id: &str) -> Option<PathBuf> {
+    let dir = app.path().app_local_data_dir().ok()?.join("icons");
+    let meta = read_meta(&dir, &file_stem(platform_id))?;
+    let path = dir.join(meta.file);
+    path.exists().then_some(path)
+}
+
+fn fetch(app: &AppHandle, platform_id: &str) -> Result<PathBuf, String> {
+    if crate::guest::active() {
+        return Err("Icons aren't cached in guest mode".to_string());
+    }
+    let platform = platforms::load(app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .ok_or_else(|| format!("Unknown platform '{}'", platform_id))?;
+    let dir = icons_dir(app)?;
+    let stem = file_stem(platform_id);
+    let cached = read_meta(&dir, &stem).filter(|m| dir.join(&m.file).exists());
+
+    if let Some(meta) = &cached {
+        // A stale icon beats a request in flight mode.
+        if now_secs().saturating_sub(meta.fetched_at) < FRESH_FOR.as_secs() || crate::flight::active(app) {
+            return Ok(dir.join(&meta.file));
+        }
+    }
+    if crate::flight::active(app) {
+        return Err("Flight mode is on".to_string());
+    }
+
+    let page = Url::parse(&platform.url).map_err(|e| format!("Invalid platform URL: {}", e))?;
+    // Revalidate the icon we already know about rather than re-scraping the page.
+    let icon_url = match &cached {
+        Some(meta) => Url::parse(&meta.source_url).unwrap_or_else(|_| discover_icon_url(&page)),
+        None => discover_icon_url(&page),
+    };
+
+    let mut request = crate::proxy::agent_for(icon_url.as_str()).get(icon_url.as_str()).set("User-Agent", USER_AGENT).timeout(Duration::from_secs(10));
+    if let Some(meta) = &cached {
+        if let Some(etag) = &meta.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(modified) = &meta.last_modified {
+            request = request.set("If-Modified-Since", modified);
+        }
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        // Offline or the site is down: a stale icon beats no icon.
+        Err(e) => {
+            return match cached {
+                Some(meta) => Ok(dir.join(&meta.file)),
+                None => Err(format!("Failed to fetch icon: {}", e)),
+            }
+        }
+    };
+
+    if let (304, Some(meta)) = (response.status(), &cached) {
+        write_meta(&dir, &stem, &IconMeta { fetched_at: now_secs(), ..meta.clone() });
+        return Ok(dir.join(&meta.file));
+    }
+
+    let etag = response.header("ETag").map(str::to_string);
+    let last_modified = response.header("Last-Modified").map(str::to_string);
+    let file = format!("{}.{}", stem, extension_for(response.content_type(), &icon_url));
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_ICON_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    if bytes.is_empty() {
+        return Err("Icon response was empty".to_string());
+    }
+
+    if let Some(old) = &cached {
+        if old.file != file {
+            let _ = fs::remove_file(dir.join(&old.file));
+        }
+    }
+    fs::write(dir.join(&file), &bytes).map_err(|e| e.to_string())?;
+    write_meta(&dir, &stem, &IconMeta {
+        source_url: icon_url.to_string(),
+        file: file.clone(),
+        etag,
+        last_modified,
+        fetched_at: now_secs(),
+    });
+    tracing::debug!("cached icon for '{}' from {}", platform_id, icon_url);
+    Ok(dir.join(file))
+}
+
+/// Local path of the platform's favicon, downloading or revalidating it as
+/// needed. The frontend loads it through the asset protocol.
+#[tauri::command]
+pub async fn fetch_platform_icon(app: AppHandle, platform_id: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || fetch(&app, &platform_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|path| path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::{lock, settings, suspend};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// `"idle"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct IdleSettings {
+    /// System idle time after which `on_idle` is emitted.
+    threshold_seconds: u64,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self { threshold_seconds: 120 }
+    }
+}
+
+pub(crate) fn system_idle_seconds() -> Option<u64> {
+    user_idle::UserIdle::get_time().ok().map(|idle| idle.as_seconds())
+}
+
+/// Which policies already fired during the current idle stretch, so each runs
+/// once per stretch rather than on every poll.
+#[derive(Default)]
+struct IdleStretch {
+    announced: bool,
+    suspended: bool,
+    locked: bool,
+}
+
+/// Poll the OS idle timer and drive the idle-based policies: `on_idle` /
+/// `on_active` events, suspending hidden webviews, and locking the app.
+/// Settings are re-read every poll so changes apply without a restart.
+pub fn init(app: &AppHandle) {
+    if system_idle_seconds().is_none() {
+        tracing::warn!("system idle time unavailable; idle policies disabled");
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut stretch = IdleStretch::default();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let Some(idle) = system_idle_seconds() else { continue };
+            let idle_cfg: IdleSettings = settings::section(&app, "idle");
+
+            // Any input since the last poll ends the idle stretch.
+            if idle < POLL_INTERVAL.as_secs() {
+                if stretch.announced {
+                    tracing::debug!("user active again");
+                    let _ = app.emit("on_active", ());
+                }
+                stretch = IdleStretch::default();
+                continue;
+            }
+
+            if !stretch.announced && idle >= idle_cfg.threshold_seconds {
+                stretch.announced = true;
+                tracing::debug!("user idle for {}s", idle);
+                let _ = app.emit("on_idle", serde_json::json!({ "idleSeconds": idle }));
+            }
+
+            let suspension: suspend::SuspensionSettings = settings::section(&app, "suspension");
+            if !stretch.suspended && suspension.idle_minutes > 0 && idle >= suspension.idle_minutes * 60 {
+                stretch.suspended = true;
+                let closed = suspend::suspend_hidden(&app, "idle");
+                if !closed.is_empty() {
+                    tracing::info!("suspended {} hidden webviews after idle", closed.len());
+                }
+            }
+
+            let app_lock: lock::AppLockSettings = settings::section(&app, "appLock");
+            if !stretch.locked && app_lock.idle_minutes > 0 && idle >= app_lock.idle_minutes * 60 {
+                stretch.locked = true;
+                lock::lock(&app, "idle");
+            }
+        }
+    });
+}
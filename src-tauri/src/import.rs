@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use url::Url;
+
+use crate::platforms::{self, Platform};
+
+/// A link pulled out of an import file.
+struct Entry {
+    name: String,
+    url: String,
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Value of `name="..."` (or single-quoted) inside one tag's text.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name).map(|p| p + from) {
+        from = pos + name.len();
+        // Whole attribute names only: `url=` must not match inside `xmlurl=`.
+        if pos > 0 && !lower.as_bytes()[pos - 1].is_ascii_whitespace() {
+            continue;
+        }
+        let rest = lower[from..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else { continue };
+        let rest = rest.trim_start();
+        let start = tag.len() - rest.len();
+        let quote = rest.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let end = tag[start + 1..].find(quote)? + start + 1;
+        return Some(decode_entities(&tag[start + 1..end]));
+    }
+    None
+}
+
+/// Links in a browser bookmarks export (the Netscape format every browser
+/// writes): `<A HREF="...">Title</A>`.
+fn parse_bookmarks_html(data: &str) -> Vec<Entry> {
+    let lower = data.to_ascii_lowercase();
+    let mut entries = Vec::new();
+    let mut from = 0;
+    while let Some(start) = lower[from..].find("<a ").map(|p| p + from) {
+        let Some(tag_end) = lower[start..].find('>').map(|p| p + start) else { break };
+        let close = lower[tag_end..].find("</a>").map(|p| p + tag_end).unwrap_or(tag_end + 1);
+        if let Some(url) = attribute(&data[start..tag_end], "href") {
+            let name = decode_entities(data[tag_end + 1..close.max(tag_end + 1)].trim());
+            entries.push(Entry { name, url });
+        }
+        from = tag_end + 1;
+    }
+    entries
+}
+
+/// `<outline text="..." htmlUrl="..."/>` elements of an OPML file.
+fn parse_opml(data: &str) -> Vec<Entry> {
+    let lower = data.to_ascii_lowercase();
+    let mut entries = Vec::new();
+    let mut from = 0;
+    while let Some(start) = lower[from..].find("<outline").map(|p| p + from) {
+        let Some(end) = lower[start..].find('>').map(|p| p + start) else { break };
+        let tag = &data[start..end];
+        let url = ["htmlurl", "url", "xmlurl"].iter().find_map(|name| attribute(tag, name));
+        if let Some(url) = url {
+            let name = attribute(tag, "text").or_else(|| attribute(tag, "title")).unwrap_or_default();
+            entries.push(Entry { name, url });
+        }
+        from = end + 1;
+    }
+    entries
+}
+
+/// A JSON array of URL strings or `{ "name", "url" }` objects; a saved
+/// platforms.json works as is.
+fn parse_json(data: &str) -> Result<Vec<Entry>, String> {
+    let value: serde_json::Value = serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match &value {
+        serde_json::Value::Array(items) => items,
+        _ => value.get("platforms").and_then(|p| p.as_array()).ok_or("Expected a JSON array of links")?,
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(url) => Some(Entry { name: String::new(), url: url.clone() }),
+            serde_json::Value::Object(obj) => {
+                let url = obj.get("url").or_else(|| obj.get("href"))?.as_str()?.to_string();
+                let name = obj.get("name").or_else(|| obj.get("title")).and_then(|n| n.as_str()).unwrap_or_default();
+                Some(Entry { name: name.to_string(), url })
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Key two URLs are considered duplicates under: host and path, ignoring
+/// scheme, `www.`, query and a trailing slash.
+fn dedup_key(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default().trim_start_matches("www.");
+    format!("{}{}", host, url.path().trim_end_matches('/')).to_lowercase()
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name.to_lowercase().chars().map(|c| if c.is_alphanumeric() { c } else { '-' }).collect();
+    slug.trim_matches('-').to_string()
+}
+
+/// Parse a bookmarks export (`html`), OPML outline (`opml`) or JSON list
+/// (`json`) into platform entries. `format` defaults to the file extension.
+/// Links already saved as platforms, repeated links and non-web links are
+/// skipped. Nothing is saved; the frontend adds the returned platforms.
+#[tauri::command]
+pub fn import_platforms(app: AppHandle, path: String, format: Option<String>) -> Result<Vec<Platform>, String> {
+    let data = std::fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let format = format
+        .or_else(|| Path::new(&path).extension().map(|e| e.to_string_lossy().to_lowercase()))
+        .unwrap_or_default();
+    let entries = match format.as_str() {
+        "html" | "htm" => parse_bookmarks_html(&data),
+        "opml" | "xml" => parse_opml(&data),
+        "json" => parse_json(&data)?,
+        other => return Err(format!("Unsupported import format '{}'", other)),
+    };
+
+    let mut seen: HashSet<String> = platforms::load(&app)
+        .iter()
+        .filter_map(|p| Url::parse(&p.url).ok())
+        .map(|u| dedup_key(&u))
+        .collect();
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let mut imported = Vec::new();
+    for entry in entries {
+        let Ok(url) = Url::parse(entry.url.trim()) else { continue };
+        if !matches!(url.scheme(), "http" | "https") || !seen.insert(dedup_key(&url)) {
+            continue;
+        }
+        let name = match entry.name.trim() {
+            "" => url.host_str().unwrap_or_default().trim_start_matches("www.").to_string(),
+            name => name.to_string(),
+        };
+        let id = format!("{}-{}-{}", slug(&name), stamp, imported.len());
+        imported.push(Platform::new(id, &name, url.as_str()));
+    }
+    tracing::info!("imported {} platforms from {}", imported.len(), path);
+    Ok(imported)
+}
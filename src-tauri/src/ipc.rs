@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::ipc::{Invoke, InvokeMessage};
+use tauri::Wry;
+
+/// Label of the webview running the app's own frontend.
+const MAIN_WEBVIEW: &str = "main";
+
+/// Commands the scripts we inject into child webviews call back into. Every
+/// other command is reserved for the app's frontend.
+const CHILD_COMMANDS: &[&str] = &[
+    "bridge_reply",
+    "network_log_entries",
+    "open_link_in_new_tab",
+    "record_navigation",
+    "report_audio_state",
+    "expand_snippet",
+    "login_form_detected",
+    "switch_platform",
+    "report_scroll",
+    "report_session",
+    "report_response",
+];
+
+/// Commands that exist to store or move data on disk, refused in guest mode.
+const GUEST_BLOCKED_COMMANDS: &[&str] = &[
+    "save_platforms",
+    "save_settings",
+    "remove_platform",
+    "clear_platform_data",
+    "undo_delete",
+    "purge_trash",
+    "create_workspace",
+    "delete_workspace",
+    "switch_workspace",
+    "sync_now",
+    "set_sync_passphrase",
+    "save_login",
+    "delete_login",
+    "get_http_api_token",
+    "rotate_http_api_token",
+];
+
+/// Calls a child webview may make per `RATE_WINDOW`.
+const RATE_LIMIT: u32 = 100;
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// label -> (window start, calls in window)
+static RATE: Mutex<Option<HashMap<String, (Instant, u32)>>> = Mutex::new(None);
+
+/// The frontend is served from the bundled assets, or the dev server in dev
+/// builds. A release build never trusts a page on localhost: any local server
+/// a tab is pointed at would otherwise pass as the app.
+fn is_app_origin(url: &url::Url) -> bool {
+    url.scheme() == "tauri"
+        || url.host_str() == Some("tauri.localhost")
+        || (cfg!(debug_assertions) && matches!(url.host_str(), Some("localhost" | "127.0.0.1")))
+}
+
+fn within_rate(label: &str) -> bool {
+    let mut rate = RATE.lock().unwrap();
+    let (start, calls) = rate.get_or_insert_with(HashMap::new).entry(label.to_string()).or_insert((Instant::now(), 0));
+    if start.elapsed() >= RATE_WINDOW {
+        *start = Instant::now();
+        *calls = 0;
+    }
+    *calls += 1;
+    *calls <= RATE_LIMIT
+}
+
+fn check(message: &InvokeMessage<Wry>) -> Result<(), String> {
+    let command = message.command();
+    let webview = message.webview_ref();
+    let label = webview.label();
+    if crate::guest::active() && GUEST_BLOCKED_COMMANDS.contains(&command) {
+        return Err(format!("'{}' is not available in guest mode", command));
+    }
+    if label == MAIN_WEBVIEW {
+        return match webview.url() {
+            Ok(url) if is_app_origin(&url) => Ok(()),
+            Ok(url) => Err(format!("'{}' is not allowed from {}", command, url.origin().ascii_serialization())),
+            Err(e) => Err(e.to_string()),
+        };
+    }
+    if !CHILD_COMMANDS.contains(&command) {
+        return Err(format!("'{}' is not allowed from webview '{}'", command, label));
+    }
+    if !within_rate(label) {
+        return Err(format!("Too many calls from webview '{}'", label));
+    }
+    Ok(())
+}
+
+/// Wrap the command handler so pages loaded in child webviews (AI sites we
+/// don't control) can only reach the callbacks our injected scripts use.
+/// Plugin commands are governed by capabilities and never get here.
+pub fn guarded(
+    handler: impl Fn(Invoke<Wry>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<Wry>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if let Err(e) = check(&invoke.message) {
+            tracing::warn!("rejected IPC call: {}", e);
+            invoke.resolver.reject(e);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Forget a closed webview's rate counter.
+pub fn forget(label: &str) {
+    if let Some(rate) = RATE.lock().unwrap().as_mut() {
+        rate.remove(label);
+    }
+}
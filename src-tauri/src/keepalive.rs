@@ -0,0 +1,124 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{adapters, ai_window_manager, settings};
+
+const TICK: Duration = Duration::from_secs(60);
+/// Floor for per-platform intervals, so a typo can't hammer a site.
+const MIN_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// At most this many pings per tick; the rest wait for the next one.
+const MAX_PINGS_PER_TICK: usize = 3;
+
+/// `"keepAlive"` section of settings.json. `enabled` is the global switch;
+/// only platforms listed in `platforms` are pinged.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct KeepAliveSettings {
+    enabled: bool,
+    platforms: HashMap<String, PlatformKeepAlive>,
+}
+
+impl Default for KeepAliveSettings {
+    fn default() -> Self {
+        Self { enabled: true, platforms: HashMap::new() }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    /// Request a page of the site with the tab's cookies.
+    #[default]
+    Fetch,
+    /// Briefly report the tab as visible, for sites that sign out idle tabs
+    /// from their own visibility timers.
+    Nudge,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct PlatformKeepAlive {
+    interval_minutes: u64,
+    mode: Mode,
+    /// Path or URL to fetch; the site's root by default.
+    url: Option<String>,
+}
+
+impl Default for PlatformKeepAlive {
+    fn default() -> Self {
+        Self { interval_minutes: 15, mode: Mode::Fetch, url: None }
+    }
+}
+
+/// platform id -> last ping.
+static LAST_PING: Mutex<Option<HashMap<String, Instant>>> = Mutex::new(None);
+
+fn fetch_js(url: Option<&str>) -> String {
+    let url = url.map(adapters::js_string).unwrap_or_else(|| "location.origin + '/'".to_string());
+    format!(
+        "fetch(new URL({}, location.href), {{ credentials: 'include', cache: 'no-store' }}).catch(function() {{}});",
+        url
+    )
+}
+
+const NUDGE_JS: &str = r#"
+(function() {
+    var s = window.__anybrainThrottle;
+    if (!s || !s.hidden) return;
+    s.hidden = false;
+    document.dispatchEvent(new Event('visibilitychange'));
+    window.dispatchEvent(new Event('focus'));
+    setTimeout(function() {
+        s.hidden = true;
+        document.dispatchEvent(new Event('visibilitychange'));
+    }, 2000);
+})();
+"#;
+
+fn tick(app: &AppHandle) {
+    let cfg: KeepAliveSettings = settings::section(app, "keepAlive");
+    if !cfg.enabled || cfg.platforms.is_empty() || crate::flight::active(app) {
+        return;
+    }
+    let active = ai_window_manager::active_platform(app);
+    let mut last = LAST_PING.lock().unwrap();
+    let last = last.get_or_insert_with(HashMap::new);
+    let mut pinged = 0;
+    for (platform_id, ka) in &cfg.platforms {
+        if pinged == MAX_PINGS_PER_TICK {
+            break;
+        }
+        // The visible tab is kept alive by the user.
+        if active.as_deref() == Some(platform_id.as_str()) {
+            continue;
+        }
+        let Some(webview) = ai_window_manager::get_webview(app, platform_id) else { continue };
+        let interval = Duration::from_secs(ka.interval_minutes * 60).max(MIN_INTERVAL);
+        let since = *last.entry(platform_id.clone()).or_insert_with(Instant::now);
+        if since.elapsed() < interval {
+            continue;
+        }
+        tracing::debug!("keep-alive {:?} for '{}'", ka.mode, platform_id);
+        let js = match ka.mode {
+            Mode::Fetch => fetch_js(ka.url.as_deref()),
+            Mode::Nudge => NUDGE_JS.to_string(),
+        };
+        let _ = webview.eval(&js);
+        last.insert(platform_id.clone(), Instant::now());
+        pinged += 1;
+    }
+    last.retain(|id, _| cfg.platforms.contains_key(id));
+}
+
+/// Ping hidden tabs of platforms that sign out idle sessions. Settings are
+/// re-read every tick.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(TICK);
+        tick(&app);
+    });
+}
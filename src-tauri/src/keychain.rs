@@ -0,0 +1,29 @@
+use crate::cli::APP_IDENTIFIER;
+
+/// Secrets (header values, autofill credentials) live in the OS keychain —
+/// Keychain on macOS, Credential Manager on Windows, Secret Service on Linux —
+/// never in the JSON files. `account` namespaces the entry, e.g. `header:<id>:<name>`.
+fn entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(APP_IDENTIFIER, account).map_err(|e| e.to_string())
+}
+
+pub fn get_secret(account: &str) -> Option<String> {
+    match entry(account).and_then(|e| e.get_password().map_err(|e| e.to_string())) {
+        Ok(secret) => Some(secret),
+        Err(e) => {
+            tracing::debug!("no keychain entry for {}: {}", account, e);
+            None
+        }
+    }
+}
+
+pub fn set_secret(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?.set_password(secret).map_err(|e| e.to_string())
+}
+
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -18,20 +17,17 @@ fn state_file_path(app: &tauri::AppHandle) -> PathBuf {
 
 fn load_window_state(app: &tauri::AppHandle) -> Option<WindowState> {
     let path = state_file_path(app);
-    let data = fs::read_to_string(&path).ok()?;
+    let data = persist::read_to_string(&path).ok()?;
     let state: WindowState = serde_json::from_str(&data).ok()?;
-    eprintln!("[state] loaded: {:?}", state);
+    tracing::debug!("window state loaded: {:?}", state);
     Some(state)
 }
 
 fn save_window_state(app: &tauri::AppHandle, state: &WindowState) {
-    let path = state_file_path(app);
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
     if let Ok(json) = serde_json::to_string_pretty(state) {
-        let _ = fs::write(&path, json);
-        eprintln!("[state] saved: {:?}", state);
+        if persist::write(state_file_path(app), json) {
+            tracing::debug!("window state saved: {:?}", state);
+        }
     }
 }
 
@@ -40,62 +36,277 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-fn platforms_file_path(app: &tauri::AppHandle) -> PathBuf {
-    use tauri::Manager;
-    let dir = app.path().app_local_data_dir().unwrap();
-    dir.join("platforms.json")
-}
-
 #[tauri::command]
-fn load_platforms(app: tauri::AppHandle) -> Result<String, String> {
-    let path = platforms_file_path(&app);
-    match fs::read_to_string(&path) {
+fn load_platforms(app: tauri::AppHandle) -> Result<String, error::AppError> {
+    if let Some(seeded) = onboarding::seed_if_first_run(&app) {
+        return Ok(seeded);
+    }
+    let path = platforms::platforms_file_path(&app);
+    match persist::read_to_string(&path) {
         Ok(data) => Ok(data),
         Err(_) => Ok("[]".to_string()),
     }
 }
 
-#[tauri::command]
-fn save_platforms(app: tauri::AppHandle, data: String) -> Result<(), String> {
-    let path = platforms_file_path(&app);
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    fs::write(&path, &data).map_err(|e| e.to_string())
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedPlatforms {
+    revision: u64,
+    /// The list as stored after the save.
+    data: String,
+    /// The save was based on an outdated revision and was not applied; `data`
+    /// holds the newer list to adopt instead.
+    conflict: bool,
 }
 
-fn settings_file_path(app: &tauri::AppHandle) -> PathBuf {
-    use tauri::Manager;
-    let dir = app.path().app_local_data_dir().unwrap();
-    dir.join("settings.json")
+/// Save the whole list. With `base`, the save only applies if nobody else
+/// changed the list since the window last saw revision `base`.
+#[tauri::command]
+fn save_platforms(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    data: String,
+    base: Option<u64>,
+) -> Result<SavedPlatforms, error::AppError> {
+    let (handle, source) = (app.clone(), window.label().to_string());
+    let (committed, conflict) = persist::update(platforms::platforms_file_path(&app), move |revision, current| {
+        if base.is_some_and(|base| base != revision) {
+            return Ok((None, true));
+        }
+        changelog::record(&handle, current.as_deref(), &data, &source);
+        Ok((Some(data), false))
+    })?;
+    // The frontend saves on every edit; menus only need rebuilding on real changes.
+    platforms::notify(&app, &committed, Some(window.label()));
+    Ok(SavedPlatforms { revision: committed.revision, data: committed.data, conflict })
 }
 
 #[tauri::command]
-fn load_settings(app: tauri::AppHandle) -> Result<String, String> {
-    let path = settings_file_path(&app);
-    match fs::read_to_string(&path) {
+fn load_settings(app: tauri::AppHandle) -> Result<String, error::AppError> {
+    let path = settings::settings_file_path(&app);
+    match persist::read_to_string(&path) {
         Ok(data) => Ok(data),
         Err(_) => Ok("{}".to_string()),
     }
 }
 
 #[tauri::command]
-fn save_settings(app: tauri::AppHandle, data: String) -> Result<(), String> {
-    let path = settings_file_path(&app);
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+fn save_settings(app: tauri::AppHandle, data: String) -> Result<(), error::AppError> {
+    let patch: serde_json::Value = serde_json::from_str(&data)?;
+    settings::merge(&app, patch)?;
+    dns::apply_settings(&app);
+    Ok(())
+}
+
+/// Bring the main window to the front, restoring it if minimized or hidden.
+pub(crate) fn show_main_window(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
     }
-    fs::write(&path, &data).map_err(|e| e.to_string())
 }
 
+/// Hide the main window if it is visible and focused, otherwise bring it forward.
+pub(crate) fn toggle_main_window(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    if let Some(window) = app.get_webview_window("main") {
+        let visible = window.is_visible().unwrap_or(false);
+        let focused = window.is_focused().unwrap_or(false);
+        if visible && focused {
+            let _ = window.hide();
+        } else {
+            show_main_window(app);
+        }
+    }
+}
+
+mod adapters;
 mod ai_window_manager;
+#[cfg(desktop)]
+mod app_menu;
+mod audio;
+#[cfg(desktop)]
+mod autofill;
+mod autorefresh;
+#[cfg(desktop)]
+mod autostart;
+mod bench;
+mod bookmarks;
+mod bridge;
+mod cache_quota;
+mod certs;
+mod changelog;
+mod cli;
+mod connectivity;
+mod crash;
+mod deeplink;
+mod detach;
+mod diagnostics;
+mod dictation;
+mod dns;
+mod error;
+mod event_bridge;
+mod fingerprint;
+mod flags;
+mod flight;
+mod gpu;
+mod guest;
+#[cfg(target_os = "macos")]
+mod handoff;
+mod headers;
+mod heartbeat;
+mod history;
+#[cfg(desktop)]
+mod hotkey;
+mod http_api;
+mod icons;
+#[cfg(desktop)]
+mod idle;
+mod import;
+mod ipc;
+mod keepalive;
+#[cfg(desktop)]
+mod keychain;
+mod links;
+#[cfg(target_os = "linux")]
+mod linux;
+mod locale;
+mod lock;
+mod logging;
+#[cfg(target_os = "macos")]
+mod macos;
+mod mcp;
+#[cfg(desktop)]
+mod memory;
+#[cfg(mobile)]
+mod mobile;
+mod netlog;
+mod notes;
+mod notifications;
+mod onboarding;
+mod permalink;
+mod persist;
+#[cfg(desktop)]
+mod pip;
+mod platforms;
+#[cfg(desktop)]
+mod power;
+mod preconnect;
+mod prompt;
+mod proxy;
+mod reader;
+mod recent;
+mod recycle;
+mod routing;
+mod runtime;
+mod scroll_sync;
+mod selfhosted;
+mod send_key;
+mod session;
+mod settings;
+mod share;
+#[cfg(desktop)]
+mod shortcuts;
+mod site_mode;
+mod snippets;
+mod spellcheck;
+mod splash;
+mod startup;
+mod stream;
+mod suspend;
+#[cfg(desktop)]
+mod sync;
+#[cfg(desktop)]
+mod taskbar;
+mod theme;
+mod throttle;
+#[cfg(desktop)]
+mod titlebar;
+mod trash;
+#[cfg(desktop)]
+mod tray;
+mod tts;
+#[cfg(desktop)]
+mod updater;
+mod usage;
+#[cfg(desktop)]
+mod vibrancy;
+mod webview2;
+#[cfg(windows)]
+mod win32;
+mod workspaces;
+
+#[cfg(desktop)]
+#[derive(Serialize, Clone)]
+struct SecondInstancePayload {
+    args: Vec<String>,
+    cwd: String,
+}
+
+/// A second launch was attempted: focus the existing window and hand its
+/// arguments to the frontend instead of starting another instance.
+#[cfg(desktop)]
+fn on_second_instance(app: &tauri::AppHandle, args: Vec<String>, cwd: String) {
+    use tauri::Emitter;
+    tracing::info!("second launch args={:?} cwd={}", args, cwd);
+    show_main_window(app);
+    match cli::parse(args.iter().skip(1).cloned()) {
+        // Deep links (Windows/Linux protocol activation) arrive here as an argv
+        // entry too, but the single-instance plugin's deep-link feature already
+        // forwards them to the deep-link plugin's on_open_url handler.
+        Ok(parsed) => cli::apply(app, &cli::CliArgs { deep_link: None, ..parsed }),
+        Err(e) => tracing::warn!("{}", e),
+    }
+    let _ = app.emit("second_instance", SecondInstancePayload { args, cwd });
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    startup::begin();
+    // Before logging and crash handling, which would otherwise touch the disk.
+    if std::env::args().any(|arg| arg == "--guest") {
+        guest::enable();
+    }
+    logging::init();
+    if !guest::active() {
+        crash::init();
+    }
+    #[cfg(target_os = "linux")]
+    linux::apply_process_flags();
+    gpu::apply_process_flags();
+    startup::mark("logging and crash handlers");
+    let cli_args = match cli::parse(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if cli::run_standalone(&cli_args) {
+        return;
+    }
+
+    let mut builder = tauri::Builder::default();
+
+    // Must be the first plugin so a second process exits before doing any work.
+    #[cfg(desktop)]
+    {
+        builder = builder
+            .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+                on_second_instance(app, args, cwd);
+            }))
+            .plugin(autostart::plugin())
+            .plugin(tauri_plugin_updater::Builder::new().build());
+    }
+
+    builder
         .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .invoke_handler(ipc::guarded(tauri::generate_handler![
             greet,
             load_platforms,
             save_platforms,
@@ -103,17 +314,276 @@ pub fn run() {
             save_settings,
             ai_window_manager::create_or_show_webview,
             ai_window_manager::destroy_webview,
+            ai_window_manager::list_recently_closed,
+            ai_window_manager::list_webviews,
+            heartbeat::list_unresponsive_webviews,
+            session::report_session,
+            session::list_signed_out,
+            send_key::set_send_key,
+            send_key::get_send_key,
+            stream::report_response,
+            ai_window_manager::reopen_last_closed,
+            ai_window_manager::open_new_conversation,
+            workspaces::list_workspaces,
+            workspaces::create_workspace,
+            workspaces::delete_workspace,
+            workspaces::switch_workspace,
+            import::import_platforms,
+            onboarding::list_platform_catalog,
+            changelog::get_change_history,
+            guest::enter_guest_mode,
+            guest::is_guest_mode,
+            flags::get_flags,
+            flags::set_flag,
+            selfhosted::discover_local_uis,
+            #[cfg(desktop)]
+            sync::sync_now,
+            #[cfg(desktop)]
+            sync::set_sync_passphrase,
+            #[cfg(desktop)]
+            sync::get_sync_status,
+            trash::remove_platform,
+            trash::clear_platform_data,
+            trash::undo_delete,
+            trash::list_trash,
+            trash::purge_trash,
+            runtime::apply_runtime_remedy,
+            #[cfg(mobile)]
+            mobile::set_safe_area,
+            #[cfg(mobile)]
+            mobile::switch_platform,
+            #[cfg(mobile)]
+            mobile::get_mobile_state,
+            #[cfg(target_os = "android")]
+            mobile::handle_back_button,
+            #[cfg(target_os = "linux")]
+            linux::get_webkit_info,
             ai_window_manager::hide_all_webviews,
             ai_window_manager::reload_webview,
-            ai_window_manager::reload_webview_url
-        ])
-        .setup(|app| {
+            ai_window_manager::reload_webview_url,
+            ai_window_manager::go_back,
+            site_mode::set_site_mode,
+            scroll_sync::set_scroll_sync,
+            scroll_sync::get_scroll_sync,
+            scroll_sync::report_scroll,
+            permalink::get_current_url,
+            permalink::copy_conversation_link,
+            prompt::send_prompt,
+            dictation::start_dictation,
+            dictation::stop_dictation,
+            bridge::bridge_reply,
+            tts::speak_text,
+            tts::speak_last_response,
+            tts::stop_speaking,
+            tts::pause_speaking,
+            http_api::set_http_api_enabled,
+            http_api::http_api_status,
+            http_api::get_http_api_token,
+            http_api::rotate_http_api_token,
+            bench::measure_tab_switch,
+            cache_quota::trim_web_caches,
+            proxy::refresh_proxy_settings,
+            connectivity::get_connectivity,
+            certs::accept_certificate,
+            certs::revoke_certificate,
+            certs::list_certificate_overrides,
+            netlog::start_network_log,
+            netlog::stop_network_log,
+            netlog::network_log_entries,
+            flight::set_flight_mode,
+            flight::get_flight_mode,
+            notifications::notify,
+            detach::detach_tab,
+            detach::attach_tab,
+            #[cfg(desktop)]
+            pip::pip_webview,
+            #[cfg(desktop)]
+            pip::end_pip,
+            links::open_link_in_new_tab,
+            theme::set_platform_theme,
+            reader::extract_readable,
+            notes::list_notes,
+            notes::create_note,
+            notes::update_note,
+            notes::delete_note,
+            history::record_navigation,
+            history::get_history,
+            history::clear_history,
+            bookmarks::list_bookmarks,
+            bookmarks::add_bookmark,
+            bookmarks::rename_bookmark,
+            bookmarks::move_bookmark,
+            bookmarks::remove_bookmark,
+            bookmarks::create_bookmark_folder,
+            bookmarks::rename_bookmark_folder,
+            bookmarks::delete_bookmark_folder,
+            bookmarks::open_bookmark,
+            audio::report_audio_state,
+            audio::list_playing_audio,
+            audio::stop_all_audio,
+            snippets::expand_snippet,
+            #[cfg(desktop)]
+            autofill::login_form_detected,
+            #[cfg(desktop)]
+            autofill::fill_login,
+            #[cfg(desktop)]
+            autofill::save_login,
+            #[cfg(desktop)]
+            autofill::delete_login,
+            #[cfg(desktop)]
+            autofill::saved_login_username,
+            #[cfg(desktop)]
+            titlebar::get_titlebar_layout,
+            #[cfg(desktop)]
+            titlebar::set_chrome_height,
+            #[cfg(desktop)]
+            titlebar::set_chrome_visible,
+            #[cfg(desktop)]
+            titlebar::set_drag_regions,
+            #[cfg(desktop)]
+            titlebar::start_window_drag,
+            #[cfg(desktop)]
+            titlebar::window_control,
+            #[cfg(desktop)]
+            vibrancy::set_vibrancy,
+            #[cfg(desktop)]
+            headers::set_header_secret,
+            #[cfg(desktop)]
+            headers::delete_header_secret,
+            crash::list_crash_reports,
+            crash::send_crash_report,
+            crash::delete_crash_report,
+            diagnostics::get_diagnostics,
+            icons::fetch_platform_icon,
+            lock::lock_app,
+            lock::unlock_app,
+            lock::is_app_locked,
+            logging::set_log_level,
+            logging::get_recent_logs,
+            recent::get_recent_platforms,
+            startup::app_ready,
+            startup::get_startup_profile,
+            suspend::suspend_webview,
+            suspend::list_suspended_webviews,
+            usage::get_usage_stats,
+            usage::reset_usage_stats,
+            share::take_shared_text,
+            share::share_to_platform,
+            #[cfg(desktop)]
+            hotkey::set_toggle_window_shortcut,
+            #[cfg(desktop)]
+            shortcuts::list_shortcuts,
+            #[cfg(desktop)]
+            shortcuts::set_shortcut,
+            #[cfg(desktop)]
+            autostart::enable_autostart,
+            #[cfg(desktop)]
+            autostart::disable_autostart,
+            #[cfg(desktop)]
+            autostart::is_autostart_enabled,
+            #[cfg(desktop)]
+            updater::check_for_updates,
+            #[cfg(desktop)]
+            updater::download_update,
+            #[cfg(desktop)]
+            updater::install_update_and_restart
+        ]))
+        .setup(move |app| {
             use tauri::Manager;
             use tauri::WindowEvent;
             use std::sync::Mutex;
             use std::time::Instant;
 
+            app.manage(ai_window_manager::WebviewRegistry::default());
+            app.manage(dictation::DictationState::default());
+            app.manage(bridge::BridgeState::default());
+            app.manage(tts::TtsState::default());
+            app.manage(prompt::PendingPrompts::default());
+            app.manage(http_api::HttpApiState::default());
+            app.manage(event_bridge::EventBridgeState::default());
+            app.manage(share::ShareState::default());
+            app.manage(usage::UsageState::default());
+            app.manage(notes::NotesState::default());
+            app.manage(history::HistoryState::default());
+            app.manage(bookmarks::BookmarksState::default());
+            app.manage(audio::AudioState::default());
+            app.manage(suspend::SuspendState::default());
+            app.manage(recycle::RecycleState::default());
+            app.manage(connectivity::ConnectivityState::default());
+            app.manage(netlog::NetworkLogState::default());
+            #[cfg(mobile)]
+            mobile::init(app.handle());
+            app.manage(flight::FlightState::default());
+            app.manage(detach::DetachState::default());
+            #[cfg(desktop)]
+            app.manage(pip::PipState::default());
+            app.manage(lock::LockState::default());
+            #[cfg(desktop)]
+            app.manage(hotkey::HotkeyState::default());
+            #[cfg(desktop)]
+            app.manage(updater::UpdaterState::default());
+            #[cfg(desktop)]
+            app.manage(power::PowerState::default());
+            #[cfg(desktop)]
+            app.manage(taskbar::TaskbarState::default());
+            dns::apply_settings(app.handle());
+            proxy::init(app.handle());
+            flight::init(app.handle());
+            logging::apply_settings(app.handle());
+            startup::mark("managed state");
+
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                // Linux and Windows dev builds need the scheme registered at runtime;
+                // bundled builds get it from the installer.
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                app.deep_link().register_all()?;
+
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        deeplink::handle(&handle, url.as_str());
+                    }
+                });
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    for url in urls {
+                        deeplink::handle(&app.handle(), url.as_str());
+                    }
+                }
+            }
+
+            startup::mark("deep links");
+
+            let start_hidden = cli_args.hidden;
+            // A deep link in argv is already delivered by the deep-link plugin's get_current().
+            cli::apply(app.handle(), &cli::CliArgs { deep_link: None, ..cli_args });
+            #[cfg(desktop)]
+            {
+                tray::init(app.handle())?;
+                app_menu::init(app.handle())?;
+                hotkey::init(app.handle())?;
+            }
+            #[cfg(target_os = "macos")]
+            {
+                macos::init_dock_menu(app.handle());
+                macos::init_services();
+            }
+            startup::mark("tray, menu and shortcuts");
+            // Servers, watchers and the update check wait for the first paint.
+            startup::schedule_deferred(app.handle());
+
             let main_window = app.get_webview_window("main").unwrap();
+            #[cfg(desktop)]
+            {
+                titlebar::init(app.handle());
+                vibrancy::init(app.handle());
+            }
+            if start_hidden {
+                // Launched at login: keep sessions warm without showing the window.
+                let _ = main_window.hide();
+                tracing::info!("started hidden");
+            }
 
             // Restore saved window state
             if let Some(state) = load_window_state(&app.handle()) {
@@ -121,7 +591,7 @@ pub fn run() {
                 use tauri::PhysicalSize;
                 let _ = main_window.set_size(PhysicalSize::new(state.width, state.height));
                 let _ = main_window.set_position(PhysicalPosition::new(state.x, state.y));
-                eprintln!("[setup] Restored window: {}x{} at ({},{})", state.width, state.height, state.x, state.y);
+                tracing::info!("Restored window: {}x{} at ({},{})", state.width, state.height, state.x, state.y);
             }
 
             let window_clone = main_window.clone();
@@ -129,48 +599,56 @@ pub fn run() {
             // Throttle state: last resize timestamp
             let last_resize = Mutex::new(Instant::now());
 
-            eprintln!("[setup] Window resize listener registered");
+            tracing::debug!("Window resize listener registered");
 
             main_window.on_window_event(move |event| {
                 match event {
+                    WindowEvent::Focused(focused) => {
+                        usage::on_focus_changed(&window_clone.app_handle(), *focused);
+                        // Mobile apps lose focus when sent to the background.
+                        #[cfg(mobile)]
+                        if !focused {
+                            mobile::on_background(&window_clone.app_handle());
+                        }
+                    }
                     WindowEvent::Resized(physical_size) => {
-                        // Throttle: skip if less than 16ms (~60fps) since last update
+                        // Throttle: ~60fps normally, slower in battery saver
+                        #[cfg(desktop)]
+                        let min_interval = power::resize_interval(&window_clone.app_handle());
+                        #[cfg(not(desktop))]
+                        let min_interval = std::time::Duration::from_millis(16);
                         {
                             let mut last = last_resize.lock().unwrap();
                             let now = Instant::now();
-                            if now.duration_since(*last).as_millis() < 16 {
+                            if now.duration_since(*last) < min_interval {
                                 return;
                             }
                             *last = now;
                         }
 
                         let scale_factor = window_clone.scale_factor().unwrap_or(2.0);
+                        let (position, size) = ai_window_manager::child_bounds_for(*physical_size, scale_factor);
 
-                        let tab_logical_height = ai_window_manager::TAB_BAR_LOGICAL_HEIGHT;
-                        let tab_physical_height = (tab_logical_height * scale_factor) as u32;
-
-                        let child_y = tab_physical_height as i32;
-                        let child_width = physical_size.width;
-                        let child_height = physical_size.height.saturating_sub(tab_physical_height);
-
-                        eprintln!(
-                            "[resize] window={}x{} scale={} tab_phys={} child: y={} w={} h={}",
+                        tracing::debug!(
+                            "resize window={}x{} scale={} child: y={} w={} h={}",
                             physical_size.width, physical_size.height,
-                            scale_factor, tab_physical_height,
-                            child_y, child_width, child_height
+                            scale_factor, position.y, size.width, size.height
                         );
 
-                        let webviews = window_clone.app_handle().webviews();
-                        for webview in webviews.values() {
-                            if webview.label() != "main" {
-                                use tauri::PhysicalPosition;
-                                use tauri::PhysicalSize;
-                                let _ = webview.set_position(PhysicalPosition::new(0, child_y));
-                                let _ = webview.set_size(PhysicalSize::new(child_width, child_height));
-                            }
-                        }
+                        ai_window_manager::reposition_children(&window_clone.app_handle(), position, size);
+                    }
+                    WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size, .. } => {
+                        let (position, size) = ai_window_manager::child_bounds_for(*new_inner_size, *scale_factor);
+                        tracing::debug!(
+                            "scale factor changed to {} child: y={} w={} h={}",
+                            scale_factor, position.y, size.width, size.height
+                        );
+                        ai_window_manager::reposition_all_children(&window_clone.app_handle(), position, size);
                     }
-                    WindowEvent::CloseRequested { .. } => {
+                    WindowEvent::ThemeChanged(theme) => {
+                        theme::on_system_theme_changed(&window_clone.app_handle(), *theme);
+                    }
+                    WindowEvent::CloseRequested { api, .. } => {
                         // Save window state on close
                         if let (Ok(size), Ok(pos)) = (
                             window_clone.inner_size(),
@@ -184,13 +662,39 @@ pub fn run() {
                             };
                             save_window_state(&window_clone.app_handle(), &state);
                         }
+
+                        #[cfg(desktop)]
+                        {
+                            let tray_settings: tray::TraySettings = settings::section(&window_clone.app_handle(), "tray");
+                            if tray_settings.close_to_tray {
+                                api.prevent_close();
+                                let _ = window_clone.hide();
+                                tracing::info!("window hidden to tray");
+                            }
+                        }
+                        #[cfg(not(desktop))]
+                        let _ = api;
                     }
                     _ => {}
                 }
             });
 
+            startup::mark("setup done");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            #[cfg(mobile)]
+            if let tauri::RunEvent::Resumed = event {
+                mobile::on_resume(app);
+            }
+            if let tauri::RunEvent::Exit = event {
+                usage::flush(app);
+                persist::flush();
+                // A downloaded update is applied on quit so it never interrupts a session.
+                #[cfg(desktop)]
+                updater::install_pending(app);
+            }
+        });
 }
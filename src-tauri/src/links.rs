@@ -0,0 +1,41 @@
+use tauri::{AppHandle, Emitter, WebviewBuilder, Wry};
+use url::Url;
+
+/// Middle-clicking a link opens it in a new tab, as in a browser. Pages can't
+/// open background tabs themselves, so the click is reported back to Rust.
+const MIDDLE_CLICK_JS: &str = r#"
+(function() {
+    var linkFor = function(e) {
+        if (e.button !== 1 || !e.target || !e.target.closest) return null;
+        var a = e.target.closest('a[href]');
+        return a && /^https?:/i.test(a.href) ? a : null;
+    };
+    // Keeps Windows from entering autoscroll mode on the press.
+    document.addEventListener('mousedown', function(e) {
+        if (linkFor(e)) e.preventDefault();
+    }, true);
+    document.addEventListener('auxclick', function(e) {
+        var a = linkFor(e);
+        if (!a) return;
+        e.preventDefault();
+        e.stopPropagation();
+        window.__TAURI_INTERNALS__.invoke('open_link_in_new_tab', { url: a.href }).catch(function() {});
+    }, true);
+})();
+"#;
+
+pub fn configure_builder(builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(MIDDLE_CLICK_JS)
+}
+
+/// Called by the middle-click handler in child webviews; goes through the
+/// same `new_tab_request` flow as `window.open`.
+#[tauri::command]
+pub fn open_link_in_new_tab(app: AppHandle, url: String) -> Result<(), String> {
+    let url = Url::parse(&url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Refusing to open '{}' in a new tab", url));
+    }
+    tracing::debug!("middle-click new tab {}", url);
+    app.emit("new_tab_request", url.to_string()).map_err(|e| e.to_string())
+}
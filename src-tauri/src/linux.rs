@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+extern "C" {
+    fn webkit_get_major_version() -> u32;
+    fn webkit_get_minor_version() -> u32;
+    fn webkit_get_micro_version() -> u32;
+}
+
+/// Environment variables WebKitGTK reads at startup that help with black or
+/// blank webviews.
+const WEBKIT_FLAGS: &[&str] = &[
+    "WEBKIT_DISABLE_COMPOSITING_MODE",
+    "WEBKIT_DISABLE_DMABUF_RENDERER",
+    "WEBKIT_DISABLE_SANDBOX_THIS_IS_DANGEROUS",
+    "WEBKIT_FORCE_SANDBOX",
+    "GDK_BACKEND",
+    "LIBGL_ALWAYS_SOFTWARE",
+];
+
+/// `"linuxCompat"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct LinuxCompatSettings {
+    /// Apply the workarounds known for the detected GPU.
+    auto_workarounds: bool,
+}
+
+impl Default for LinuxCompatSettings {
+    fn default() -> Self {
+        Self { auto_workarounds: true }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum Renderer {
+    Nvidia,
+    Amd,
+    Intel,
+    /// VMware, VirtualBox, QEMU and virtio GPUs.
+    Virtual,
+    /// No DRM device, e.g. a container or a remote session.
+    Software,
+    Unknown,
+}
+
+/// What was decided at startup, for `get_webkit_info`.
+struct Startup {
+    renderer: Renderer,
+    /// Flags the user set before launch.
+    preset: Vec<&'static str>,
+    /// Flags set here, with the reason.
+    applied: Vec<(&'static str, &'static str)>,
+}
+
+static STARTUP: OnceLock<Startup> = OnceLock::new();
+
+/// GPU vendor of the first DRM card, from its PCI vendor id.
+fn detect_renderer() -> Renderer {
+    let vendors: Vec<String> = std::fs::read_dir("/sys/class/drm")
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("card") && !e.file_name().to_string_lossy().contains('-'))
+        .filter_map(|e| std::fs::read_to_string(e.path().join("device/vendor")).ok())
+        .map(|v| v.trim().to_lowercase())
+        .collect();
+    if vendors.is_empty() {
+        return Renderer::Software;
+    }
+    // The proprietary driver is the one with the DMA-BUF problems; prefer it on hybrid laptops.
+    if vendors.iter().any(|v| v == "0x10de") {
+        return Renderer::Nvidia;
+    }
+    match vendors[0].as_str() {
+        "0x1002" => Renderer::Amd,
+        "0x8086" => Renderer::Intel,
+        "0x15ad" | "0x80ee" | "0x1234" | "0x1af4" | "0x1b36" => Renderer::Virtual,
+        _ => Renderer::Unknown,
+    }
+}
+
+/// Known workarounds for `renderer`.
+fn workarounds(renderer: Renderer) -> &'static [(&'static str, &'static str)] {
+    match renderer {
+        Renderer::Nvidia => &[("WEBKIT_DISABLE_DMABUF_RENDERER", "NVIDIA driver shows blank webviews with DMA-BUF")],
+        Renderer::Virtual | Renderer::Software => &[
+            ("WEBKIT_DISABLE_COMPOSITING_MODE", "no usable GPU for accelerated compositing"),
+            ("WEBKIT_DISABLE_DMABUF_RENDERER", "no usable GPU for DMA-BUF"),
+        ],
+        _ => &[],
+    }
+}
+
+/// Detect the renderer and set the matching WebKitGTK variables. Runs at
+/// the top of `run()`, before `gpu::apply_process_flags` and any webview;
+/// values the user set themselves are left alone.
+pub fn apply_process_flags() {
+    let cfg: LinuxCompatSettings = serde_json::from_value(
+        crate::cli::read_json("settings.json", serde_json::json!({}))["linuxCompat"].clone(),
+    )
+    .unwrap_or_default();
+    let renderer = detect_renderer();
+    let preset: Vec<&'static str> = WEBKIT_FLAGS.iter().copied().filter(|v| std::env::var_os(v).is_some()).collect();
+    let mut applied = Vec::new();
+    if cfg.auto_workarounds {
+        for &(var, reason) in workarounds(renderer) {
+            if std::env::var_os(var).is_none() {
+                std::env::set_var(var, "1");
+                applied.push((var, reason));
+            }
+        }
+    }
+    tracing::info!("renderer {:?}, WebKitGTK workarounds applied: {:?}", renderer, applied);
+    let _ = STARTUP.set(Startup { renderer, preset, applied });
+}
+
+/// WebKitGTK version, detected renderer and each flag in effect with where it came from.
+#[tauri::command]
+pub fn get_webkit_info() -> serde_json::Value {
+    let version = unsafe {
+        format!("{}.{}.{}", webkit_get_major_version(), webkit_get_minor_version(), webkit_get_micro_version())
+    };
+    let startup = STARTUP.get();
+    let flags: serde_json::Map<String, serde_json::Value> = WEBKIT_FLAGS
+        .iter()
+        .filter_map(|&var| {
+            let value = std::env::var(var).ok()?;
+            let source = match startup {
+                Some(s) if s.preset.contains(&var) => "environment".to_string(),
+                Some(s) => match s.applied.iter().find(|(v, _)| *v == var) {
+                    Some((_, reason)) => format!("auto: {}", reason),
+                    None => "settings".to_string(),
+                },
+                None => "unknown".to_string(),
+            };
+            Some((var.to_string(), serde_json::json!({ "value": value, "source": source })))
+        })
+        .collect();
+    let session = if std::env::var_os("WAYLAND_DISPLAY").is_some() { "wayland" } else { "x11" };
+    serde_json::json!({
+        "webkitVersion": version,
+        "renderer": startup.map(|s| s.renderer).unwrap_or(Renderer::Unknown),
+        "session": session,
+        "flags": flags,
+    })
+}
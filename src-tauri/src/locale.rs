@@ -0,0 +1,55 @@
+use tauri::{AppHandle, WebviewBuilder, Wry};
+
+use crate::platforms;
+
+/// Overrides `navigator.language(s)` before any page script runs.
+const LANGUAGE_JS: &str = r#"
+(function(languages) {
+    Object.defineProperty(Navigator.prototype, 'language', { get: function() { return languages[0]; }, configurable: true });
+    Object.defineProperty(Navigator.prototype, 'languages', { get: function() { return languages.slice(); }, configurable: true });
+})(__LANGUAGES__);
+"#;
+
+/// Only plain BCP 47 tags are accepted since the value ends up in a script.
+fn valid_tag(tag: &str) -> bool {
+    !tag.is_empty() && tag.len() <= 35 && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// `fr-FR` -> `["fr-FR", "fr"]`
+fn languages(tag: &str) -> Vec<String> {
+    let mut languages = vec![tag.to_string()];
+    if let Some((primary, _)) = tag.split_once('-') {
+        languages.push(primary.to_string());
+    }
+    languages
+}
+
+/// Accept-Language value for a platform's language setting.
+pub fn accept_language(tag: &str) -> Option<String> {
+    let tag = tag.trim();
+    if !valid_tag(tag) {
+        return None;
+    }
+    let languages = languages(tag);
+    let mut value = languages[0].clone();
+    if let Some(primary) = languages.get(1) {
+        value.push_str(&format!(",{};q=0.9", primary));
+    }
+    Some(value)
+}
+
+/// Make the page see the platform's language. The Accept-Language header is
+/// sent by `headers::apply` alongside the platform's other headers.
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let language = platforms::load(app).into_iter().find(|p| p.id == platform_id).and_then(|p| p.language);
+    let Some(tag) = language.as_deref().map(str::trim).filter(|t| !t.is_empty()) else {
+        return builder;
+    };
+    if !valid_tag(tag) {
+        tracing::warn!("ignoring invalid language '{}' for '{}'", tag, platform_id);
+        return builder;
+    }
+    tracing::debug!("creating '{}' with language {}", platform_id, tag);
+    let languages = serde_json::to_string(&languages(tag)).unwrap_or_default();
+    builder.initialization_script(&LANGUAGE_JS.replace("__LANGUAGES__", &languages))
+}
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{ai_window_manager, settings};
+
+/// `"appLock"` section of settings.json.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppLockSettings {
+    /// Lock after this many minutes of system idle; 0 disables.
+    pub idle_minutes: u64,
+    /// Hex SHA-256 of the unlock PIN. Without one the lock is a privacy screen
+    /// that any click dismisses.
+    pub pin_sha256: Option<String>,
+}
+
+/// While locked, every child webview is hidden and `create_or_show_webview`
+/// refuses to show one, so page content can't leak past the lock screen.
+#[derive(Default)]
+pub struct LockState {
+    locked: AtomicBool,
+}
+
+pub fn is_locked(app: &AppHandle) -> bool {
+    app.try_state::<LockState>().is_some_and(|s| s.locked.load(Ordering::SeqCst))
+}
+
+pub fn lock(app: &AppHandle, reason: &str) {
+    if app.state::<LockState>().locked.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let _ = ai_window_manager::hide_all_webviews(app.clone());
+    tracing::info!("app locked ({})", reason);
+    let _ = app.emit("app_locked", reason);
+}
+
+fn sha256_hex(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[tauri::command]
+pub fn lock_app(app: AppHandle) {
+    lock(&app, "manual");
+}
+
+/// Unlock with the PIN from settings (if one is set). The frontend re-shows
+/// the active tab when it receives `app_unlocked`.
+#[tauri::command]
+pub fn unlock_app(app: AppHandle, pin: Option<String>) -> Result<(), String> {
+    let cfg: AppLockSettings = settings::section(&app, "appLock");
+    if let Some(expected) = cfg.pin_sha256 {
+        let given = sha256_hex(pin.as_deref().unwrap_or_default());
+        if !given.eq_ignore_ascii_case(&expected) {
+            tracing::warn!("unlock attempt with wrong PIN");
+            return Err("Incorrect PIN".to_string());
+        }
+    }
+    if app.state::<LockState>().locked.swap(false, Ordering::SeqCst) {
+        tracing::info!("app unlocked");
+        let _ = app.emit("app_unlocked", ());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_app_locked(app: AppHandle) -> bool {
+    is_locked(&app)
+}
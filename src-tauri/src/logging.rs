@@ -0,0 +1,130 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::{Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::settings;
+
+const LOG_FILE_PREFIX: &str = "anybrain";
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_LEVEL: &str = "info";
+
+/// `"logging"` section of settings.json.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct LoggingSettings {
+    /// `error`, `warn`, `info`, `debug`, `trace`, or a full `EnvFilter` directive.
+    level: Option<String>,
+}
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+/// Keeps the background file writer alive; dropping it would lose buffered lines.
+static GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// Rotated daily, next to the rest of the app data so it's easy to find and
+/// available before the Tauri runtime (and its path resolver) exists.
+pub fn log_dir() -> PathBuf {
+    crate::cli::data_dir().join("logs")
+}
+
+fn parse_filter(level: &str) -> Result<EnvFilter, String> {
+    EnvFilter::try_new(level).map_err(|e| format!("Invalid log level '{}': {}", level, e))
+}
+
+/// Install the global subscriber: stderr plus a rotating file, which guest mode
+/// skips or mutes. Runs first thing in `run()` so CLI parsing is logged too.
+/// `RUST_LOG` overrides the default.
+pub fn init() {
+    let initial = std::env::var("RUST_LOG").unwrap_or_else(|_| DEFAULT_LEVEL.to_string());
+    let filter = parse_filter(&initial).unwrap_or_else(|_| EnvFilter::new(DEFAULT_LEVEL));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let appender = if crate::guest::active() {
+        None
+    } else {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(log_dir())
+            .ok()
+    };
+    let file_layer = appender.map(|appender| {
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let _ = GUARD.set(guard);
+        // Guest mode can also start later, after the file is open.
+        let on_disk = tracing_subscriber::filter::filter_fn(|_| !crate::guest::active());
+        fmt::layer().with_ansi(false).with_writer(writer).with_filter(on_disk)
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(file_layer)
+        .try_init();
+    let _ = FILTER.set(handle);
+}
+
+fn set_level(level: &str) -> Result<(), String> {
+    let filter = parse_filter(level)?;
+    FILTER
+        .get()
+        .ok_or("Logging is not initialized")?
+        .reload(filter)
+        .map_err(|e| e.to_string())?;
+    tracing::info!("log level set to {}", level);
+    Ok(())
+}
+
+/// Apply the level saved in settings once the app is up.
+pub fn apply_settings(app: &AppHandle) {
+    let cfg: LoggingSettings = settings::section(app, "logging");
+    if let Some(level) = cfg.level {
+        if let Err(e) = set_level(&level) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// Change verbosity at runtime; the frontend persists the choice in settings.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), String> {
+    set_level(&level)
+}
+
+/// The last `lines` lines (default 500) across the newest log files, oldest first,
+/// for attaching to bug reports.
+#[tauri::command]
+pub fn get_recent_logs(lines: Option<usize>) -> Result<String, String> {
+    let wanted = lines.unwrap_or(500);
+    let mut files: Vec<PathBuf> = std::fs::read_dir(log_dir())
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    // Daily file names end in the date, so lexical order is chronological.
+    files.sort();
+
+    let mut collected: Vec<String> = Vec::new();
+    for path in files.iter().rev() {
+        let content = std::fs::read_to_string(path).unwrap_or_default();
+        let mut chunk: Vec<String> = content.lines().map(str::to_string).collect();
+        chunk.append(&mut collected);
+        collected = chunk;
+        if collected.len() >= wanted {
+            break;
+        }
+    }
+    let start = collected.len().saturating_sub(wanted);
+    Ok(collected[start..].join("\n"))
+}
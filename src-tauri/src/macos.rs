@@ -0,0 +1,205 @@
+use std::ffi::{c_char, c_void, CString};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+type Id = *mut c_void;
+type Sel = *mut c_void;
+
+extern "C" {
+    fn objc_getClass(name: *const c_char) -> Id;
+    fn sel_registerName(name: *const c_char) -> Sel;
+    fn object_getClass(obj: Id) -> Id;
+    fn class_addMethod(cls: Id, name: Sel, imp: *const c_void, types: *const c_char) -> bool;
+    fn objc_msgSend();
+}
+
+const OPEN_RECENT_SELECTOR: &str = "anybrainOpenRecent:";
+/// Must match `NSMessage` in Info.plist, plus the `:userData:error:` suffix.
+const SHARE_SERVICE_SELECTOR: &str = "shareToAnyBrain:userData:error:";
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+/// (platform id, title) pairs shown in the dock menu, most recent first.
+static DOCK_ITEMS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+/// The retained `NSUserActivity` currently offered to Handoff, as an address.
+static ACTIVITY: Mutex<usize> = Mutex::new(0);
+
+fn sel(name: &str) -> Sel {
+    let name = CString::new(name).unwrap();
+    unsafe { sel_registerName(name.as_ptr()) }
+}
+
+fn class(name: &str) -> Id {
+    let name = CString::new(name).unwrap();
+    unsafe { objc_getClass(name.as_ptr()) }
+}
+
+unsafe fn send0(obj: Id, selector: &str) -> Id {
+    let f: unsafe extern "C" fn(Id, Sel) -> Id = std::mem::transmute(objc_msgSend as *const ());
+    f(obj, sel(selector))
+}
+
+unsafe fn send1(obj: Id, selector: &str, arg: Id) -> Id {
+    let f: unsafe extern "C" fn(Id, Sel, Id) -> Id = std::mem::transmute(objc_msgSend as *const ());
+    f(obj, sel(selector), arg)
+}
+
+unsafe fn nsstring(s: &str) -> Id {
+    let c = CString::new(s.replace('\0', "")).unwrap();
+    let f: unsafe extern "C" fn(Id, Sel, *const c_char) -> Id = std::mem::transmute(objc_msgSend as *const ());
+    f(class("NSString"), sel("stringWithUTF8String:"), c.as_ptr())
+}
+
+/// `[wkWebView loadRequest:]` with extra HTTP headers, since WKWebView offers
+/// no hook to add headers to requests it issues itself.
+pub(crate) unsafe fn load_request_with_headers(wk_webview: Id, url: &str, headers: &[(String, String)]) {
+    if wk_webview.is_null() {
+        return;
+    }
+    let nsurl = send1(class("NSURL"), "URLWithString:", nsstring(url));
+    if nsurl.is_null() {
+        return;
+    }
+    let request = send1(class("NSMutableURLRequest"), "requestWithURL:", nsurl);
+    let set_header: unsafe extern "C" fn(Id, Sel, Id, Id) = std::mem::transmute(objc_msgSend as *const ());
+    for (name, value) in headers {
+        set_header(request, sel("setValue:forHTTPHeaderField:"), nsstring(value), nsstring(name));
+    }
+    send1(wk_webview, "loadRequest:", request);
+}
+
+/// `-[NSApplicationDelegate applicationDockMenu:]`
+extern "C" fn application_dock_menu(this: Id, _cmd: Sel, _sender: Id) -> Id {
+    unsafe {
+        let menu = send0(send0(class("NSMenu"), "alloc"), "init");
+        let items = DOCK_ITEMS.lock().unwrap();
+        for (index, (_, title)) in items.iter().enumerate() {
+            let init: unsafe extern "C" fn(Id, Sel, Id, Sel, Id) -> Id = std::mem::transmute(objc_msgSend as *const ());
+            let item = init(
+                send0(class("NSMenuItem"), "alloc"),
+                sel("initWithTitle:action:keyEquivalent:"),
+                nsstring(title),
+                sel(OPEN_RECENT_SELECTOR),
+                nsstring(""),
+            );
+            send1(item, "setTarget:", this);
+            let set_tag: unsafe extern "C" fn(Id, Sel, isize) = std::mem::transmute(objc_msgSend as *const ());
+            set_tag(item, sel("setTag:"), index as isize);
+            send1(menu, "addItem:", item);
+            send0(item, "release");
+        }
+        send0(menu, "autorelease")
+    }
+}
+
+/// Action of each dock menu item; the tag is the index into `DOCK_ITEMS`.
+extern "C" fn open_recent(_this: Id, _cmd: Sel, sender: Id) {
+    let tag = unsafe {
+        let get_tag: unsafe extern "C" fn(Id, Sel) -> isize = std::mem::transmute(objc_msgSend as *const ());
+        get_tag(sender, sel("tag"))
+    };
+    let platform_id = DOCK_ITEMS.lock().unwrap().get(tag as usize).map(|(id, _)| id.clone());
+    if let (Some(app), Some(platform_id)) = (APP.get(), platform_id) {
+        crate::show_main_window(app);
+        let _ = app.emit("activate_platform", platform_id);
+    }
+}
+
+/// Services menu handler: reads the selected text off the pasteboard.
+extern "C" fn share_service(_this: Id, _cmd: Sel, pboard: Id, _user_data: Id, _error: *mut Id) {
+    let text = unsafe {
+        let string = send1(pboard, "stringForType:", nsstring("public.utf8-plain-text"));
+        if string.is_null() {
+            return;
+        }
+        let utf8: unsafe extern "C" fn(Id, Sel) -> *const c_char = std::mem::transmute(objc_msgSend as *const ());
+        let ptr = utf8(string, sel("UTF8String"));
+        if ptr.is_null() {
+            return;
+        }
+        std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    };
+    if let Some(app) = APP.get() {
+        if let Err(e) = crate::share::receive(app, text, None) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// Teach tao's application delegate to supply a dock menu. Must run on the main thread.
+pub fn init_dock_menu(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+    unsafe {
+        let ns_app = send0(class("NSApplication"), "sharedApplication");
+        let delegate = send0(ns_app, "delegate");
+        if delegate.is_null() {
+            tracing::warn!("no application delegate, dock menu unavailable");
+            return;
+        }
+        let cls = object_getClass(delegate);
+        class_addMethod(cls, sel("applicationDockMenu:"), application_dock_menu as *const c_void, b"@@:@\0".as_ptr() as *const c_char);
+        class_addMethod(cls, sel(OPEN_RECENT_SELECTOR), open_recent as *const c_void, b"v@:@\0".as_ptr() as *const c_char);
+    }
+}
+
+/// Register the delegate as provider for the "Send to AnyBrain" service
+/// declared in Info.plist. Must run after `init_dock_menu`.
+pub fn init_services() {
+    unsafe {
+        let ns_app = send0(class("NSApplication"), "sharedApplication");
+        let delegate = send0(ns_app, "delegate");
+        if delegate.is_null() {
+            return;
+        }
+        let cls = object_getClass(delegate);
+        class_addMethod(cls, sel(SHARE_SERVICE_SELECTOR), share_service as *const c_void, b"v@:@@^@\0".as_ptr() as *const c_char);
+        send1(ns_app, "setServicesProvider:", delegate);
+    }
+}
+
+/// Offer `url` to Handoff as a web browsing activity, replacing the previous
+/// one; `None` withdraws it. Must run on the main thread.
+pub fn set_browsing_activity(url: Option<&str>) {
+    let mut current = ACTIVITY.lock().unwrap();
+    unsafe {
+        if *current != 0 {
+            let activity = *current as Id;
+            send0(activity, "invalidate");
+            send0(activity, "release");
+            *current = 0;
+        }
+        let Some(url) = url else { return };
+        let nsurl = send1(class("NSURL"), "URLWithString:", nsstring(url));
+        if nsurl.is_null() {
+            return;
+        }
+        let activity = send1(
+            send0(class("NSUserActivity"), "alloc"),
+            "initWithActivityType:",
+            nsstring("NSUserActivityTypeBrowsingWeb"),
+        );
+        send1(activity, "setWebpageURL:", nsurl);
+        send0(activity, "becomeCurrent");
+        *current = activity as usize;
+    }
+}
+
+/// Pin a view's appearance to dark (`Some(true)`) or light, or let it follow
+/// the system again with `None`.
+pub(crate) unsafe fn set_view_appearance(view: Id, dark: Option<bool>) {
+    if view.is_null() {
+        return;
+    }
+    let appearance = match dark {
+        Some(dark) => send1(
+            class("NSAppearance"),
+            "appearanceNamed:",
+            nsstring(if dark { "NSAppearanceNameDarkAqua" } else { "NSAppearanceNameAqua" }),
+        ),
+        None => std::ptr::null_mut(),
+    };
+    send1(view, "setAppearance:", appearance);
+}
+
+pub fn set_dock_platforms(items: Vec<(String, String)>) {
+    *DOCK_ITEMS.lock().unwrap() = items;
+}
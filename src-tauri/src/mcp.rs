@@ -0,0 +1,162 @@
+//! Model Context Protocol server over stdio, started with `anybrain --mcp`.
+//!
+//! The MCP process doesn't own any webviews; it forwards tool calls to the
+//! running app through the local HTTP API (which must be enabled), so agents
+//! get the same logged-in sessions the user sees.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+struct ApiClient {
+    base: String,
+    token: String,
+}
+
+impl ApiClient {
+    fn get(&self, path: &str) -> Result<Value, String> {
+        let resp = ureq::get(&format!("{}{}", self.base, path))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .call();
+        Self::decode(resp)
+    }
+
+    fn post(&self, path: &str, body: Value) -> Result<Value, String> {
+        let resp = ureq::post(&format!("{}{}", self.base, path))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .send_json(body);
+        Self::decode(resp)
+    }
+
+    fn decode(resp: Result<ureq::Response, ureq::Error>) -> Result<Value, String> {
+        match resp {
+            Ok(r) => r.into_json().map_err(|e| e.to_string()),
+            Err(ureq::Error::Status(code, r)) => {
+                let body: Value = r.into_json().unwrap_or(Value::Null);
+                Err(body["error"].as_str().map(|s| s.to_string()).unwrap_or_else(|| format!("HTTP {}", code)))
+            }
+            Err(e) => Err(format!("AnyBrain is not reachable (is the HTTP API enabled?): {}", e)),
+        }
+    }
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_platforms",
+            "description": "List the AI platforms configured in AnyBrain.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "open_platform",
+            "description": "Bring a platform's tab to the front in AnyBrain.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "platform": { "type": "string", "description": "Platform id or name, e.g. 'claude'" } },
+                "required": ["platform"]
+            }
+        },
+        {
+            "name": "send_prompt",
+            "description": "Type a prompt into a platform's chat box and submit it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "platform": { "type": "string" },
+                    "prompt": { "type": "string" }
+                },
+                "required": ["platform", "prompt"]
+            }
+        },
+        {
+            "name": "get_last_response",
+            "description": "Return the text of the latest answer shown in a platform's tab.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "platform": { "type": "string" } },
+                "required": ["platform"]
+            }
+        }
+    ])
+}
+
+fn call_tool(api: &ApiClient, name: &str, args: &Value) -> Result<String, String> {
+    let arg = |key: &str| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("Missing argument '{}'", key))
+    };
+    match name {
+        "list_platforms" => {
+            let list = api.get("/platforms")?;
+            Ok(serde_json::to_string_pretty(&list).unwrap_or_default())
+        }
+        "open_platform" => {
+            api.post("/open", json!({ "platform": arg("platform")? }))?;
+            Ok("Opened.".to_string())
+        }
+        "send_prompt" => {
+            api.post("/prompt", json!({ "platform": arg("platform")?, "text": arg("prompt")?, "send": true }))?;
+            Ok("Prompt sent.".to_string())
+        }
+        "get_last_response" => {
+            let platform = arg("platform")?;
+            let encoded: String = url::form_urlencoded::byte_serialize(platform.as_bytes()).collect();
+            let resp = api.get(&format!("/response?platform={}", encoded))?;
+            Ok(resp["text"].as_str().unwrap_or_default().to_string())
+        }
+        other => Err(format!("Unknown tool '{}'", other)),
+    }
+}
+
+fn handle(api: &ApiClient, request: &Value) -> Option<Value> {
+    // Notifications carry no id and get no response.
+    let id = request.get("id")?.clone();
+    let method = request["method"].as_str().unwrap_or_default();
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "anybrain", "version": env!("CARGO_PKG_VERSION") }
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => {
+            let params = &request["params"];
+            let name = params["name"].as_str().unwrap_or_default();
+            // Tool failures are reported in-band so the agent can see them.
+            Ok(match call_tool(api, name, &params["arguments"]) {
+                Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+                Err(e) => json!({ "content": [{ "type": "text", "text": e }], "isError": true }),
+            })
+        }
+        _ => Err(json!({ "code": -32601, "message": format!("Method not found: {}", method) })),
+    };
+    Some(match result {
+        Ok(r) => json!({ "jsonrpc": "2.0", "id": id, "result": r }),
+        Err(e) => json!({ "jsonrpc": "2.0", "id": id, "error": e }),
+    })
+}
+
+/// Serve newline-delimited JSON-RPC on stdin/stdout until stdin closes.
+pub fn run_stdio(port: u16, token: String) {
+    let api = ApiClient { base: format!("http://127.0.0.1:{}", port), token };
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => handle(&api, &request),
+            Err(e) => Some(json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32700, "message": e.to_string() } })),
+        };
+        if let Some(response) = response {
+            let _ = writeln!(stdout, "{}", response);
+            let _ = stdout.flush();
+        }
+    }
+}
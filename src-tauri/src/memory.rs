@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::{suspend, usage};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Don't react again for this long after reclaiming, to give the OS time to settle.
+const COOLDOWN: Duration = Duration::from_secs(60);
+/// Hidden webviews left running under a warning; critical pressure suspends them all.
+const KEEP_HIDDEN_ON_WARNING: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Pressure {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl Pressure {
+    fn as_str(self) -> &'static str {
+        match self {
+            Pressure::Normal => "normal",
+            Pressure::Warning => "warning",
+            Pressure::Critical => "critical",
+        }
+    }
+}
+
+/// The kernel's own pressure verdict (1 normal, 2 warn, 4 critical), the same
+/// signal that drives dispatch memory-pressure sources.
+#[cfg(target_os = "macos")]
+struct Monitor;
+
+#[cfg(target_os = "macos")]
+impl Monitor {
+    fn new() -> Option<Self> {
+        Some(Monitor)
+    }
+
+    fn level(&self) -> Option<Pressure> {
+        let mut level: libc::c_int = 0;
+        let mut size = std::mem::size_of::<libc::c_int>();
+        let rc = unsafe {
+            libc::sysctlbyname(
+                b"kern.memorystatus_vm_pressure_level\0".as_ptr() as *const libc::c_char,
+                &mut level as *mut _ as *mut libc::c_void,
+                &mut size,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if rc != 0 {
+            return None;
+        }
+        Some(match level {
+            4 => Pressure::Critical,
+            2 => Pressure::Warning,
+            _ => Pressure::Normal,
+        })
+    }
+}
+
+/// Windows' low-memory resource notification; it doesn't distinguish levels,
+/// so a low signal is treated as critical.
+#[cfg(windows)]
+struct Monitor(windows::Win32::Foundation::HANDLE);
+
+#[cfg(windows)]
+impl Monitor {
+    fn new() -> Option<Self> {
+        use windows::Win32::System::Memory::{CreateMemoryResourceNotification, LowMemoryResourceNotification};
+        unsafe { CreateMemoryResourceNotification(LowMemoryResourceNotification).ok().map(Monitor) }
+    }
+
+    fn level(&self) -> Option<Pressure> {
+        use windows::Win32::System::Memory::QueryMemoryResourceNotification;
+        let mut low = windows::Win32::Foundation::BOOL::default();
+        unsafe { QueryMemoryResourceNotification(self.0, &mut low).ok()? };
+        Some(if low.as_bool() { Pressure::Critical } else { Pressure::Normal })
+    }
+}
+
+/// No pressure notification on Linux desktops, so derive it from MemAvailable.
+#[cfg(not(any(target_os = "macos", windows)))]
+struct Monitor;
+
+#[cfg(not(any(target_os = "macos", windows)))]
+impl Monitor {
+    fn new() -> Option<Self> {
+        std::path::Path::new("/proc/meminfo").exists().then_some(Monitor)
+    }
+
+    fn level(&self) -> Option<Pressure> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let field = |name: &str| -> Option<u64> {
+            meminfo
+                .lines()
+                .find(|l| l.starts_with(name))?
+                .split_whitespace()
+                .nth(1)?
+                .parse()
+                .ok()
+        };
+        let total = field("MemTotal:")?;
+        let available = field("MemAvailable:")?;
+        let percent = available * 100 / total.max(1);
+        Some(match percent {
+            0..=5 => Pressure::Critical,
+            6..=12 => Pressure::Warning,
+            _ => Pressure::Normal,
+        })
+    }
+}
+
+/// Hidden webviews ordered least recently used first.
+fn hidden_by_lru(app: &AppHandle) -> Vec<String> {
+    let stats = usage::stats(app);
+    let mut hidden = suspend::hidden_webviews(app);
+    hidden.sort_by_key(|id| stats.get(id).map(|u| u.last_used).unwrap_or(0));
+    hidden
+}
+
+fn reclaim(app: &AppHandle, pressure: Pressure) -> Vec<String> {
+    let hidden = hidden_by_lru(app);
+    let count = match pressure {
+        Pressure::Critical => hidden.len(),
+        _ => hidden.len().saturating_sub(KEEP_HIDDEN_ON_WARNING),
+    };
+    hidden
+        .into_iter()
+        .take(count)
+        .filter(|id| matches!(suspend::suspend(app, id, "memory_pressure"), Ok(true)))
+        .collect()
+}
+
+/// Watch for OS memory pressure and suspend least-recently-used hidden webviews,
+/// emitting `memory_pressure` with what was reclaimed.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let Some(monitor) = Monitor::new() else {
+            tracing::warn!("memory pressure monitoring unavailable");
+            return;
+        };
+        let mut last = Pressure::Normal;
+        let mut last_reclaim: Option<Instant> = None;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let Some(pressure) = monitor.level() else { continue };
+            let rising = pressure > last;
+            last = pressure;
+            if pressure == Pressure::Normal {
+                continue;
+            }
+            let cooling = last_reclaim.is_some_and(|at| at.elapsed() < COOLDOWN);
+            if !rising && cooling {
+                continue;
+            }
+            let reclaimed = reclaim(&app, pressure);
+            last_reclaim = Some(Instant::now());
+            if reclaimed.is_empty() {
+                continue;
+            }
+            tracing::warn!("{} memory pressure, suspended {:?}", pressure.as_str(), reclaimed);
+            let _ = app.emit(
+                "memory_pressure",
+                serde_json::json!({ "level": pressure.as_str(), "reclaimed": reclaimed }),
+            );
+        }
+    });
+}
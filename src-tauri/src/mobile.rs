@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewBuilder, Wry};
+
+use crate::ai_window_manager;
+use crate::error::AppError;
+
+/// Horizontal swipes starting this close to a screen edge switch platforms;
+/// further in they belong to the page (carousels, code blocks).
+const SWIPE_JS: &str = r#"
+(function() {
+    var EDGE = 24, MIN_DISTANCE = 80;
+    var start = null;
+    document.addEventListener('touchstart', function(e) {
+        if (e.touches.length !== 1) { start = null; return; }
+        var t = e.touches[0];
+        var fromEdge = t.clientX < EDGE || t.clientX > window.innerWidth - EDGE;
+        start = fromEdge ? { x: t.clientX, y: t.clientY } : null;
+    }, { capture: true, passive: true });
+    document.addEventListener('touchend', function(e) {
+        if (!start) return;
+        var t = e.changedTouches[0];
+        var dx = t.clientX - start.x, dy = t.clientY - start.y;
+        start = null;
+        if (Math.abs(dx) < MIN_DISTANCE || Math.abs(dx) < Math.abs(dy) * 2) return;
+        var direction = dx < 0 ? 'next' : 'previous';
+        window.__TAURI_INTERNALS__.invoke('switch_platform', { direction: direction }).catch(function() {});
+    }, { capture: true, passive: true });
+})();
+"#;
+
+/// Safe-area insets in logical pixels, as the frontend reads them from
+/// `env(safe-area-inset-*)`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct SafeArea {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+static SAFE_AREA: Mutex<SafeArea> = Mutex::new(SafeArea { top: 0.0, right: 0.0, bottom: 0.0, left: 0.0 });
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    Next,
+    Previous,
+}
+
+/// What was on screen when the app last went to the background. The OS may
+/// kill a backgrounded app at any time, so this is all there is to restore.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MobileState {
+    active_platform: Option<String>,
+    url: Option<String>,
+}
+
+/// The saved URL, handed out once when its platform's webview is first created.
+pub struct RestoreState(Mutex<Option<MobileState>>);
+
+fn state_file_path(app: &AppHandle) -> PathBuf {
+    app.path().app_local_data_dir().unwrap().join("mobile_state.json")
+}
+
+fn load_state(app: &AppHandle) -> MobileState {
+    crate::persist::read_to_string(&state_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Load what was saved before the last time the app went away.
+pub fn init(app: &AppHandle) {
+    app.manage(RestoreState(Mutex::new(Some(load_state(app)))));
+}
+
+/// A single webview filling the screen inside the safe area; there's no tab
+/// bar, platforms are switched by swiping.
+pub fn child_bounds(physical_size: PhysicalSize<u32>, scale_factor: f64) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    let inset = *SAFE_AREA.lock().unwrap();
+    let px = |logical: f64| (logical * scale_factor).round() as u32;
+    let position = PhysicalPosition::new(px(inset.left) as i32, px(inset.top) as i32);
+    let size = PhysicalSize::new(
+        physical_size.width.saturating_sub(px(inset.left) + px(inset.right)),
+        physical_size.height.saturating_sub(px(inset.top) + px(inset.bottom)),
+    );
+    (position, size)
+}
+
+pub fn configure_builder(builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(SWIPE_JS)
+}
+
+/// The frontend reports the insets on load and on rotation.
+#[tauri::command]
+pub fn set_safe_area(app: AppHandle, insets: SafeArea) -> Result<(), AppError> {
+    *SAFE_AREA.lock().unwrap() = insets;
+    let window = app.get_window("main").ok_or_else(|| AppError::not_found("Window", "main"))?;
+    let (position, size) = ai_window_manager::compute_child_bounds(&window);
+    ai_window_manager::reposition_all_children(&app, position, size);
+    Ok(())
+}
+
+/// Show the next or previous visible platform, wrapping around. Called by
+/// the swipe script and the frontend; the frontend follows via `activate_platform`.
+#[tauri::command]
+pub fn switch_platform(app: AppHandle, direction: Direction) -> Result<Option<String>, AppError> {
+    let visible: Vec<_> = crate::platforms::load(&app).into_iter().filter(|p| !p.hidden).collect();
+    if visible.is_empty() {
+        return Ok(None);
+    }
+    let current = ai_window_manager::active_platform(&app)
+        .and_then(|id| visible.iter().position(|p| p.id == id))
+        .unwrap_or(0);
+    let next = match direction {
+        Direction::Next => (current + 1) % visible.len(),
+        Direction::Previous => (current + visible.len() - 1) % visible.len(),
+    };
+    let platform = &visible[next];
+    ai_window_manager::create_or_show_webview(app.clone(), platform.id.clone(), platform.url.clone(), 0.0)?;
+    let _ = app.emit("activate_platform", &platform.id);
+    Ok(Some(platform.id.clone()))
+}
+
+/// Where the app was when it last went to the background, so the frontend
+/// can reopen that tab after the OS killed the process.
+#[tauri::command]
+pub fn get_mobile_state(app: AppHandle) -> MobileState {
+    app.try_state::<RestoreState>()
+        .and_then(|s| s.0.lock().unwrap().clone())
+        .unwrap_or_default()
+}
+
+/// The page `platform_id` was on when the app was last backgrounded, once.
+pub fn take_restore_url(app: &AppHandle, platform_id: &str) -> Option<String> {
+    let state = app.try_state::<RestoreState>()?;
+    let mut saved = state.0.lock().unwrap();
+    if saved.as_ref()?.active_platform.as_deref() != Some(platform_id) {
+        return None;
+    }
+    saved.take()?.url
+}
+
+/// Android back button: back in the active webview's history, and once
+/// there's none left, to the background the way the system would.
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub async fn handle_back_button(app: AppHandle) -> Result<bool, AppError> {
+    if ai_window_manager::go_back(app.clone(), None).await? {
+        return Ok(true);
+    }
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(false);
+    };
+    window.with_webview(|wv| {
+        wv.jni_handle().exec(|env, activity, _webview| {
+            // Keeps the activity alive, unlike finishing it.
+            if let Err(e) = env.call_method(activity, "moveTaskToBack", "(Z)Z", &[jni::objects::JValue::Bool(1)]) {
+                tracing::warn!("moveTaskToBack failed: {}", e);
+            }
+        });
+    })?;
+    Ok(false)
+}
+
+/// The main window lost focus, i.e. the app went to the background: save
+/// what's on screen and get everything onto disk while the process still runs.
+pub fn on_background(app: &AppHandle) {
+    let active_platform = ai_window_manager::active_platform(app);
+    let url = active_platform
+        .as_deref()
+        .and_then(|id| ai_window_manager::get_webview(app, id))
+        .and_then(|w| w.url().ok())
+        .map(|u| u.to_string());
+    let state = MobileState { active_platform, url };
+    if let Ok(json) = serde_json::to_string(&state) {
+        crate::persist::write(state_file_path(app), json);
+    }
+    crate::usage::flush(app);
+    crate::persist::flush();
+}
+
+/// Back in the foreground: the window may have been resized (rotation,
+/// split screen) while away, so lay the active webview out again.
+pub fn on_resume(app: &AppHandle) {
+    let Some(window) = app.get_window("main") else { return };
+    let (position, size) = ai_window_manager::compute_child_bounds(&window);
+    ai_window_manager::reposition_all_children(app, position, size);
+    if let Some(webview) = ai_window_manager::active_platform(app).and_then(|id| ai_window_manager::get_webview(app, &id)) {
+        let _ = webview.show();
+    }
+}
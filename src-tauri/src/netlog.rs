@@ -0,0 +1,139 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Webview};
+
+use crate::ai_window_manager;
+
+/// Platforms with request logging switched on. Developer tooling: entries go
+/// to the regular log under the `network` target.
+#[derive(Default)]
+pub struct NetworkLogState {
+    enabled: Mutex<HashSet<String>>,
+}
+
+/// One request as reported by the page.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkLogEntry {
+    method: String,
+    url: String,
+    /// HTTP status, 0 for network errors or when the engine doesn't expose it.
+    status: u16,
+    duration_ms: f64,
+    /// `fetch`, `xhr`, `navigation`, or a resource initiator type (`script`, `img`, ...).
+    kind: String,
+}
+
+/// Wraps `fetch` and `XMLHttpRequest` and observes resource timing, batching
+/// entries back to `network_log_entries` once a second. Idempotent; the
+/// argument switches reporting on or off.
+const NETLOG_JS: &str = r#"
+(function(enabled) {
+    var s = window.__anybrainNetLog;
+    if (!s) {
+        if (!enabled) return;
+        s = window.__anybrainNetLog = { enabled: true, queue: [] };
+        var push = function(entry) { if (s.enabled) s.queue.push(entry); };
+        var origFetch = window.fetch;
+        window.fetch = function(input, init) {
+            var started = performance.now();
+            var method = (init && init.method) || (input && input.method) || 'GET';
+            var url = typeof input === 'string' ? input : (input && input.url) || String(input);
+            return origFetch.apply(this, arguments).then(function(res) {
+                push({ method: method, url: url, status: res.status, durationMs: performance.now() - started, kind: 'fetch' });
+                return res;
+            }, function(err) {
+                push({ method: method, url: url, status: 0, durationMs: performance.now() - started, kind: 'fetch' });
+                throw err;
+            });
+        };
+        var origOpen = XMLHttpRequest.prototype.open;
+        var origSend = XMLHttpRequest.prototype.send;
+        XMLHttpRequest.prototype.open = function(method, url) {
+            this.__anybrainReq = { method: method, url: String(url) };
+            return origOpen.apply(this, arguments);
+        };
+        XMLHttpRequest.prototype.send = function() {
+            var xhr = this, req = xhr.__anybrainReq, started = performance.now();
+            if (req) {
+                xhr.addEventListener('loadend', function() {
+                    push({ method: req.method, url: req.url, status: xhr.status, durationMs: performance.now() - started, kind: 'xhr' });
+                });
+            }
+            return origSend.apply(this, arguments);
+        };
+        if (window.PerformanceObserver) {
+            new PerformanceObserver(function(list) {
+                list.getEntries().forEach(function(e) {
+                    if (e.initiatorType === 'fetch' || e.initiatorType === 'xmlhttprequest') return;
+                    push({ method: 'GET', url: e.name, status: e.responseStatus || 0, durationMs: e.duration, kind: e.entryType === 'navigation' ? 'navigation' : e.initiatorType });
+                });
+            }).observe({ entryTypes: ['navigation', 'resource'] });
+        }
+        setInterval(function() {
+            if (!s.queue.length) return;
+            var entries = s.queue.splice(0, s.queue.length);
+            window.__TAURI_INTERNALS__.invoke('network_log_entries', { entries: entries }).catch(function() {});
+        }, 1000);
+    }
+    s.enabled = enabled;
+})(__ENABLED__);
+"#;
+
+fn is_enabled(app: &AppHandle, platform_id: &str) -> bool {
+    app.try_state::<NetworkLogState>()
+        .is_some_and(|s| s.enabled.lock().unwrap().contains(platform_id))
+}
+
+fn inject(webview: &Webview, enabled: bool) {
+    let _ = webview.eval(&NETLOG_JS.replace("__ENABLED__", if enabled { "true" } else { "false" }));
+}
+
+/// Called when a page finishes loading; navigation wipes the injected hooks.
+pub fn on_page_load(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    if is_enabled(app, platform_id) {
+        inject(webview, true);
+    }
+}
+
+#[tauri::command]
+pub fn start_network_log(app: AppHandle, platform_id: String) -> Result<(), String> {
+    let webview = ai_window_manager::get_webview(&app, &platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    app.state::<NetworkLogState>().enabled.lock().unwrap().insert(platform_id.clone());
+    inject(&webview, true);
+    tracing::info!(target: "network", "request logging started for '{}'", platform_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_network_log(app: AppHandle, platform_id: String) -> Result<(), String> {
+    app.state::<NetworkLogState>().enabled.lock().unwrap().remove(&platform_id);
+    if let Some(webview) = ai_window_manager::get_webview(&app, &platform_id) {
+        inject(&webview, false);
+    }
+    tracing::info!(target: "network", "request logging stopped for '{}'", platform_id);
+    Ok(())
+}
+
+/// Reported by the injected script; the calling webview identifies the platform.
+#[tauri::command]
+pub fn network_log_entries(app: AppHandle, webview: Webview, entries: Vec<NetworkLogEntry>) {
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    if !is_enabled(&app, &platform_id) {
+        return;
+    }
+    for entry in entries {
+        tracing::info!(
+            target: "network",
+            "'{}' {} {} {} -> {} in {:.0}ms",
+            platform_id,
+            entry.kind,
+            entry.method,
+            entry.url,
+            entry.status,
+            entry.duration_ms
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// A free-form note, optionally tied to a platform and to the conversation
+/// (page URL) it was written about. Kept in `notes.json`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Note {
+    pub id: String,
+    pub platform_id: Option<String>,
+    pub conversation_url: Option<String>,
+    pub text: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Loaded lazily from disk, newest first.
+#[derive(Default)]
+pub struct NotesState {
+    notes: Mutex<Option<Vec<Note>>>,
+}
+
+fn notes_file_path(app: &AppHandle) -> PathBuf {
+    let dir = crate::workspaces::dir(app);
+    dir.join("notes.json")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn new_id() -> String {
+    use rand::distributions::Alphanumeric;
+    use rand::Rng;
+    rand::thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect()
+}
+
+fn with_notes<R>(app: &AppHandle, f: impl FnOnce(&mut Vec<Note>) -> R) -> R {
+    let state = app.state::<NotesState>();
+    let mut notes = state.notes.lock().unwrap();
+    let notes = notes.get_or_insert_with(|| {
+        crate::persist::read_to_string(&notes_file_path(app))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    });
+    f(notes)
+}
+
+fn save(app: &AppHandle) {
+    if let Ok(json) = with_notes(app, |notes| serde_json::to_string_pretty(notes)) {
+        crate::persist::write(notes_file_path(app), json);
+    }
+}
+
+/// Drop the loaded notes so the next access reads the active workspace's file.
+pub(crate) fn unload(app: &AppHandle) {
+    *app.state::<NotesState>().notes.lock().unwrap() = None;
+}
+
+/// Notes for one platform, or all of them when `platform_id` is omitted.
+/// Passing `conversation_url` narrows to notes about that conversation.
+#[tauri::command]
+pub fn list_notes(app: AppHandle, platform_id: Option<String>, conversation_url: Option<String>) -> Vec<Note> {
+    with_notes(&app, |notes| {
+        notes
+            .iter()
+            .filter(|n| platform_id.is_none() || n.platform_id == platform_id)
+            .filter(|n| conversation_url.is_none() || n.conversation_url == conversation_url)
+            .cloned()
+            .collect()
+    })
+}
+
+#[tauri::command]
+pub fn create_note(
+    app: AppHandle,
+    platform_id: Option<String>,
+    conversation_url: Option<String>,
+    text: String,
+) -> Note {
+    let now = now_millis();
+    let note = Note { id: new_id(), platform_id, conversation_url, text, created_at: now, updated_at: now };
+    with_notes(&app, |notes| notes.insert(0, note.clone()));
+    save(&app);
+    note
+}
+
+#[tauri::command]
+pub fn update_note(app: AppHandle, id: String, text: String) -> Result<Note, String> {
+    let note = with_notes(&app, |notes| {
+        let note = notes.iter_mut().find(|n| n.id == id)?;
+        note.text = text;
+        note.updated_at = now_millis();
+        Some(note.clone())
+    })
+    .ok_or_else(|| format!("Note '{}' not found", id))?;
+    save(&app);
+    Ok(note)
+}
+
+#[tauri::command]
+pub fn delete_note(app: AppHandle, id: String) -> Result<(), String> {
+    let removed = with_notes(&app, |notes| {
+        let before = notes.len();
+        notes.retain(|n| n.id != id);
+        notes.len() != before
+    });
+    if !removed {
+        return Err(format!("Note '{}' not found", id));
+    }
+    save(&app);
+    Ok(())
+}
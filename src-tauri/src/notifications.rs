@@ -0,0 +1,63 @@
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tauri::AppHandle;
+
+/// A button on a notification. Clicking it emits `notification_action` with
+/// the notification id and this action's id.
+#[derive(Deserialize, Debug, Clone)]
+pub struct NotificationAction {
+    pub id: String,
+    pub title: String,
+}
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Show a native notification and return its id. Action buttons need a
+/// notification server that reports clicks back, which only the freedesktop
+/// protocol does; elsewhere the notification is shown without them.
+#[tauri::command]
+pub fn notify(
+    app: AppHandle,
+    title: String,
+    body: Option<String>,
+    actions: Option<Vec<NotificationAction>>,
+) -> Result<u32, String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let actions = actions.unwrap_or_default();
+    #[cfg(target_os = "linux")]
+    if !actions.is_empty() {
+        show_with_actions(app, id, title, body.unwrap_or_default(), actions)?;
+        return Ok(id);
+    }
+    if !actions.is_empty() {
+        tracing::debug!("notification actions aren't supported on this platform, dropping {}", actions.len());
+    }
+    use tauri_plugin_notification::NotificationExt;
+    let mut builder = app.notification().builder().title(&title);
+    if let Some(body) = &body {
+        builder = builder.body(body);
+    }
+    builder.show().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[cfg(target_os = "linux")]
+fn show_with_actions(app: AppHandle, id: u32, title: String, body: String, actions: Vec<NotificationAction>) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname("Brainer").summary(&title).body(&body);
+    for action in &actions {
+        notification.action(&action.id, &action.title);
+    }
+    let handle = notification.show().map_err(|e| e.to_string())?;
+    // Blocks until the notification is clicked, dismissed or expires.
+    std::thread::spawn(move || {
+        handle.wait_for_action(|action| {
+            if action == "__closed" {
+                return;
+            }
+            use tauri::Emitter;
+            let _ = app.emit("notification_action", serde_json::json!({ "id": id, "action": action }));
+        });
+    });
+    Ok(())
+}
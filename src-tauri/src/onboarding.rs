@@ -0,0 +1,67 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::platforms::{self, Platform};
+
+/// A platform we know how to set up out of the box.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogEntry {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub url: &'static str,
+    /// Part of the set a fresh install starts with.
+    pub default: bool,
+    /// Runs on the user's own machine or LAN; the URL is the project's
+    /// default and usually needs editing, and certificates are often self-signed.
+    pub self_hosted: bool,
+}
+
+/// Ids match the frontend's preset list so built-in icons line up.
+const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry { id: "openai", name: "ChatGPT", url: "https://chatgpt.com", default: true, self_hosted: false },
+    CatalogEntry { id: "claude", name: "Claude", url: "https://claude.ai", default: true, self_hosted: false },
+    CatalogEntry { id: "gemini", name: "Gemini", url: "https://gemini.google.com/app", default: true, self_hosted: false },
+    CatalogEntry { id: "deepseek", name: "DeepSeek", url: "https://chat.deepseek.com/", default: true, self_hosted: false },
+    CatalogEntry { id: "qwen", name: "通义千问", url: "https://tongyi.aliyun.com/qianwen/", default: false, self_hosted: false },
+    CatalogEntry { id: "kimi", name: "Kimi", url: "https://kimi.moonshot.cn/", default: false, self_hosted: false },
+    CatalogEntry { id: "zhipu", name: "智谱清言", url: "https://chatglm.cn/", default: false, self_hosted: false },
+    CatalogEntry { id: "minimax", name: "MiniMax", url: "https://api.minimax.chat/", default: false, self_hosted: false },
+    CatalogEntry { id: "open-webui", name: "Open WebUI", url: "http://localhost:3000/", default: false, self_hosted: true },
+    CatalogEntry {
+        id: "text-generation-webui",
+        name: "text-generation-webui",
+        url: "http://localhost:7860/",
+        default: false,
+        self_hosted: true,
+    },
+];
+
+#[tauri::command]
+pub fn list_platform_catalog() -> Vec<CatalogEntry> {
+    CATALOG.to_vec()
+}
+
+/// On first launch (no platforms.json yet) write the default set and tell the
+/// frontend it's looking at a fresh install. Returns the seeded JSON, or
+/// `None` when a platform list already exists.
+pub fn seed_if_first_run(app: &AppHandle) -> Option<String> {
+    let seeded: Vec<Platform> =
+        CATALOG.iter().filter(|e| e.default).map(|e| Platform::new(e.id.to_string(), e.name, e.url)).collect();
+    let json = serde_json::to_string_pretty(&seeded).ok()?;
+    let handle = app.clone();
+    let (committed, fresh) = crate::persist::update(platforms::platforms_file_path(app), move |_, current| {
+        if current.is_some() {
+            return Ok((None, false));
+        }
+        crate::changelog::record(&handle, None, &json, "onboarding");
+        Ok((Some(json), true))
+    })
+    .ok()?;
+    if !fresh {
+        return None;
+    }
+    tracing::info!("first run: seeded {} platforms", seeded.len());
+    let _ = app.emit("onboarding", serde_json::json!({ "platforms": seeded }));
+    Some(committed.data)
+}
@@ -0,0 +1,82 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::error::AppError;
+use crate::{adapters, ai_window_manager, bridge};
+
+/// Resolves to the conversation's link, or null when the page isn't one.
+/// Without a share flow that's the canonical URL or the page's own; with one,
+/// the share buttons are clicked in turn until the link element appears.
+const PERMALINK_JS: &str = r#"
+(function(cfg) {
+    var under = function(u) {
+        try { return !cfg.path || new URL(u).pathname.indexOf(cfg.path) === 0; } catch (e) { return false; }
+    };
+    if (!cfg.share) {
+        var canonical = document.querySelector('link[rel="canonical"]');
+        return [canonical && canonical.href, location.href].filter(function(u) { return u && under(u); })[0] || null;
+    }
+    if (!under(location.href)) return null;
+    return new Promise(function(resolve, reject) {
+        var step = 0, tries = 0;
+        (function next() {
+            var el = document.querySelector(cfg.share.link);
+            var link = el && (el.value || el.href || el.textContent.trim());
+            if (link) { resolve(link); return; }
+            var button = step < cfg.share.clicks.length && document.querySelector(cfg.share.clicks[step]);
+            if (button) { button.click(); step++; tries = 0; }
+            if (++tries > 40) { reject('share link did not appear'); return; }
+            setTimeout(next, 250);
+        })();
+    });
+})(__CONFIG__)
+"#;
+
+#[derive(Serialize)]
+struct ShareConfig<'a> {
+    clicks: &'a [&'a str],
+    link: &'a str,
+}
+
+#[derive(Serialize)]
+struct PermalinkConfig<'a> {
+    path: Option<&'a str>,
+    share: Option<ShareConfig<'a>>,
+}
+
+/// Where `platform_id`'s tab is right now.
+#[tauri::command]
+pub fn get_current_url(app: AppHandle, platform_id: String) -> Result<String, AppError> {
+    let webview = ai_window_manager::get_webview(&app, &platform_id).ok_or_else(|| AppError::not_found("Webview", &platform_id))?;
+    Ok(webview.url()?.to_string())
+}
+
+/// Copy a link to the conversation open in `platform_id`'s tab and return
+/// it. By default it's the page's own URL, which only opens for the signed-in
+/// user; `public` goes through the site's share dialog where the adapter
+/// knows it.
+#[tauri::command]
+pub async fn copy_conversation_link(app: AppHandle, platform_id: String, public: Option<bool>) -> Result<String, AppError> {
+    let current_url = get_current_url(app.clone(), platform_id.clone())?;
+    let adapter = match crate::flags::enabled(&app, crate::flags::ADAPTER_EXTRACTION, Some(&platform_id)) {
+        true => adapters::for_url(&current_url),
+        false => adapters::for_url(""),
+    };
+    let share = match (public.unwrap_or(false), &adapter.share) {
+        (false, _) => None,
+        (true, Some(flow)) => Some(ShareConfig { clicks: flow.clicks, link: flow.link }),
+        (true, None) => return Err(AppError::Unavailable(format!("No share flow known for {}", adapter.key))),
+    };
+    let config = serde_json::to_string(&PermalinkConfig { path: adapter.conversation_path, share })?;
+    let value = bridge::eval_with_result(&app, &platform_id, &PERMALINK_JS.replace("__CONFIG__", &config), Duration::from_secs(15)).await?;
+    let link = value
+        .as_str()
+        .filter(|l| l.starts_with("http://") || l.starts_with("https://"))
+        .ok_or_else(|| AppError::NotFound("This tab isn't showing a saved conversation".to_string()))?
+        .to_string();
+    app.clipboard().write_text(link.clone()).map_err(|e| AppError::Other(e.to_string()))?;
+    tracing::debug!("copied conversation link for '{}'", platform_id);
+    Ok(link)
+}
@@ -0,0 +1,195 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Saves arriving within this window of each other are written once.
+const BATCH_WINDOW: Duration = Duration::from_millis(250);
+
+/// Latest contents queued for one file. `dirty` means `data` hasn't reached
+/// disk yet; `busy` means a writer task owns the file. In guest mode slots
+/// are never drained, so they double as the session's in-memory storage.
+struct Slot {
+    data: Vec<u8>,
+    dirty: bool,
+    busy: bool,
+}
+
+#[derive(Default)]
+struct Queue {
+    slots: HashMap<PathBuf, Slot>,
+    /// Hash of what's on disk for each file we've read or written, so
+    /// identical snapshots can be dropped without touching the disk.
+    persisted: HashMap<PathBuf, u64>,
+}
+
+static QUEUE: Mutex<Option<Queue>> = Mutex::new(None);
+
+fn with_queue<R>(f: impl FnOnce(&mut Queue) -> R) -> R {
+    f(QUEUE.lock().unwrap().get_or_insert_with(Queue::default))
+}
+
+fn hash(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Write through a temp file so a crash mid-write never leaves a truncated file.
+fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path)
+}
+
+/// Write the newest queued contents until nothing new arrives, then release
+/// the file. Intermediate versions queued while waiting or writing are skipped.
+fn drain(path: PathBuf) {
+    loop {
+        std::thread::sleep(BATCH_WINDOW);
+        let data = with_queue(|queue| {
+            let slot = queue.slots.get_mut(&path)?;
+            if !slot.dirty {
+                queue.slots.remove(&path);
+                return None;
+            }
+            slot.dirty = false;
+            Some(slot.data.clone())
+        });
+        let Some(data) = data else { return };
+        let digest = hash(&data);
+        if with_queue(|queue| queue.persisted.get(&path) == Some(&digest)) {
+            continue;
+        }
+        match write_atomic(&path, &data) {
+            Ok(()) => {
+                with_queue(|queue| queue.persisted.insert(path.clone(), digest));
+                tracing::debug!("wrote {} ({} bytes)", path.display(), data.len());
+            }
+            Err(e) => tracing::warn!("failed to write {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Queue `data` to be written to `path` on the async runtime's blocking pool
+/// and return immediately. Back-to-back writes to the same file are batched,
+/// and contents identical to the latest known version are dropped. Returns
+/// whether the contents changed.
+pub fn write(path: PathBuf, data: impl Into<Vec<u8>>) -> bool {
+    let data = data.into();
+    let digest = hash(&data);
+    let known = with_queue(|queue| match queue.slots.get(&path) {
+        Some(slot) => Some(hash(&slot.data)),
+        None => queue.persisted.get(&path).copied(),
+    });
+    // First save of this file in the session: compare with what's on disk.
+    let known = known.or_else(|| {
+        let on_disk = hash(&fs::read(&path).ok()?);
+        with_queue(|queue| queue.persisted.insert(path.clone(), on_disk));
+        Some(on_disk)
+    });
+    if known == Some(digest) {
+        return false;
+    }
+
+    let guest = crate::guest::active();
+    let spawn = with_queue(|queue| {
+        let slot = queue.slots.entry(path.clone()).or_insert(Slot { data: Vec::new(), dirty: false, busy: false });
+        slot.data = data;
+        slot.dirty = !guest;
+        !std::mem::replace(&mut slot.busy, true) && !guest
+    });
+    if spawn {
+        tauri::async_runtime::spawn_blocking(move || drain(path));
+    }
+    true
+}
+
+/// Read `path`, preferring contents that are queued but not yet on disk.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    let queued = with_queue(|queue| queue.slots.get(path).map(|slot| slot.data.clone()));
+    match queued {
+        Some(data) => String::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        None => fs::read_to_string(path),
+    }
+}
+
+/// Outcome of an [`update`].
+pub struct Committed {
+    /// Counts the changes made to the file through `update` this session.
+    pub revision: u64,
+    /// The file's contents after the update.
+    pub data: String,
+    pub changed: bool,
+}
+
+type Job = Box<dyn FnOnce(&mut HashMap<PathBuf, u64>) + Send>;
+
+/// The one thread that runs read-modify-write jobs, in the order they arrive.
+fn writer() -> &'static Mutex<mpsc::Sender<Job>> {
+    static WRITER: OnceLock<Mutex<mpsc::Sender<Job>>> = OnceLock::new();
+    WRITER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        std::thread::spawn(move || {
+            let mut revisions = HashMap::new();
+            for job in rx {
+                let run = std::panic::AssertUnwindSafe(|| job(&mut revisions));
+                if std::panic::catch_unwind(run).is_err() {
+                    tracing::error!("persistence job panicked");
+                }
+            }
+        });
+        Mutex::new(tx)
+    })
+}
+
+/// Read-modify-write `path` on the single writer thread, so concurrent
+/// callers (several windows saving at once) can't interleave and lose each
+/// other's changes. `f` gets the current revision and contents and returns
+/// the new contents, or `None` to leave the file alone. Blocks until the job
+/// ran and returns the authoritative state along with whatever else `f`
+/// returned. `f` must not call `update`.
+pub fn update<F, R>(path: PathBuf, f: F) -> Result<(Committed, R), String>
+where
+    F: FnOnce(u64, Option<String>) -> Result<(Option<String>, R), String> + Send + 'static,
+    R: Send + 'static,
+{
+    let (tx, rx) = mpsc::sync_channel(1);
+    let job: Job = Box::new(move |revisions| {
+        let current = read_to_string(&path).ok();
+        let revision = revisions.get(&path).copied().unwrap_or(0);
+        let result = f(revision, current.clone()).map(|(next, value)| match next {
+            Some(data) if write(path.clone(), data.clone()) => {
+                revisions.insert(path.clone(), revision + 1);
+                (Committed { revision: revision + 1, data, changed: true }, value)
+            }
+            next => (Committed { revision, data: next.or(current).unwrap_or_default(), changed: false }, value),
+        });
+        let _ = tx.send(result);
+    });
+    const STOPPED: &str = "persistence writer has stopped";
+    writer().lock().unwrap().send(job).map_err(|_| STOPPED.to_string())?;
+    rx.recv().map_err(|_| STOPPED.to_string())?
+}
+
+/// Wait (bounded) for queued writes to land. Called on exit.
+pub fn flush() {
+    if crate::guest::active() {
+        return;
+    }
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while with_queue(|queue| !queue.slots.is_empty()) {
+        if Instant::now() >= deadline {
+            tracing::warn!("gave up waiting for pending writes on exit");
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
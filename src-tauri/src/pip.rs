@@ -0,0 +1,112 @@
+use std::sync::Mutex;
+use tauri::window::WindowBuilder;
+use tauri::{AppHandle, Emitter, LogicalSize, Manager, PhysicalPosition, WindowEvent};
+
+use crate::{ai_window_manager, platforms};
+
+const PIP_LABEL: &str = "pip";
+const PIP_SIZE: LogicalSize<f64> = LogicalSize { width: 360.0, height: 480.0 };
+/// Gap between the PiP window and the screen edges, in logical pixels.
+const PIP_MARGIN: f64 = 24.0;
+
+/// The platform currently shown picture-in-picture; there is at most one.
+#[derive(Default)]
+pub struct PipState {
+    current: Mutex<Option<String>>,
+}
+
+pub fn is_pip(app: &AppHandle, platform_id: &str) -> bool {
+    app.try_state::<PipState>()
+        .is_some_and(|s| s.current.lock().unwrap().as_deref() == Some(platform_id))
+}
+
+/// Move a tab into a small always-on-top window in the bottom-right corner so
+/// it can be watched while working elsewhere. The webview is re-parented, so
+/// a streaming answer keeps going.
+#[tauri::command]
+pub fn pip_webview(app: AppHandle, platform_id: String) -> Result<(), String> {
+    if crate::detach::window_for(&app, &platform_id).is_some() {
+        return Err(format!("'{}' is already in its own window", platform_id));
+    }
+    let current = app.state::<PipState>().current.lock().unwrap().clone();
+    match current {
+        Some(id) if id == platform_id => return Ok(()),
+        Some(_) => end_pip(app.clone())?,
+        None => {}
+    }
+    let webview = ai_window_manager::get_webview(&app, &platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    let title = platforms::load(&app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .map(|p| p.name)
+        .unwrap_or_else(|| platform_id.clone());
+
+    let mut builder = WindowBuilder::new(&app, PIP_LABEL)
+        .title(title)
+        .inner_size(PIP_SIZE.width, PIP_SIZE.height)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true);
+    let monitor = app.get_window("main").and_then(|w| w.current_monitor().ok().flatten());
+    if let Some(monitor) = monitor {
+        let scale = monitor.scale_factor();
+        let area = monitor.size().to_logical::<f64>(scale);
+        let origin = monitor.position().to_logical::<f64>(scale);
+        builder = builder.position(
+            origin.x + area.width - PIP_SIZE.width - PIP_MARGIN,
+            origin.y + area.height - PIP_SIZE.height - PIP_MARGIN * 3.0,
+        );
+    }
+    let window = builder.build().map_err(|e| e.to_string())?;
+    webview.reparent(&window).map_err(|e| e.to_string())?;
+    let _ = webview.set_position(PhysicalPosition::new(0, 0));
+    if let Ok(inner) = window.inner_size() {
+        let _ = webview.set_size(inner);
+    }
+    let _ = webview.set_auto_resize(true);
+    let _ = webview.show();
+    crate::throttle::on_shown(&webview);
+
+    // Closing the PiP window puts the tab back instead of closing it.
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { api, .. } = event {
+            api.prevent_close();
+            if let Err(e) = end_pip(handle.clone()) {
+                tracing::warn!("failed to end picture-in-picture: {}", e);
+            }
+        }
+    });
+
+    *app.state::<PipState>().current.lock().unwrap() = Some(platform_id.clone());
+    tracing::info!("'{}' in picture-in-picture", platform_id);
+    let _ = app.emit("pip_started", serde_json::json!({ "platformId": platform_id }));
+    Ok(())
+}
+
+/// Return the PiP tab to the main window, shown again if it's still the
+/// selected tab.
+#[tauri::command]
+pub fn end_pip(app: AppHandle) -> Result<(), String> {
+    let Some(platform_id) = app.state::<PipState>().current.lock().unwrap().take() else {
+        return Ok(());
+    };
+    let main = app.get_window("main").ok_or("Main window not found")?;
+    if let Some(webview) = ai_window_manager::get_webview(&app, &platform_id) {
+        let _ = webview.set_auto_resize(false);
+        webview.reparent(&main).map_err(|e| e.to_string())?;
+        if ai_window_manager::active_platform(&app).as_deref() == Some(platform_id.as_str()) {
+            let (position, size) = ai_window_manager::compute_child_bounds(&main);
+            let _ = webview.set_position(position);
+            let _ = webview.set_size(size);
+        } else {
+            let _ = webview.hide();
+            crate::throttle::on_hidden(&app, &webview);
+        }
+    }
+    if let Some(window) = app.get_window(PIP_LABEL) {
+        let _ = window.destroy();
+    }
+    let _ = app.emit("pip_ended", serde_json::json!({ "platformId": platform_id }));
+    Ok(())
+}
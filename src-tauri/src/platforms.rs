@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::Emitter;
+
+use crate::adapters;
+use crate::persist::Committed;
+
+/// A platform entry as stored in platforms.json by the frontend.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Platform {
+    pub id: String,
+    pub name: String,
+    /// Home URL, loaded when the tab is first opened.
+    pub url: String,
+    /// Where "new conversation" goes, if not where the site's adapter says.
+    #[serde(rename = "newConversationUrl", default, skip_serializing_if = "Option::is_none")]
+    pub new_conversation_url: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Extra request headers sent to the platform's host.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<PlatformHeader>,
+    /// Language tag (`en-US`) the platform is browsed in instead of the OS language.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Reload the page this often (seconds) while it's hidden or the user is idle.
+    #[serde(rename = "refreshInterval", default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u64>,
+    /// `Some(false)` turns spellcheck off, e.g. for prompts full of code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spellcheck: Option<bool>,
+    /// Dictionaries to check against (`en-US`), in preference order.
+    #[serde(rename = "spellcheckLanguages", default, skip_serializing_if = "Vec::is_empty")]
+    pub spellcheck_languages: Vec<String>,
+    /// Accept whatever certificate the platform's own host presents, for
+    /// self-hosted UIs behind a self-signed certificate.
+    #[serde(rename = "allowSelfSigned", default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_self_signed: bool,
+    /// WebView2 data folder, arguments and channel, Windows only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webview2: Option<crate::webview2::Webview2Options>,
+    /// Desktop or mobile user agent and viewport instead of the engine's own.
+    #[serde(rename = "siteMode", default, skip_serializing_if = "Option::is_none")]
+    pub site_mode: Option<crate::site_mode::SiteMode>,
+    /// `#rrggbb` shown while the tab loads, instead of the site's own color.
+    #[serde(rename = "backgroundColor", default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    /// Fields owned by the frontend that Rust doesn't interpret.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Platform {
+    /// A visible platform with no per-platform options set.
+    pub fn new(id: String, name: &str, url: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            url: url.to_string(),
+            new_conversation_url: None,
+            hidden: false,
+            headers: Vec::new(),
+            language: None,
+            refresh_interval: None,
+            spellcheck: None,
+            spellcheck_languages: Vec::new(),
+            allow_self_signed: false,
+            webview2: None,
+            site_mode: None,
+            background_color: None,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+/// An extra request header. Secret values aren't stored here but in the
+/// keychain (see `set_header_secret`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlatformHeader {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
+}
+
+/// platforms.json of the active workspace.
+pub fn platforms_file_path(app: &tauri::AppHandle) -> PathBuf {
+    crate::workspaces::dir(app).join("platforms.json")
+}
+
+/// Load the saved platform list; missing or malformed files yield an empty list.
+pub fn load(app: &tauri::AppHandle) -> Vec<Platform> {
+    crate::persist::read_to_string(&platforms_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Sent to every window after platforms.json changes. `source` is the label
+/// of the window whose save caused it, so that window can ignore its own echo.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PlatformsChanged<'a> {
+    revision: u64,
+    data: &'a str,
+    source: Option<&'a str>,
+}
+
+/// Rebuild the menus that list platforms and tell the windows, if the write
+/// changed anything.
+pub fn notify(app: &tauri::AppHandle, committed: &Committed, source: Option<&str>) {
+    if !committed.changed {
+        return;
+    }
+    #[cfg(desktop)]
+    crate::tray::refresh(app);
+    crate::recent::refresh(app);
+    let _ = app.emit("platforms_changed", PlatformsChanged { revision: committed.revision, data: &committed.data, source });
+}
+
+/// Edit the saved list through the persistence writer, so backend edits and
+/// window saves never overwrite each other. Returns what `f` returned.
+pub fn update<R: Send + 'static>(
+    app: &tauri::AppHandle,
+    f: impl FnOnce(&mut Vec<Platform>) -> Result<R, String> + Send + 'static,
+) -> Result<R, String> {
+    let handle = app.clone();
+    let (committed, value) = crate::persist::update(platforms_file_path(app), move |_, current| {
+        let mut list: Vec<Platform> = current.as_deref().and_then(|data| serde_json::from_str(data).ok()).unwrap_or_default();
+        let value = f(&mut list)?;
+        // Compact, like the frontend's own saves, so an echoed save is a no-op.
+        let json = serde_json::to_string(&list).map_err(|e| e.to_string())?;
+        crate::changelog::record(&handle, current.as_deref(), &json, "backend");
+        Ok((Some(json), value))
+    })?;
+    notify(app, &committed, None);
+    Ok(value)
+}
+
+/// URL of a fresh conversation: the platform's own setting, else the
+/// adapter's new-chat path on the platform's origin, else the home URL.
+pub fn new_conversation_url(platform: &Platform) -> String {
+    if let Some(url) = platform.new_conversation_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+        return url.to_string();
+    }
+    adapters::for_url(&platform.url)
+        .new_chat_path
+        .and_then(|path| url::Url::parse(&platform.url).ok()?.join(path).ok())
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| platform.url.clone())
+}
+
+/// Resolve a user-supplied platform reference (`claude`, `ChatGPT`, an id)
+/// against the saved list: exact id, then case-insensitive name, then the
+/// adapter key of the platform's URL.
+pub fn find(app: &tauri::AppHandle, query: &str) -> Option<Platform> {
+    let platforms = load(app);
+    let q = query.trim().to_lowercase();
+    platforms
+        .iter()
+        .find(|p| p.id == query)
+        .or_else(|| platforms.iter().find(|p| p.name.to_lowercase() == q))
+        .or_else(|| platforms.iter().find(|p| adapters::for_url(&p.url).key == q))
+        .or_else(|| platforms.iter().find(|p| p.id.to_lowercase().starts_with(&q)))
+        .cloned()
+}
@@ -0,0 +1,116 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{settings, suspend};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+const NORMAL_RESIZE_INTERVAL: Duration = Duration::from_millis(16);
+
+/// `"batterySaver"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct BatterySaverSettings {
+    /// `"auto"` (follow the power source), `"on"` or `"off"`.
+    mode: String,
+    /// In auto mode, only kick in once charge drops below `low_charge_percent`.
+    only_when_low: bool,
+    low_charge_percent: f32,
+    /// Hidden webviews are suspended after this long while saving power.
+    suspend_hidden_after_minutes: u64,
+    /// Minimum gap between child repositions during a window resize.
+    resize_interval_ms: u64,
+}
+
+impl Default for BatterySaverSettings {
+    fn default() -> Self {
+        Self {
+            mode: "auto".to_string(),
+            only_when_low: false,
+            low_charge_percent: 20.0,
+            suspend_hidden_after_minutes: 5,
+            resize_interval_ms: 50,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PowerState {
+    saver_active: AtomicBool,
+}
+
+/// Whether battery saver is currently in effect. Background work that isn't
+/// needed for what's on screen (preloading, keep-alive pings) should skip
+/// itself while this is true.
+pub fn saver_active(app: &AppHandle) -> bool {
+    app.try_state::<PowerState>().is_some_and(|s| s.saver_active.load(Ordering::Relaxed))
+}
+
+/// Throttle for the resize repositioning loop.
+pub fn resize_interval(app: &AppHandle) -> Duration {
+    if saver_active(app) {
+        let cfg: BatterySaverSettings = settings::section(app, "batterySaver");
+        Duration::from_millis(cfg.resize_interval_ms)
+    } else {
+        NORMAL_RESIZE_INTERVAL
+    }
+}
+
+/// (on battery, charge percent) for the first battery, if the machine has one.
+fn power_status() -> Option<(bool, f32)> {
+    let manager = starship_battery::Manager::new().ok()?;
+    let battery = manager.batteries().ok()?.next()?.ok()?;
+    let on_battery = battery.state() == starship_battery::State::Discharging;
+    let charge = battery.state_of_charge().value * 100.0;
+    Some((on_battery, charge))
+}
+
+fn should_save(cfg: &BatterySaverSettings, status: Option<(bool, f32)>) -> bool {
+    match cfg.mode.as_str() {
+        "on" => true,
+        "off" => false,
+        _ => match status {
+            Some((true, charge)) => !cfg.only_when_low || charge < cfg.low_charge_percent,
+            _ => false,
+        },
+    }
+}
+
+/// Poll the power source, flip battery saver on and off, and while it's on
+/// suspend webviews that have sat hidden longer than the configured limit.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut hidden_since: HashMap<String, Instant> = HashMap::new();
+        loop {
+            let cfg: BatterySaverSettings = settings::section(&app, "batterySaver");
+            let status = power_status();
+            let active = should_save(&cfg, status);
+            let state = app.state::<PowerState>();
+            if state.saver_active.swap(active, Ordering::Relaxed) != active {
+                tracing::info!("battery saver {}", if active { "on" } else { "off" });
+                let _ = app.emit(
+                    "battery_saver_changed",
+                    serde_json::json!({
+                        "active": active,
+                        "onBattery": status.map(|(on_battery, _)| on_battery),
+                        "charge": status.map(|(_, charge)| charge),
+                    }),
+                );
+            }
+
+            let hidden = suspend::hidden_webviews(&app);
+            hidden_since.retain(|id, _| hidden.contains(id));
+            for id in hidden {
+                let since = *hidden_since.entry(id.clone()).or_insert_with(Instant::now);
+                if active && since.elapsed() >= Duration::from_secs(cfg.suspend_hidden_after_minutes * 60) {
+                    let _ = suspend::suspend(&app, &id, "battery_saver");
+                }
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
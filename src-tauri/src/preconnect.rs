@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::net::ToSocketAddrs;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use url::Url;
+
+use crate::{ai_window_manager, platforms, settings};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `"preconnect"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct PreconnectSettings {
+    enabled: bool,
+    /// Wait this long after startup so warming doesn't compete with the first tab.
+    delay_seconds: u64,
+}
+
+impl Default for PreconnectSettings {
+    fn default() -> Self {
+        Self { enabled: true, delay_seconds: 3 }
+    }
+}
+
+/// Resolve the host and make a HEAD request, which fills the resolver cache
+/// and lets the network stack learn the route before the first tab needs it.
+/// With DoH on, the name is resolved through it so it never reaches the OS
+/// resolver.
+fn warm(url: &Url) {
+    let started = Instant::now();
+    if let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) {
+        match crate::dns::resolver() {
+            Some(doh) => {
+                let _ = ureq::Resolver::resolve(&doh, &format!("{}:{}", host, port));
+            }
+            None => {
+                let _ = (host, port).to_socket_addrs();
+            }
+        }
+    }
+    let resolved = started.elapsed();
+    let result = crate::proxy::agent_for(url.as_str()).head(url.as_str()).timeout(REQUEST_TIMEOUT).call();
+    tracing::debug!(
+        "preconnected {} (dns {}ms, total {}ms, ok={})",
+        url.host_str().unwrap_or_default(),
+        resolved.as_millis(),
+        started.elapsed().as_millis(),
+        result.is_ok()
+    );
+}
+
+/// Warm every host in the tab bar, once, shortly after startup. Skipped in
+/// battery saver and flight mode, and for the platform that's already open.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let cfg: PreconnectSettings = settings::section(&app, "preconnect");
+        if !cfg.enabled {
+            return;
+        }
+        std::thread::sleep(Duration::from_secs(cfg.delay_seconds));
+        #[cfg(desktop)]
+        if crate::power::saver_active(&app) {
+            return;
+        }
+        if crate::flight::active(&app) {
+            return;
+        }
+        let active = ai_window_manager::active_platform(&app);
+        let mut hosts = HashSet::new();
+        for platform in platforms::load(&app) {
+            if platform.hidden || active.as_deref() == Some(platform.id.as_str()) {
+                continue;
+            }
+            let Ok(url) = Url::parse(&platform.url) else { continue };
+            if !hosts.insert(url.host_str().unwrap_or_default().to_string()) {
+                continue;
+            }
+            std::thread::spawn(move || warm(&url));
+        }
+    });
+}
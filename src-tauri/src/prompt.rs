@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::adapters;
+use crate::ai_window_manager;
+
+/// Type `text` into the prompt box of `platform_id`'s webview, pressing send when `submit` is set.
+pub fn inject(app: &AppHandle, platform_id: &str, text: &str, submit: bool) -> Result<(), String> {
+    let webview = ai_window_manager::get_webview(app, platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    if crate::session::is_signed_out(platform_id) {
+        return Err(format!("'{}' is signed out", platform_id));
+    }
+    let current_url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+    let adapter = adapters::for_url(&current_url);
+    tracing::debug!("inject into '{}' via '{}' adapter ({} chars, submit={})", platform_id, adapter.key, text.len(), submit);
+    webview
+        .eval(&adapters::insert_prompt_js(adapter, text, submit))
+        .map_err(|e| e.to_string())
+}
+
+/// Prompts addressed to webviews that don't exist yet (deep links, CLI), delivered
+/// once the page reports `PageLoadEvent::Finished`.
+#[derive(Default)]
+pub struct PendingPrompts {
+    queue: Mutex<HashMap<String, (String, bool)>>,
+}
+
+/// Inject right away if the webview is live, otherwise hold the prompt until it loads.
+pub fn inject_or_queue(app: &AppHandle, platform_id: &str, text: &str, submit: bool) -> Result<(), String> {
+    if ai_window_manager::get_webview(app, platform_id).is_some() {
+        return inject(app, platform_id, text, submit);
+    }
+    tracing::debug!("queued for '{}' until page load", platform_id);
+    app.state::<PendingPrompts>()
+        .queue
+        .lock()
+        .unwrap()
+        .insert(platform_id.to_string(), (text.to_string(), submit));
+    Ok(())
+}
+
+/// Called from the page-load handler; delivers any prompt waiting for `platform_id`.
+pub fn flush_pending(app: &AppHandle, platform_id: &str) {
+    let pending = match app.try_state::<PendingPrompts>() {
+        Some(state) => state.queue.lock().unwrap().remove(platform_id),
+        None => None,
+    };
+    if let Some((text, submit)) = pending {
+        if let Err(e) = inject(app, platform_id, &text, submit) {
+            tracing::warn!("failed to deliver queued prompt to '{}': {}", platform_id, e);
+        }
+    }
+}
+
+/// Inject into whichever platform is currently shown in the main window.
+pub fn inject_active(app: &AppHandle, text: &str, submit: bool) -> Result<(), String> {
+    let platform_id = ai_window_manager::active_platform(app).ok_or("No active platform")?;
+    inject(app, &platform_id, text, submit)
+}
+
+#[tauri::command]
+pub fn send_prompt(
+    app: AppHandle,
+    platform_id: Option<String>,
+    text: String,
+    submit: bool,
+) -> Result<(), String> {
+    match platform_id {
+        Some(id) => inject(&app, &id, &text, submit),
+        None => inject_active(&app, &text, submit),
+    }
+}
@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use url::Url;
+
+use crate::settings;
+
+const PAC_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_PAC_BYTES: u64 = 256 * 1024;
+
+/// `"proxy"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct ProxySettings {
+    /// `"system"` (environment, then OS settings), `"manual"` or `"direct"`.
+    /// System mode also honors the top-level `useSystemProxy` toggle, which
+    /// turns it into a direct connection when off.
+    mode: String,
+    /// Proxy URL for manual mode, e.g. `http://proxy.corp:8080`.
+    url: Option<String>,
+    /// Extra hosts that bypass the proxy in manual mode.
+    bypass: Vec<String>,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self { mode: "system".to_string(), url: None, bypass: Vec::new() }
+    }
+}
+
+/// The proxy configuration currently in effect.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    /// `"settings"`, `"environment"`, `"system"` or `"none"`.
+    pub source: String,
+    /// Proxy used by the Rust HTTP clients; for PAC setups, the first proxy
+    /// the script names.
+    pub proxy: Option<String>,
+    pub pac_url: Option<String>,
+    /// Host patterns (`example.com`, `*.corp`, `.corp`, `<local>`) reached directly.
+    pub bypass: Vec<String>,
+}
+
+static CURRENT: Mutex<Option<ProxyConfig>> = Mutex::new(None);
+
+/// Run a helper tool without flashing a console window on Windows.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn with_scheme(proxy: &str) -> String {
+    if proxy.contains("://") {
+        proxy.to_string()
+    } else {
+        format!("http://{}", proxy)
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split([',', ';'])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn from_environment() -> Option<ProxyConfig> {
+    let var = |names: &[&str]| names.iter().find_map(|n| std::env::var(n).ok().filter(|v| !v.is_empty()));
+    let proxy = var(&["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"])?;
+    Some(ProxyConfig {
+        source: "environment".to_string(),
+        proxy: Some(with_scheme(&proxy)),
+        pac_url: None,
+        bypass: var(&["NO_PROXY", "no_proxy"]).map(|v| split_list(&v)).unwrap_or_default(),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn from_system() -> Option<ProxyConfig> {
+    let output = command_output("scutil", &["--proxy"])?;
+    let mut values = std::collections::HashMap::new();
+    let mut bypass = Vec::new();
+    let mut in_exceptions = false;
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("ExceptionsList") {
+            in_exceptions = true;
+            continue;
+        }
+        if in_exceptions {
+            if line == "}" {
+                in_exceptions = false;
+            } else if let Some((_, host)) = line.split_once(" : ") {
+                bypass.push(host.to_string());
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(" : ") {
+            values.insert(key.to_string(), value.to_string());
+        }
+    }
+    let enabled = |key: &str| values.get(key).map(String::as_str) == Some("1");
+    let pac_url = values
+        .get("ProxyAutoConfigURLString")
+        .cloned()
+        .filter(|_| enabled("ProxyAutoConfigEnable"));
+    let proxy = ["HTTPS", "HTTP"].iter().find_map(|scheme| {
+        if !enabled(&format!("{}Enable", scheme)) {
+            return None;
+        }
+        let host = values.get(&format!("{}Proxy", scheme))?;
+        let port = values.get(&format!("{}Port", scheme)).map(String::as_str).unwrap_or("80");
+        Some(format!("http://{}:{}", host, port))
+    });
+    (proxy.is_some() || pac_url.is_some()).then(|| ProxyConfig { source: "system".to_string(), proxy, pac_url, bypass })
+}
+
+#[cfg(windows)]
+fn from_system() -> Option<ProxyConfig> {
+    let output = command_output(
+        "reg",
+        &["query", r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings"],
+    )?;
+    let value = |name: &str| {
+        output.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next() == Some(name)).then(|| parts.skip(1).collect::<Vec<_>>().join(" "))
+        })
+    };
+    let pac_url = value("AutoConfigURL").filter(|v| !v.is_empty());
+    // `host:port`, or per-scheme `http=host:port;https=host:port`.
+    let proxy = value("ProxyServer")
+        .filter(|_| value("ProxyEnable").as_deref() == Some("0x1"))
+        .and_then(|server| {
+            let entries: Vec<&str> = server.split(';').collect();
+            let pick = |scheme: &str| entries.iter().find_map(|e| e.strip_prefix(&format!("{}=", scheme)));
+            pick("https")
+                .or_else(|| pick("http"))
+                .or_else(|| entries.iter().find(|e| !e.contains('=')).copied())
+                .map(with_scheme)
+        });
+    let bypass = value("ProxyOverride").map(|v| split_list(&v)).unwrap_or_default();
+    (proxy.is_some() || pac_url.is_some()).then(|| ProxyConfig { source: "system".to_string(), proxy, pac_url, bypass })
+}
+
+#[cfg(target_os = "linux")]
+fn from_system() -> Option<ProxyConfig> {
+    let get = |schema: &str, key: &str| {
+        command_output("gsettings", &["get", schema, key]).map(|v| v.trim().trim_matches('\'').to_string())
+    };
+    let bypass = get("org.gnome.system.proxy", "ignore-hosts")
+        .map(|v| split_list(&v.replace(['[', ']', '\''], "")))
+        .unwrap_or_default();
+    match get("org.gnome.system.proxy", "mode")?.as_str() {
+        "auto" => Some(ProxyConfig {
+            source: "system".to_string(),
+            proxy: None,
+            pac_url: get("org.gnome.system.proxy", "autoconfig-url").filter(|v| !v.is_empty()),
+            bypass,
+        }),
+        "manual" => {
+            let host = get("org.gnome.system.proxy.https", "host").filter(|v| !v.is_empty())?;
+            let port = get("org.gnome.system.proxy.https", "port").unwrap_or_else(|| "80".to_string());
+            Some(ProxyConfig {
+                source: "system".to_string(),
+                proxy: Some(format!("http://{}:{}", host, port)),
+                pac_url: None,
+                bypass,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(mobile)]
+fn from_system() -> Option<ProxyConfig> {
+    None
+}
+
+/// There's no JavaScript engine on the Rust side to evaluate PAC scripts, so
+/// take the first `PROXY host:port` the script mentions. The webviews evaluate
+/// PAC natively and aren't affected by this approximation.
+fn proxy_from_pac(pac_url: &str) -> Option<String> {
+    let response = ureq::get(pac_url).timeout(PAC_TIMEOUT).call().ok()?;
+    let mut script = String::new();
+    response.into_reader().take(MAX_PAC_BYTES).read_to_string(&mut script).ok()?;
+    let start = script.find("PROXY ")? + "PROXY ".len();
+    let host_port: String = script[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '[' | ']'))
+        .collect();
+    (!host_port.is_empty()).then(|| with_scheme(&host_port))
+}
+
+fn detect(app: &AppHandle) -> ProxyConfig {
+    let cfg: ProxySettings = settings::section(app, "proxy");
+    let use_system = settings::read_value(app).get("useSystemProxy").and_then(|v| v.as_bool()).unwrap_or(true);
+    match cfg.mode.as_str() {
+        "direct" => ProxyConfig { source: "none".to_string(), ..Default::default() },
+        "manual" => ProxyConfig {
+            source: "settings".to_string(),
+            proxy: cfg.url.as_deref().map(with_scheme),
+            pac_url: None,
+            bypass: cfg.bypass,
+        },
+        _ if !use_system => ProxyConfig { source: "none".to_string(), ..Default::default() },
+        _ => from_environment()
+            .or_else(from_system)
+            .unwrap_or_else(|| ProxyConfig { source: "none".to_string(), ..Default::default() }),
+    }
+}
+
+/// Fill in the Rust-side proxy from the PAC script, which means a download.
+fn resolve_pac(config: &mut ProxyConfig) {
+    if config.proxy.is_none() {
+        if let Some(pac_url) = &config.pac_url {
+            config.proxy = proxy_from_pac(pac_url);
+        }
+    }
+}
+
+fn store(config: ProxyConfig) -> ProxyConfig {
+    tracing::info!(
+        "proxy: source={} proxy={:?} pac={:?} bypass={}",
+        config.source,
+        config.proxy,
+        config.pac_url,
+        config.bypass.len()
+    );
+    *CURRENT.lock().unwrap() = Some(config.clone());
+    config
+}
+
+fn apply(app: &AppHandle) -> ProxyConfig {
+    let mut config = detect(app);
+    resolve_pac(&mut config);
+    store(config)
+}
+
+/// Detect the proxy configuration. Runs during setup, before any child webview
+/// is created; fetching a PAC script happens in the background so it can't
+/// hold up the first window.
+pub fn init(app: &AppHandle) {
+    let config = store(detect(app));
+    if config.proxy.is_none() && config.pac_url.is_some() {
+        std::thread::spawn(move || {
+            let mut config = config;
+            resolve_pac(&mut config);
+            store(config);
+        });
+    }
+}
+
+pub fn current() -> ProxyConfig {
+    CURRENT.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Whether requests to `url` skip the proxy.
+pub fn is_bypassed(config: &ProxyConfig, url: &str) -> bool {
+    let host = Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)).unwrap_or_default();
+    let host = host.as_str();
+    config.bypass.iter().any(|pattern| {
+        let pattern = pattern.trim();
+        if pattern == "<local>" {
+            return !host.contains('.');
+        }
+        let suffix = pattern.trim_start_matches('*');
+        if suffix.starts_with('.') {
+            host.ends_with(suffix) || host == &suffix[1..]
+        } else {
+            host.eq_ignore_ascii_case(pattern)
+        }
+    })
+}
+
+fn builder_for(url: &str) -> ureq::AgentBuilder {
+    let config = current();
+    let builder = ureq::AgentBuilder::new();
+    match config.proxy.as_deref().filter(|_| !is_bypassed(&config, url)) {
+        Some(proxy) => match ureq::Proxy::new(proxy) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(e) => {
+                tracing::warn!("ignoring invalid proxy '{}': {}", proxy, e);
+                builder
+            }
+        },
+        None => builder,
+    }
+}
+
+/// HTTP agent for requests to `url`, routed through the detected proxy
+/// unless the host is bypassed, and resolving through DoH when that's on.
+pub fn agent_for(url: &str) -> ureq::Agent {
+    let builder = builder_for(url);
+    match crate::dns::resolver() {
+        Some(resolver) => builder.resolver(resolver).build(),
+        None => builder.build(),
+    }
+}
+
+/// `agent_for` without DoH, for the DoH queries themselves.
+pub fn agent_without_doh(url: &str) -> ureq::Agent {
+    builder_for(url).build()
+}
+
+/// Proxy for child webviews. Only set for static proxies picked up from
+/// settings or the environment: the webview engines already follow the OS
+/// settings (PAC included) on their own. Not applied on macOS, where
+/// WKWebView proxies need macOS 14 and tauri's `macos-proxy` feature.
+pub fn webview_proxy() -> Option<Url> {
+    let config = current();
+    if config.source != "settings" && config.source != "environment" {
+        return None;
+    }
+    Url::parse(config.proxy.as_deref()?).ok()
+}
+
+/// Re-read proxy settings, e.g. after joining a VPN. Applies to HTTP requests
+/// immediately and to webviews created from now on.
+#[tauri::command]
+pub async fn refresh_proxy_settings(app: AppHandle) -> Result<ProxyConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || apply(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::{adapters, ai_window_manager, bridge};
+
+/// Cleaned-up main content of a page.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadableContent {
+    pub title: String,
+    pub url: String,
+    /// Sanitized HTML: no scripts, styles, controls or attributes beyond links and images.
+    pub html: String,
+    pub markdown: String,
+    /// Plain text, e.g. for text-to-speech.
+    pub text: String,
+}
+
+/// Picks the content root (the latest answer on known AI sites, otherwise the
+/// densest block of text), strips it down and converts it to Markdown.
+const READABLE_JS: &str = r#"
+(function(responseSelector) {
+    var pick = function() {
+        var answers = responseSelector ? document.querySelectorAll(responseSelector) : [];
+        if (answers.length) return answers[answers.length - 1];
+        var best = null, bestScore = 0;
+        document.querySelectorAll('article, main, section, div').forEach(function(el) {
+            var text = el.innerText || '';
+            if (text.length < 200) return;
+            var linkText = 0;
+            el.querySelectorAll('a').forEach(function(a) { linkText += (a.innerText || '').length; });
+            var blocks = el.querySelectorAll('p, pre, li, h1, h2, h3').length;
+            var score = (text.length - linkText * 2) * (1 + blocks / 10) / Math.sqrt(el.querySelectorAll('*').length + 1);
+            if (score > bestScore) { best = el; bestScore = score; }
+        });
+        return best || document.body;
+    };
+    var root = pick().cloneNode(true);
+    root.querySelectorAll('script, style, noscript, svg, button, nav, form, input, textarea, select, iframe, [aria-hidden="true"]')
+        .forEach(function(n) { n.remove(); });
+
+    var children = function(node) {
+        return Array.prototype.map.call(node.childNodes, md).join('');
+    };
+    var block = function(s) { return '\n\n' + s.trim() + '\n\n'; };
+    var md = function(node) {
+        if (node.nodeType === 3) return node.nodeValue.replace(/\s+/g, ' ');
+        if (node.nodeType !== 1) return '';
+        var tag = node.tagName.toLowerCase();
+        switch (tag) {
+            case 'h1': case 'h2': case 'h3': case 'h4': case 'h5': case 'h6':
+                return block('#'.repeat(+tag[1]) + ' ' + children(node).trim());
+            case 'p': case 'div': case 'section': case 'article': case 'header': case 'footer': case 'figure':
+                return block(children(node));
+            case 'br': return '\n';
+            case 'hr': return block('---');
+            case 'strong': case 'b': return '**' + children(node) + '**';
+            case 'em': case 'i': return '*' + children(node) + '*';
+            case 'code': return '`' + node.textContent + '`';
+            case 'pre':
+                var code = node.querySelector('code');
+                var lang = /language-([\w+-]+)/.exec((code && code.className) || '');
+                return block('```' + (lang ? lang[1] : '') + '\n' + node.textContent.replace(/\n$/, '') + '\n```');
+            case 'a': return '[' + children(node).trim() + '](' + node.href + ')';
+            case 'img': return '![' + (node.alt || '') + '](' + node.src + ')';
+            case 'blockquote':
+                return block(children(node).trim().split('\n').map(function(l) { return '> ' + l; }).join('\n'));
+            case 'ul': case 'ol':
+                var items = Array.prototype.filter.call(node.children, function(c) { return c.tagName === 'LI'; });
+                return block(items.map(function(li, i) {
+                    var marker = tag === 'ol' ? (i + 1) + '. ' : '- ';
+                    return marker + children(li).trim().replace(/\n+/g, '\n   ');
+                }).join('\n'));
+            case 'table':
+                var rows = Array.prototype.map.call(node.querySelectorAll('tr'), function(tr) {
+                    return '| ' + Array.prototype.map.call(tr.children, function(c) {
+                        return children(c).trim().replace(/\|/g, '\\|');
+                    }).join(' | ') + ' |';
+                });
+                if (rows.length) {
+                    var cols = node.querySelector('tr').children.length;
+                    rows.splice(1, 0, '|' + ' --- |'.repeat(cols));
+                }
+                return block(rows.join('\n'));
+            default: return children(node);
+        }
+    };
+    var markdown = md(root).replace(/[ \t]+\n/g, '\n').replace(/\n{3,}/g, '\n\n').trim();
+
+    var keep = { href: 1, src: 1, alt: 1, colspan: 1, rowspan: 1 };
+    [root].concat(Array.prototype.slice.call(root.querySelectorAll('*'))).forEach(function(n) {
+        Array.prototype.slice.call(n.attributes).forEach(function(a) {
+            if (!keep[a.name] || /^\s*javascript:/i.test(a.value)) n.removeAttribute(a.name);
+        });
+    });
+    return {
+        title: document.title,
+        url: location.href,
+        html: root.innerHTML,
+        markdown: markdown,
+        text: root.textContent.replace(/[ \t]+/g, ' ').replace(/\s*\n\s*/g, '\n').trim(),
+    };
+})(__SELECTOR__)
+"#;
+
+/// Extract the readable content of a tab for reader mode, export or TTS.
+#[tauri::command]
+pub async fn extract_readable(app: AppHandle, platform_id: String) -> Result<ReadableContent, String> {
+    let webview = ai_window_manager::get_webview(&app, &platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    let current_url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+    let adapter = adapters::for_url(&current_url);
+    // Generic pages are better served by the density heuristic than by `article`.
+    let selector = match adapter.key {
+        "generic" => "null".to_string(),
+        _ if !crate::flags::enabled(&app, crate::flags::ADAPTER_EXTRACTION, Some(&platform_id)) => "null".to_string(),
+        _ => adapters::js_string(adapter.response_selector),
+    };
+    let js = READABLE_JS.replace("__SELECTOR__", &selector);
+    let value = bridge::eval_with_result(&app, &platform_id, &js, Duration::from_secs(10)).await?;
+    let content: ReadableContent = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    if content.text.is_empty() {
+        return Err("No readable content on page".to_string());
+    }
+    Ok(content)
+}
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::{platforms, usage};
+
+const MAX_RECENT: usize = 8;
+
+/// The order last pushed to the OS, so unchanged lists aren't re-sent on every switch.
+static LAST_SYNCED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// A saved platform the user recently switched to, most recent first.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentPlatform {
+    pub id: String,
+    pub name: String,
+    pub last_used: u64,
+}
+
+/// Derived from the usage stats so there's a single record of what was used when.
+/// Platforms that were since deleted or hidden are skipped.
+pub fn load(app: &AppHandle) -> Vec<RecentPlatform> {
+    let stats = usage::stats(app);
+    let mut recent: Vec<RecentPlatform> = platforms::load(app)
+        .into_iter()
+        .filter(|p| !p.hidden)
+        .filter_map(|p| {
+            let last_used = stats.get(&p.id)?.last_used;
+            Some(RecentPlatform { id: p.id, name: p.name, last_used })
+        })
+        .collect();
+    recent.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    recent.truncate(MAX_RECENT);
+    recent
+}
+
+/// Push the OS-level shortcuts (dock menu, jump list) for the current list.
+pub fn sync_os_shortcuts(recent: &[RecentPlatform]) {
+    let items: Vec<(String, String)> = recent.iter().map(|r| (r.id.clone(), r.name.clone())).collect();
+    #[cfg(target_os = "macos")]
+    crate::macos::set_dock_platforms(items);
+    #[cfg(windows)]
+    if let Err(e) = crate::win32::set_jump_list(&items) {
+        tracing::warn!("failed to update jump list: {}", e);
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let _ = items;
+}
+
+/// Re-derive the list after usage changes and notify the OS and frontend if
+/// the order moved.
+pub fn refresh(app: &AppHandle) {
+    let recent = load(app);
+    let order: Vec<String> = recent.iter().map(|r| r.id.clone()).collect();
+    {
+        let mut last = LAST_SYNCED.lock().unwrap();
+        if *last == order {
+            return;
+        }
+        *last = order;
+    }
+    sync_os_shortcuts(&recent);
+    let _ = app.emit("recent_platforms_changed", &recent);
+}
+
+#[tauri::command]
+pub fn get_recent_platforms(app: AppHandle) -> Vec<RecentPlatform> {
+    load(&app)
+}
@@ -0,0 +1,126 @@
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Webview};
+use url::Url;
+
+use crate::{ai_window_manager, settings};
+
+/// `"recyclePool"` section of settings.json. Off by default: pooled webviews
+/// keep their renderer process alive.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct RecycleSettings {
+    enabled: bool,
+    /// Most webviews kept parked at once; the oldest is closed first.
+    size: usize,
+    /// Parked webviews older than this are closed on the next pool operation.
+    keep_seconds: u64,
+}
+
+impl Default for RecycleSettings {
+    fn default() -> Self {
+        Self { enabled: false, size: 2, keep_seconds: 120 }
+    }
+}
+
+struct Parked {
+    host: String,
+    label: String,
+    since: Instant,
+}
+
+/// Hidden webviews left behind by closed temporary tabs, reused by the next
+/// temporary tab on the same host instead of building a new one.
+#[derive(Default)]
+pub struct RecycleState {
+    pool: Mutex<Vec<Parked>>,
+}
+
+fn host_of(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(str::to_string)
+}
+
+/// Close parked webviews past their keep time or beyond the pool size.
+fn evict(app: &AppHandle, cfg: &RecycleSettings) {
+    let Some(state) = app.try_state::<RecycleState>() else { return };
+    let keep = Duration::from_secs(cfg.keep_seconds);
+    let evicted: Vec<String> = {
+        let mut pool = state.pool.lock().unwrap();
+        let mut evicted = Vec::new();
+        pool.retain(|p| {
+            let expired = p.since.elapsed() >= keep;
+            if expired {
+                evicted.push(p.label.clone());
+            }
+            !expired
+        });
+        let excess = pool.len().saturating_sub(cfg.size);
+        evicted.extend(pool.drain(..excess).map(|p| p.label));
+        evicted
+    };
+    for label in evicted {
+        tracing::debug!("closing parked webview '{}'", label);
+        let _ = ai_window_manager::close_label(app, &label);
+    }
+}
+
+pub fn is_pooled(app: &AppHandle, label: &str) -> bool {
+    app.try_state::<RecycleState>()
+        .is_some_and(|s| s.pool.lock().unwrap().iter().any(|p| p.label == label))
+}
+
+/// Park the webview of a closing temporary tab instead of destroying it.
+/// Returns false when the caller should close it as usual.
+pub fn keep(app: &AppHandle, platform_id: &str) -> bool {
+    let cfg: RecycleSettings = settings::section(app, "recyclePool");
+    if !cfg.enabled || cfg.size == 0 || !platform_id.starts_with("tmp-") {
+        return false;
+    }
+    let Some(webview) = ai_window_manager::get_webview(app, platform_id) else { return false };
+    let Some(host) = webview.url().ok().and_then(|u| u.host_str().map(str::to_string)) else {
+        return false;
+    };
+    let _ = webview.hide();
+    // Blank the page so the parked webview stops running the old site's scripts.
+    if let Ok(blank) = "about:blank".parse() {
+        let _ = webview.navigate(blank);
+    }
+    let label = webview.label().to_string();
+    tracing::debug!("parking webview '{}' for {}", label, host);
+    app.state::<RecycleState>().pool.lock().unwrap().push(Parked { host, label, since: Instant::now() });
+    evict(app, &cfg);
+    true
+}
+
+/// Claim a parked webview on `url`'s host for the temporary tab `platform_id`
+/// and navigate it there.
+pub fn take(app: &AppHandle, platform_id: &str, url: &str) -> Option<Webview> {
+    let cfg: RecycleSettings = settings::section(app, "recyclePool");
+    if !cfg.enabled || !platform_id.starts_with("tmp-") {
+        return None;
+    }
+    evict(app, &cfg);
+    let host = host_of(url)?;
+    let label = {
+        let state = app.try_state::<RecycleState>()?;
+        let mut pool = state.pool.lock().unwrap();
+        let index = pool.iter().rposition(|p| p.host == host)?;
+        pool.remove(index).label
+    };
+    let webview = app.get_webview(&label)?;
+    ai_window_manager::set_alias(app, platform_id, Some(&label));
+    if let Ok(target) = url.parse() {
+        let _ = webview.navigate(target);
+    }
+    Some(webview)
+}
+
+/// Close every parked webview, e.g. when memory is needed.
+pub fn clear(app: &AppHandle) {
+    let Some(state) = app.try_state::<RecycleState>() else { return };
+    let labels: Vec<String> = state.pool.lock().unwrap().drain(..).map(|p| p.label).collect();
+    for label in labels {
+        let _ = ai_window_manager::close_label(app, &label);
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Deserialize;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use url::Url;
+
+use crate::settings;
+
+/// What to do with a URL a page tries to open in a new window.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Route {
+    /// Open a temporary tab.
+    Tab,
+    /// Load it in the webview that asked, replacing the page.
+    Navigate,
+    /// Hand it to the system browser.
+    Browser,
+    /// Drop it.
+    Deny,
+}
+
+#[derive(Deserialize, Debug)]
+struct Rule {
+    /// Host pattern: `example.com` matches that host only, `*.example.com`
+    /// also matches its subdomains, and `*` matches everything.
+    pattern: String,
+    route: Route,
+}
+
+/// `"newTabRules"` section of settings.json. Rules are tried in order; the
+/// first whose pattern matches the URL's host wins.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct RoutingSettings {
+    rules: Vec<Rule>,
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{}", domain)),
+        None => host == pattern,
+    }
+}
+
+/// The route configured for `url`, or `None` to fall back to the built-in
+/// behavior.
+pub fn route(app: &AppHandle, url: &Url) -> Option<Route> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let cfg: RoutingSettings = settings::section(app, "newTabRules");
+    cfg.rules.iter().find(|r| host_matches(&r.pattern, &host)).map(|r| r.route)
+}
+
+pub fn open_in_browser(app: &AppHandle, url: &Url) {
+    if !matches!(url.scheme(), "http" | "https" | "mailto") {
+        tracing::warn!("not opening '{}' in the system browser", url);
+        return;
+    }
+    if let Err(e) = app.opener().open_url(url.as_str(), None::<&str>) {
+        tracing::warn!("failed to open '{}' in the system browser: {}", url, e);
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+/// Where users get the Evergreen WebView2 Runtime.
+const WEBVIEW2_DOWNLOAD_URL: &str = "https://developer.microsoft.com/microsoft-edge/webview2/";
+
+/// Why the engine refused to create a child webview.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Cause {
+    /// Windows without the WebView2 Runtime (or with a broken install).
+    Webview2Missing,
+    /// WebKitGTK failing to set up rendering, usually a GPU driver issue.
+    WebkitGtk,
+    Unknown,
+}
+
+/// What the frontend can offer to fix the cause.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Remedy {
+    InstallWebview2,
+    /// Turn off hardware acceleration and restart; see `gpu::apply_process_flags`.
+    DisableHardwareAcceleration,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CreateFailed {
+    platform_id: String,
+    cause: Cause,
+    message: String,
+    remedy: Option<Remedy>,
+}
+
+fn diagnose(error: &str) -> Cause {
+    let lower = error.to_lowercase();
+    #[cfg(windows)]
+    if tauri::webview_version().is_err() || lower.contains("webview2") {
+        return Cause::Webview2Missing;
+    }
+    #[cfg(target_os = "linux")]
+    if ["webkit", "gtk", "egl", "dmabuf", "gbm"].iter().any(|k| lower.contains(k)) {
+        return Cause::WebkitGtk;
+    }
+    let _ = lower;
+    Cause::Unknown
+}
+
+/// Turn a failed `add_child` into an `AppError` that says what went wrong,
+/// and emit `webview_create_failed` so the frontend can offer the fix.
+pub fn creation_failed(app: &AppHandle, platform_id: &str, error: tauri::Error) -> AppError {
+    let raw = error.to_string();
+    let cause = diagnose(&raw);
+    let (message, remedy) = match cause {
+        Cause::Webview2Missing => (
+            format!("Microsoft Edge WebView2 Runtime is missing or damaged ({})", raw),
+            Some(Remedy::InstallWebview2),
+        ),
+        Cause::WebkitGtk => (
+            format!("WebKitGTK could not start rendering; disabling hardware acceleration usually helps ({})", raw),
+            Some(Remedy::DisableHardwareAcceleration),
+        ),
+        Cause::Unknown => (format!("Could not create the webview: {}", raw), None),
+    };
+    tracing::error!("creating webview for {} failed ({:?}): {}", platform_id, cause, raw);
+    let _ = app.emit(
+        "webview_create_failed",
+        CreateFailed { platform_id: platform_id.to_string(), cause, message: message.clone(), remedy },
+    );
+    AppError::WebviewCreation(message)
+}
+
+/// Carry out a remedy offered with `webview_create_failed`.
+#[tauri::command]
+pub fn apply_runtime_remedy(app: AppHandle, remedy: Remedy) -> Result<(), AppError> {
+    match remedy {
+        Remedy::InstallWebview2 => {
+            use tauri_plugin_opener::OpenerExt;
+            app.opener()
+                .open_url(WEBVIEW2_DOWNLOAD_URL, None::<&str>)
+                .map_err(|e| AppError::Other(e.to_string()))
+        }
+        Remedy::DisableHardwareAcceleration => {
+            // Keep the per-platform list; only flip the global switch.
+            let mut section = crate::settings::read_value(&app)["hardwareAcceleration"].clone();
+            if !section.is_object() {
+                section = serde_json::json!({});
+            }
+            section["enabled"] = serde_json::Value::Bool(false);
+            crate::settings::write_section(&app, "hardwareAcceleration", &section)?;
+            crate::persist::flush();
+            // WebKitGTK only reads the switch from the environment at startup.
+            app.restart()
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Webview, WebviewBuilder, Wry};
+
+use crate::ai_window_manager;
+use crate::error::AppError;
+
+/// Reports how far the page's conversation is scrolled, as a 0..1 ratio, and
+/// scrolls to a ratio on request. Chat sites scroll an inner container rather
+/// than the document, so the element is taken from the scroll events
+/// themselves (they don't bubble, hence the capture phase). Scrolls caused by
+/// `apply` aren't reported back, which would ping-pong between the pair.
+const SCROLL_JS: &str = r#"
+(function() {
+    if (window.__anybrainScrollSync) return;
+    var s = window.__anybrainScrollSync = { on: false, el: null, quietUntil: 0, pending: false };
+    var range = function(el) { return el.scrollHeight - el.clientHeight; };
+    var target = function() {
+        if (s.el && s.el.isConnected) return s.el;
+        var best = document.scrollingElement || document.documentElement;
+        Array.prototype.forEach.call(document.querySelectorAll('main, main *, [class*="scroll"]'), function(el) {
+            var style = getComputedStyle(el);
+            if (/(auto|scroll)/.test(style.overflowY) && range(el) > range(best)) best = el;
+        });
+        return best;
+    };
+    document.addEventListener('scroll', function(e) {
+        if (!s.on || Date.now() < s.quietUntil) return;
+        var el = e.target === document ? (document.scrollingElement || document.documentElement) : e.target;
+        if (range(el) <= 0) return;
+        s.el = el;
+        if (s.pending) return;
+        s.pending = true;
+        // Well under the per-webview IPC rate limit.
+        setTimeout(function() {
+            s.pending = false;
+            window.__TAURI_INTERNALS__.invoke('report_scroll', { ratio: s.el.scrollTop / range(s.el) }).catch(function() {});
+        }, 50);
+    }, true);
+    s.apply = function(ratio) {
+        var el = target();
+        s.quietUntil = Date.now() + 200;
+        el.scrollTop = ratio * range(el);
+    };
+})();
+"#;
+
+/// The two platforms scrolling together, if any.
+static PAIR: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+fn set_enabled(app: &AppHandle, platform_id: &str, on: bool) {
+    if let Some(webview) = ai_window_manager::get_webview(app, platform_id) {
+        let _ = webview.eval(&format!("window.__anybrainScrollSync && (window.__anybrainScrollSync.on = {});", on));
+    }
+}
+
+pub fn configure_builder(builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(SCROLL_JS)
+}
+
+/// A navigation resets the page's flag; turn it back on for a synced tab.
+pub fn on_page_load(platform_id: &str, webview: &Webview) {
+    let synced = PAIR.lock().unwrap().as_ref().is_some_and(|(a, b)| a == platform_id || b == platform_id);
+    if synced {
+        let _ = webview.eval("window.__anybrainScrollSync && (window.__anybrainScrollSync.on = true);");
+    }
+}
+
+/// Scroll two tabs together, e.g. the answers to one broadcast prompt side by
+/// side (the active tab next to a detached one). `None` turns syncing off.
+#[tauri::command]
+pub fn set_scroll_sync(app: AppHandle, platform_ids: Option<(String, String)>) -> Result<(), AppError> {
+    if let Some((a, b)) = &platform_ids {
+        if a == b {
+            return Err(AppError::InvalidInput("Scroll sync needs two different tabs".to_string()));
+        }
+        for id in [a, b] {
+            if ai_window_manager::get_webview(&app, id).is_none() {
+                return Err(AppError::not_found("Webview", id));
+            }
+        }
+    }
+    let previous = std::mem::replace(&mut *PAIR.lock().unwrap(), platform_ids.clone());
+    if let Some((a, b)) = previous {
+        set_enabled(&app, &a, false);
+        set_enabled(&app, &b, false);
+    }
+    if let Some((a, b)) = platform_ids {
+        tracing::info!("scroll sync between '{}' and '{}'", a, b);
+        set_enabled(&app, &a, true);
+        set_enabled(&app, &b, true);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_scroll_sync() -> Option<(String, String)> {
+    PAIR.lock().unwrap().clone()
+}
+
+/// Called by the injected listener; scrolls the other tab of the pair to the
+/// same proportion, since the two answers differ in length.
+#[tauri::command]
+pub fn report_scroll(app: AppHandle, webview: Webview, ratio: f64) {
+    if !ratio.is_finite() {
+        return;
+    }
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    let sibling = match PAIR.lock().unwrap().as_ref() {
+        Some((a, b)) if *a == platform_id => b.clone(),
+        Some((a, b)) if *b == platform_id => a.clone(),
+        _ => return,
+    };
+    if let Some(target) = ai_window_manager::get_webview(&app, &sibling) {
+        let _ = target.eval(&format!(
+            "window.__anybrainScrollSync && window.__anybrainScrollSync.apply({});",
+            ratio.clamp(0.0, 1.0)
+        ));
+    }
+}
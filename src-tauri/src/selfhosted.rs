@@ -0,0 +1,133 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Read;
+use std::time::Duration;
+#[cfg(desktop)]
+use std::{net::IpAddr, time::Instant};
+
+/// How long to listen for mDNS announcements.
+#[cfg(desktop)]
+const BROWSE_WINDOW: Duration = Duration::from_secs(3);
+const PROBE_TIMEOUT: Duration = Duration::from_millis(1500);
+/// Enough of the page to find its `<title>`.
+const MAX_PROBE_BYTES: u64 = 64 * 1024;
+
+/// A self-hosted chat UI we can recognise on the network.
+struct Kind {
+    key: &'static str,
+    name: &'static str,
+    /// Ports the project listens on out of the box.
+    default_ports: &'static [u16],
+    /// Lowercase strings found in the start page or the mDNS instance name.
+    markers: &'static [&'static str],
+}
+
+const KINDS: &[Kind] = &[
+    Kind { key: "open-webui", name: "Open WebUI", default_ports: &[3000, 8080], markers: &["open webui", "open-webui"] },
+    Kind {
+        key: "text-generation-webui",
+        name: "text-generation-webui",
+        default_ports: &[7860],
+        markers: &["text generation web ui", "text-generation-webui"],
+    },
+];
+
+/// A server found by `discover_local_uis`, ready to be added as a platform.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalUi {
+    pub kind: &'static str,
+    pub name: String,
+    pub url: String,
+    /// `"mdns"` or `"localhost"`.
+    pub found_by: &'static str,
+}
+
+fn match_kind(text: &str) -> Option<&'static Kind> {
+    let text = text.to_lowercase();
+    KINDS.iter().find(|k| k.markers.iter().any(|m| text.contains(m)))
+}
+
+/// Fetch the start page and tell which UI serves it. Goes direct: these are
+/// local addresses the user's proxy can't reach.
+fn identify(url: &str) -> Option<&'static Kind> {
+    let agent = ureq::AgentBuilder::new().timeout(PROBE_TIMEOUT).redirects(3).build();
+    let response = agent.get(url).call().ok()?;
+    let mut body = String::new();
+    response.into_reader().take(MAX_PROBE_BYTES).read_to_string(&mut body).ok()?;
+    match_kind(&body)
+}
+
+#[cfg(desktop)]
+fn url_for(ip: IpAddr, port: u16) -> String {
+    match ip {
+        IpAddr::V4(v4) => format!("http://{}:{}/", v4, port),
+        IpAddr::V6(v6) => format!("http://[{}]:{}/", v6, port),
+    }
+}
+
+/// HTTP services announced on the LAN, as `(instance name, url)`.
+#[cfg(desktop)]
+fn browse_mdns() -> Vec<(String, String)> {
+    let daemon = match mdns_sd::ServiceDaemon::new() {
+        Ok(daemon) => daemon,
+        Err(e) => {
+            tracing::warn!("mDNS unavailable: {}", e);
+            return Vec::new();
+        }
+    };
+    let Ok(receiver) = daemon.browse("_http._tcp.local.") else {
+        let _ = daemon.shutdown();
+        return Vec::new();
+    };
+    let deadline = Instant::now() + BROWSE_WINDOW;
+    let mut found = Vec::new();
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        let Ok(event) = receiver.recv_timeout(left) else { break };
+        if let mdns_sd::ServiceEvent::ServiceResolved(info) = event {
+            // Prefer IPv4: link-local IPv6 needs a scope id the URL can't carry.
+            let mut addresses: Vec<IpAddr> = info.get_addresses().iter().copied().collect();
+            addresses.sort_by_key(|ip| ip.is_ipv6());
+            if let Some(ip) = addresses.first() {
+                found.push((info.get_fullname().to_string(), url_for(*ip, info.get_port())));
+            }
+        }
+    }
+    let _ = daemon.shutdown();
+    found
+}
+
+/// mdns-sd is a desktop-only dependency; mobile checks localhost alone.
+#[cfg(mobile)]
+fn browse_mdns() -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// Look for self-hosted chat UIs: services announced over mDNS, then the
+/// default ports on this machine, which rarely announce themselves.
+#[tauri::command]
+pub async fn discover_local_uis() -> Result<Vec<LocalUi>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        for (instance, url) in browse_mdns() {
+            let Some(kind) = match_kind(&instance).or_else(|| identify(&url)) else { continue };
+            if seen.insert(url.clone()) {
+                let name = instance.split("._http").next().unwrap_or(kind.name).to_string();
+                found.push(LocalUi { kind: kind.key, name, url, found_by: "mdns" });
+            }
+        }
+        for kind in KINDS {
+            for port in kind.default_ports {
+                let url = format!("http://localhost:{}/", port);
+                if identify(&url).is_some_and(|k| k.key == kind.key) && seen.insert(url.clone()) {
+                    found.push(LocalUi { kind: kind.key, name: kind.name.to_string(), url, found_by: "localhost" });
+                }
+            }
+        }
+        tracing::info!("found {} local chat UIs", found.len());
+        found
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
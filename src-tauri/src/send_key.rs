@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Webview, WebviewBuilder, Wry};
+
+use crate::error::AppError;
+use crate::{adapters, ai_window_manager, settings};
+
+/// A key combination that sends the prompt.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SendKey {
+    /// Enter sends, Shift+Enter starts a new line.
+    Enter,
+    /// Cmd+Enter (Ctrl+Enter off macOS) sends, Enter starts a new line.
+    ModEnter,
+}
+
+/// `"sendKey"` section of settings.json. Unset or `null` keeps each site's own key.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct SendKeySettings {
+    default: Option<SendKey>,
+    /// Platform id -> key, `null` for the site's own.
+    platforms: HashMap<String, Option<SendKey>>,
+}
+
+/// Remaps the prompt box's keys so the chosen combination sends and the
+/// site's own one doesn't. Runs in the capture phase, ahead of the site's
+/// handlers. A newline is produced by re-dispatching Shift+Enter, which every
+/// editor treats as one; sending clicks the send button, or re-dispatches
+/// the site's combination when there isn't one. Only the user's own key
+/// presses are remapped, so re-dispatched and scripted ones pass through.
+/// Evaluated again to change the keys.
+const SEND_KEY_JS: &str = r#"
+(function(prompt, send, native, wanted) {
+    var s = window.__anybrainSendKey;
+    if (!s) {
+        s = window.__anybrainSendKey = {};
+        var isMac = /Mac|iP(hone|ad|od)/.test(navigator.platform);
+        var redispatch = function(target, mod, shift) {
+            var e = new KeyboardEvent('keydown', {
+                key: 'Enter', code: 'Enter', keyCode: 13, which: 13, bubbles: true, cancelable: true,
+                shiftKey: shift, metaKey: mod && isMac, ctrlKey: mod && !isMac,
+            });
+            target.dispatchEvent(e);
+        };
+        document.addEventListener('keydown', function(e) {
+            if (!e.isTrusted || e.key !== 'Enter' || e.isComposing || e.keyCode === 229) return;
+            if (!s.wanted || s.wanted === s.native) return;
+            var box = e.target.closest && e.target.closest(s.prompt);
+            if (!box) return;
+            var mod = isMac ? e.metaKey : e.ctrlKey;
+            if (e.shiftKey || e.altKey) return;
+            var sends = s.wanted === 'enter' ? !mod : mod;
+            var sitesSend = s.native === 'enter' ? !mod : mod;
+            if (sends === sitesSend) return;
+            e.preventDefault();
+            e.stopImmediatePropagation();
+            if (!sends) {
+                redispatch(e.target, false, true);
+                return;
+            }
+            var btn = document.querySelector(s.send);
+            if (btn && !btn.disabled) btn.click();
+            else redispatch(e.target, s.native === 'modEnter', false);
+        }, true);
+    }
+    s.prompt = prompt;
+    s.send = send;
+    s.native = native;
+    s.wanted = wanted;
+})(__PROMPT__, __SEND__, __NATIVE__, __WANTED__);
+"#;
+
+fn wanted(app: &AppHandle, platform_id: &str) -> Option<SendKey> {
+    let cfg: SendKeySettings = settings::section(app, "sendKey");
+    cfg.platforms.get(platform_id).copied().unwrap_or(cfg.default)
+}
+
+fn key_js(key: Option<SendKey>) -> String {
+    serde_json::to_string(&key).unwrap_or_else(|_| "null".to_string())
+}
+
+fn script(app: &AppHandle, platform_id: &str, url: &str) -> String {
+    let adapter = adapters::for_url(url);
+    SEND_KEY_JS
+        .replace("__PROMPT__", &adapters::js_string(adapter.prompt_selector))
+        .replace("__SEND__", &adapters::js_string(adapter.send_selector))
+        .replace("__NATIVE__", &key_js(Some(adapter.send_key)))
+        .replace("__WANTED__", &key_js(wanted(app, platform_id)))
+}
+
+pub fn configure_builder(app: &AppHandle, platform_id: &str, url: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(&script(app, platform_id, url))
+}
+
+fn apply(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    let url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+    let _ = webview.eval(&script(app, platform_id, &url));
+}
+
+/// Choose the key that sends prompts, for one platform or (`platform_id`
+/// unset) as the default. `None` keeps the site's own key. Saved in settings
+/// and applied to open tabs straight away.
+#[tauri::command]
+pub fn set_send_key(app: AppHandle, platform_id: Option<String>, key: Option<SendKey>) -> Result<(), AppError> {
+    let mut value = settings::read_value(&app).get("sendKey").cloned().unwrap_or_else(|| serde_json::json!({}));
+    if !value.is_object() {
+        value = serde_json::json!({});
+    }
+    match &platform_id {
+        Some(id) => {
+            if !value["platforms"].is_object() {
+                value["platforms"] = serde_json::json!({});
+            }
+            value["platforms"][id] = serde_json::to_value(key)?;
+        }
+        None => value["default"] = serde_json::to_value(key)?,
+    }
+    settings::write_section(&app, "sendKey", &value)?;
+    for (label, webview) in app.webviews() {
+        if label == "main" {
+            continue;
+        }
+        let id = ai_window_manager::platform_for_label(&app, &label);
+        if platform_id.is_some() && platform_id.as_deref() != Some(id.as_str()) {
+            continue;
+        }
+        apply(&app, &id, &webview);
+    }
+    Ok(())
+}
+
+/// The key that sends prompts on the platform, and the site's own key.
+#[tauri::command]
+pub fn get_send_key(app: AppHandle, platform_id: String) -> serde_json::Value {
+    let url = ai_window_manager::get_webview(&app, &platform_id)
+        .and_then(|w| w.url().ok())
+        .map(|u| u.to_string())
+        .or_else(|| crate::platforms::load(&app).into_iter().find(|p| p.id == platform_id).map(|p| p.url));
+    let native = url.map(|u| adapters::for_url(&u).send_key);
+    serde_json::json!({ "key": wanted(&app, &platform_id), "native": native })
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Webview, WebviewBuilder, Wry};
+
+use crate::{adapters, ai_window_manager, platforms, settings};
+
+/// `"sessionWatch"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct SessionWatchSettings {
+    enabled: bool,
+    /// Show a system notification when a tab out of sight is signed out.
+    notify: bool,
+}
+
+impl Default for SessionWatchSettings {
+    fn default() -> Self {
+        Self { enabled: true, notify: true }
+    }
+}
+
+/// Reports whether the page looks signed out: a login/sign-in URL (sites
+/// that redirect, or hand off to an identity provider) or an element the
+/// adapter only shows to signed-out visitors (sites that keep the chat page
+/// and swap the account menu for a "Log in" button). Checked on a timer
+/// because SPAs change both without a navigation; only changes are reported.
+const SESSION_JS: &str = r#"
+(function(selector) {
+    if (window.top !== window) return;
+    var last = null;
+    var loginUrl = function() {
+        if (/^(login|auth|accounts|signin)\./i.test(location.hostname)) return true;
+        return /(^|\/)(log-?in|sign-?in|sign_in|auth)(\/|$)/i.test(location.pathname);
+    };
+    var check = function() {
+        if (!document.body) return;
+        var signedOut = loginUrl() || (!!selector && !!document.querySelector(selector));
+        if (signedOut === last) return;
+        last = signedOut;
+        window.__TAURI_INTERNALS__.invoke('report_session', { signedOut: signedOut }).catch(function() {});
+    };
+    document.addEventListener('DOMContentLoaded', check);
+    setInterval(check, 3000);
+})(__SELECTOR__);
+"#;
+
+#[derive(Default)]
+struct Sessions {
+    /// Platforms whose page last looked signed out.
+    signed_out: HashSet<String>,
+    /// Platforms seen signed in since the app started; only these can expire.
+    signed_in: HashSet<String>,
+}
+
+static SESSIONS: Mutex<Option<Sessions>> = Mutex::new(None);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SessionChanged<'a> {
+    platform_id: &'a str,
+    url: Option<String>,
+}
+
+fn script(url: &str) -> String {
+    let selector = match adapters::for_url(url).signed_out_selector {
+        Some(selector) => adapters::js_string(selector),
+        None => "null".to_string(),
+    };
+    SESSION_JS.replace("__SELECTOR__", &selector)
+}
+
+pub fn configure_builder(app: &AppHandle, url: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let cfg: SessionWatchSettings = settings::section(app, "sessionWatch");
+    if !cfg.enabled {
+        return builder;
+    }
+    builder.initialization_script(&script(url))
+}
+
+/// Whether the platform's page was last seen signed out. Prompts aren't sent there.
+pub fn is_signed_out(platform_id: &str) -> bool {
+    SESSIONS.lock().unwrap().as_ref().is_some_and(|s| s.signed_out.contains(platform_id))
+}
+
+/// A closed tab can't report that it was signed back in.
+pub fn forget(platform_id: &str) {
+    if let Some(sessions) = SESSIONS.lock().unwrap().as_mut() {
+        sessions.signed_out.remove(platform_id);
+    }
+}
+
+/// Notify when the user can't see the tab: it's in the background or the
+/// window is.
+fn notify(app: &AppHandle, platform_id: &str) {
+    let cfg: SessionWatchSettings = settings::section(app, "sessionWatch");
+    if !cfg.notify {
+        return;
+    }
+    let focused = app.get_window("main").and_then(|w| w.is_focused().ok()).unwrap_or(false);
+    if focused && ai_window_manager::active_platform(app).as_deref() == Some(platform_id) {
+        return;
+    }
+    let name = platforms::load(app).into_iter().find(|p| p.id == platform_id).map(|p| p.name).unwrap_or_else(|| platform_id.to_string());
+    use tauri_plugin_notification::NotificationExt;
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(format!("Signed out of {}", name))
+        .body("Sign in again before sending prompts to it.")
+        .show()
+    {
+        tracing::warn!("failed to show session notification: {}", e);
+    }
+}
+
+/// Called by the injected check when the page starts or stops looking signed
+/// out. Emits `session_expired` when a tab that was signed in drops to its
+/// login page, and `session_restored` once it's back.
+#[tauri::command]
+pub fn report_session(app: AppHandle, webview: Webview, signed_out: bool) {
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    let (expired, restored) = {
+        let mut guard = SESSIONS.lock().unwrap();
+        let sessions = guard.get_or_insert_with(Sessions::default);
+        if signed_out {
+            let newly = sessions.signed_out.insert(platform_id.clone());
+            (newly && sessions.signed_in.contains(&platform_id), false)
+        } else {
+            sessions.signed_in.insert(platform_id.clone());
+            (false, sessions.signed_out.remove(&platform_id))
+        }
+    };
+    let url = webview.url().ok().map(|u| u.to_string());
+    if expired {
+        tracing::info!("'{}' was signed out ({:?})", platform_id, url);
+        let _ = app.emit("session_expired", SessionChanged { platform_id: &platform_id, url });
+        notify(&app, &platform_id);
+    } else if restored {
+        tracing::info!("'{}' is signed in again", platform_id);
+        let _ = app.emit("session_restored", SessionChanged { platform_id: &platform_id, url });
+    }
+}
+
+/// Platforms currently showing their login page, sorted by id.
+#[tauri::command]
+pub fn list_signed_out() -> Vec<String> {
+    let mut ids: Vec<String> = SESSIONS.lock().unwrap().as_ref().map(|s| s.signed_out.iter().cloned().collect()).unwrap_or_default();
+    ids.sort();
+    ids
+}
@@ -0,0 +1,57 @@
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+
+/// settings.json of the active workspace.
+pub fn settings_file_path(app: &tauri::AppHandle) -> PathBuf {
+    crate::workspaces::dir(app).join("settings.json")
+}
+
+/// Read settings.json as a raw JSON object. Missing or malformed files yield `{}`.
+pub fn read_value(app: &tauri::AppHandle) -> serde_json::Value {
+    crate::persist::read_to_string(&settings_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// Deserialize one top-level section of settings.json (e.g. `"dictation"`),
+/// falling back to the section's defaults when it is absent or invalid.
+pub fn section<T: DeserializeOwned + Default>(app: &tauri::AppHandle, key: &str) -> T {
+    read_value(app)
+        .get(key)
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Replace one top-level section of settings.json, leaving the rest as the
+/// frontend wrote it.
+pub fn write_section<T: serde::Serialize>(app: &tauri::AppHandle, key: &str, value: &T) -> Result<(), String> {
+    let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    let mut patch = serde_json::Map::new();
+    patch.insert(key.to_string(), value);
+    merge(app, serde_json::Value::Object(patch))
+}
+
+/// Replace the top-level sections present in `patch`, keeping the others.
+/// Runs on the persistence writer, so concurrent section writes don't drop
+/// each other's changes.
+pub fn merge(app: &tauri::AppHandle, patch: serde_json::Value) -> Result<(), String> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err("settings must be a JSON object".to_string());
+    };
+    crate::persist::update(settings_file_path(app), move |_, current| {
+        let mut settings = current
+            .as_deref()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+            .filter(|v| v.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        for (key, value) in patch {
+            settings[key] = value;
+        }
+        let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+        Ok((Some(json), ()))
+    })?;
+    Ok(())
+}
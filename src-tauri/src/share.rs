@@ -0,0 +1,43 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::deeplink::{self, DeepLink};
+
+/// Text shared from another app that is waiting for the user to pick a platform.
+/// Kept here as well as emitted so a cold start doesn't lose it before the
+/// frontend has subscribed.
+#[derive(Default)]
+pub struct ShareState {
+    pending: Mutex<Option<String>>,
+}
+
+/// Entry point for every share source (macOS Services, Send To, `anybrain://share`).
+/// With a platform the text goes straight into its prompt box; otherwise the
+/// frontend shows a picker and calls `share_to_platform`.
+pub fn receive(app: &AppHandle, text: String, platform: Option<String>) -> Result<(), String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Nothing to share".to_string());
+    }
+    tracing::info!("received {} chars", text.len());
+    if platform.is_some() {
+        return deeplink::dispatch(app, DeepLink::Open { platform, prompt: Some(text), send: false });
+    }
+    crate::show_main_window(app);
+    *app.state::<ShareState>().pending.lock().unwrap() = Some(text.clone());
+    app.emit("share_received", text).map_err(|e| e.to_string())
+}
+
+/// Fetch (and clear) text that arrived before the frontend was listening.
+#[tauri::command]
+pub fn take_shared_text(app: AppHandle) -> Option<String> {
+    app.state::<ShareState>().pending.lock().unwrap().take()
+}
+
+/// Finish a share once the user has picked a platform. The text is typed but
+/// not submitted so it can be edited first.
+#[tauri::command]
+pub fn share_to_platform(app: AppHandle, platform_id: String, text: String) -> Result<(), String> {
+    app.state::<ShareState>().pending.lock().unwrap().take();
+    deeplink::dispatch(&app, DeepLink::Open { platform: Some(platform_id), prompt: Some(text), send: false })
+}
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::{app_menu, hotkey, settings};
+
+/// Where a binding is registered: menu accelerators only fire while the app
+/// is focused, global shortcuts fire from anywhere.
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Scope {
+    Menu,
+    Global,
+}
+
+struct Definition {
+    action: &'static str,
+    label: &'static str,
+    default: &'static str,
+    scope: Scope,
+}
+
+/// Every remappable binding. The numbered tab shortcuts are fixed.
+const DEFINITIONS: &[Definition] = &[
+    Definition { action: "toggle_window", label: "Show/Hide Window", default: "CommandOrControl+Shift+Space", scope: Scope::Global },
+    Definition { action: "quick_capture", label: "Quick Capture", default: "CmdOrCtrl+Shift+K", scope: Scope::Menu },
+    Definition { action: "broadcast", label: "Broadcast Prompt", default: "CmdOrCtrl+Shift+B", scope: Scope::Menu },
+    Definition { action: "lock", label: "Lock", default: "CmdOrCtrl+Shift+L", scope: Scope::Menu },
+    Definition { action: "find", label: "Find", default: "CmdOrCtrl+F", scope: Scope::Menu },
+    Definition { action: "reload", label: "Reload", default: "CmdOrCtrl+R", scope: Scope::Menu },
+    Definition { action: "zoom_in", label: "Zoom In", default: "CmdOrCtrl+Plus", scope: Scope::Menu },
+    Definition { action: "zoom_out", label: "Zoom Out", default: "CmdOrCtrl+Minus", scope: Scope::Menu },
+    Definition { action: "zoom_reset", label: "Actual Size", default: "CmdOrCtrl+0", scope: Scope::Menu },
+    Definition { action: "toggle_zen", label: "Zen Mode", default: "CmdOrCtrl+Shift+F", scope: Scope::Menu },
+    Definition { action: "next_tab", label: "Next Tab", default: "Ctrl+Tab", scope: Scope::Menu },
+    Definition { action: "prev_tab", label: "Previous Tab", default: "Ctrl+Shift+Tab", scope: Scope::Menu },
+    Definition { action: "reopen_closed_tab", label: "Reopen Closed Tab", default: "CmdOrCtrl+Shift+T", scope: Scope::Menu },
+];
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutInfo {
+    action: String,
+    label: String,
+    scope: Scope,
+    /// `None` when the user unbound it.
+    accelerator: Option<String>,
+    default: String,
+}
+
+/// `"shortcuts"` section of settings.json: action -> accelerator, `null` to
+/// unbind. Actions not listed use their default.
+fn overrides(app: &AppHandle) -> HashMap<String, Option<String>> {
+    settings::section(app, "shortcuts")
+}
+
+fn definition(action: &str) -> Option<&'static Definition> {
+    DEFINITIONS.iter().find(|d| d.action == action)
+}
+
+/// Before the shortcuts section existed the toggle shortcut lived in `"globalShortcut"`.
+fn legacy_toggle_window(app: &AppHandle) -> Option<Option<String>> {
+    let value = settings::read_value(app);
+    let toggle = value.get("globalShortcut")?.get("toggleWindow")?;
+    Some(toggle.as_str().map(str::to_string))
+}
+
+fn effective(app: &AppHandle, overrides: &HashMap<String, Option<String>>, def: &Definition) -> Option<String> {
+    match overrides.get(def.action) {
+        Some(accelerator) => accelerator.clone(),
+        None if def.action == "toggle_window" => legacy_toggle_window(app).unwrap_or_else(|| Some(def.default.to_string())),
+        None => Some(def.default.to_string()),
+    }
+}
+
+/// The accelerator currently bound to `action`, if any.
+pub fn accelerator(app: &AppHandle, action: &str) -> Option<String> {
+    effective(app, &overrides(app), definition(action)?)
+}
+
+/// Actions bound in `scope`, with their accelerators.
+pub fn bound(app: &AppHandle, scope: Scope) -> Vec<(&'static str, String)> {
+    let overrides = overrides(app);
+    DEFINITIONS
+        .iter()
+        .filter(|d| d.scope == scope)
+        .filter_map(|d| effective(app, &overrides, d).map(|a| (d.action, a)))
+        .collect()
+}
+
+/// Accelerators are compared after parsing so `CmdOrCtrl+K` and
+/// `CommandOrControl+k` count as the same key.
+fn same_keys(a: &str, b: &str) -> bool {
+    match (a.parse::<Shortcut>(), b.parse::<Shortcut>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.eq_ignore_ascii_case(b),
+    }
+}
+
+#[tauri::command]
+pub fn list_shortcuts(app: AppHandle) -> Vec<ShortcutInfo> {
+    let overrides = overrides(&app);
+    DEFINITIONS
+        .iter()
+        .map(|d| ShortcutInfo {
+            action: d.action.to_string(),
+            label: d.label.to_string(),
+            scope: d.scope,
+            accelerator: effective(&app, &overrides, d),
+            default: d.default.to_string(),
+        })
+        .collect()
+}
+
+/// Rebind `action` (or unbind it with `null`), refusing accelerators another
+/// action already uses. Takes effect immediately and is saved in settings.
+#[tauri::command]
+pub fn set_shortcut(app: AppHandle, action: String, accelerator: Option<String>) -> Result<(), String> {
+    let def = definition(&action).ok_or_else(|| format!("Unknown shortcut action '{}'", action))?;
+    let accelerator = accelerator.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    let mut overrides = overrides(&app);
+    if let Some(wanted) = &accelerator {
+        let taken = DEFINITIONS
+            .iter()
+            .filter(|d| d.action != def.action)
+            .find(|d| effective(&app, &overrides, d).is_some_and(|a| same_keys(&a, wanted)));
+        if let Some(other) = taken {
+            return Err(format!("'{}' is already used by {}", wanted, other.label));
+        }
+        if (1..=9).any(|n| same_keys(&format!("CmdOrCtrl+{}", n), wanted)) {
+            return Err(format!("'{}' is reserved for switching tabs", wanted));
+        }
+    }
+    let previous = overrides.insert(action.clone(), accelerator.clone());
+    settings::write_section(&app, "shortcuts", &overrides)?;
+    // Registering is what validates the accelerator; undo the save if it fails.
+    let applied = match def.scope {
+        Scope::Global => hotkey::apply(&app, def.action, accelerator.as_deref()),
+        Scope::Menu => app_menu::refresh(&app).map_err(|e| format!("Invalid shortcut: {}", e)),
+    };
+    if let Err(e) = applied {
+        match previous {
+            Some(previous) => overrides.insert(action.clone(), previous),
+            None => overrides.remove(&action),
+        };
+        settings::write_section(&app, "shortcuts", &overrides)?;
+        if def.scope == Scope::Menu {
+            let _ = app_menu::refresh(&app);
+        }
+        return Err(e);
+    }
+    tracing::info!("{} bound to {}", action, accelerator.as_deref().unwrap_or("nothing"));
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, WebviewBuilder, Wry};
+
+use crate::ai_window_manager;
+use crate::error::AppError;
+
+/// Safari's own strings on Apple platforms, where the engine is WebKit, and
+/// Chrome's elsewhere, so sites don't serve features the engine lacks.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const DESKTOP_UA: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Safari/605.1.15";
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+const DESKTOP_UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36";
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const MOBILE_UA: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1";
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+const MOBILE_UA: &str = "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Mobile Safari/537.36";
+
+/// Layout width sites see in desktop mode on a phone; the page is zoomed out to fit.
+const DESKTOP_VIEWPORT: &str = "width=1280";
+
+/// Desktop mode rewrites the page's viewport so it lays out at desktop width;
+/// mobile mode reports a touch device to sites that check beyond the UA.
+const SITE_MODE_JS: &str = r#"
+(function(mode, viewport) {
+    if (mode === 'desktop') {
+        var apply = function() {
+            var meta = document.querySelector('meta[name="viewport"]');
+            if (!meta) {
+                if (!document.head) return;
+                meta = document.createElement('meta');
+                meta.name = 'viewport';
+                document.head.appendChild(meta);
+            }
+            if (meta.content !== viewport) meta.content = viewport;
+        };
+        document.addEventListener('DOMContentLoaded', function() {
+            apply();
+            new MutationObserver(apply).observe(document.head, { childList: true, subtree: true, attributes: true });
+        });
+        return;
+    }
+    try { Object.defineProperty(Navigator.prototype, 'maxTouchPoints', { get: function() { return 5; }, configurable: true }); } catch (e) {}
+    if (navigator.userAgentData) {
+        try { Object.defineProperty(NavigatorUAData.prototype, 'mobile', { get: function() { return true; }, configurable: true }); } catch (e) {}
+    }
+})(__MODE__, __VIEWPORT__);
+"#;
+
+/// `siteMode` field of a platform; unset leaves the engine's own user agent.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SiteMode {
+    Desktop,
+    Mobile,
+}
+
+impl SiteMode {
+    fn user_agent(self) -> &'static str {
+        match self {
+            SiteMode::Desktop => DESKTOP_UA,
+            SiteMode::Mobile => MOBILE_UA,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SiteMode::Desktop => "desktop",
+            SiteMode::Mobile => "mobile",
+        }
+    }
+}
+
+fn mode(app: &AppHandle, platform_id: &str) -> Option<SiteMode> {
+    crate::platforms::load(app).into_iter().find(|p| p.id == platform_id)?.site_mode
+}
+
+/// User agent and viewport script; both only take effect at creation.
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let Some(mode) = mode(app, platform_id) else {
+        return builder;
+    };
+    tracing::debug!("creating '{}' in {} site mode", platform_id, mode.as_str());
+    let script = SITE_MODE_JS
+        .replace("__MODE__", &crate::adapters::js_string(mode.as_str()))
+        .replace("__VIEWPORT__", &crate::adapters::js_string(DESKTOP_VIEWPORT));
+    builder.user_agent(mode.user_agent()).initialization_script(&script)
+}
+
+/// Switch a platform between the desktop and mobile site, or back to the
+/// default with `None`. An open tab is rebuilt on the page it was showing,
+/// since the user agent can't change on a live webview.
+#[tauri::command]
+pub fn set_site_mode(app: AppHandle, platform_id: String, mode: Option<SiteMode>) -> Result<(), AppError> {
+    if !crate::platforms::load(&app).iter().any(|p| p.id == platform_id) {
+        return Err(AppError::not_found("Platform", &platform_id));
+    }
+    let id = platform_id.clone();
+    let changed = crate::platforms::update(&app, move |list| {
+        let platform = list.iter_mut().find(|p| p.id == id).ok_or_else(|| format!("Platform '{}' not found", id))?;
+        let changed = platform.site_mode != mode;
+        platform.site_mode = mode;
+        Ok(changed)
+    })?;
+    if !changed {
+        return Ok(());
+    }
+    let Some(webview) = ai_window_manager::get_webview(&app, &platform_id) else {
+        return Ok(());
+    };
+    let active = ai_window_manager::active_platform(&app).as_deref() == Some(platform_id.as_str());
+    if !active {
+        // Reopened at its page in the new mode when next selected.
+        crate::suspend::suspend(&app, &platform_id, "site mode")?;
+        return Ok(());
+    }
+    let url = webview.url().map_err(|e| AppError::Other(e.to_string()))?.to_string();
+    ai_window_manager::close_webview(&app, &platform_id)?;
+    ai_window_manager::create_or_show_webview(app, platform_id, url, ai_window_manager::TAB_BAR_LOGICAL_HEIGHT)?;
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use serde::Deserialize;
+use tauri::{AppHandle, WebviewBuilder, Wry};
+
+use crate::{ai_window_manager, settings};
+
+/// `"snippets"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct SnippetSettings {
+    enabled: bool,
+    /// Abbreviations must start with this so ordinary words never expand.
+    prefix: String,
+    items: Vec<Snippet>,
+}
+
+impl Default for SnippetSettings {
+    fn default() -> Self {
+        Self { enabled: true, prefix: ";".to_string(), items: Vec::new() }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct Snippet {
+    /// Typed without the prefix, e.g. `sum` for `;sum`.
+    abbreviation: String,
+    text: String,
+    /// Platform ids the snippet is limited to; empty means everywhere.
+    platforms: Vec<String>,
+}
+
+/// Typing an abbreviation followed by a space asks Rust for its expansion
+/// and, if there is one, replaces the abbreviation in place. `insertText`
+/// keeps the edit on the page's undo stack and goes through React-controlled
+/// inputs and rich editors alike.
+const SNIPPETS_JS: &str = r#"
+(function(prefix) {
+    var tokenBefore = function(text) {
+        // Rich editors insert a non-breaking space at the end of a line.
+        var m = /(\S+)[ \u00a0]$/.exec(text);
+        return m && m[1].indexOf(prefix) === 0 && m[1].length > prefix.length ? m[1] : null;
+    };
+    document.addEventListener('input', function(e) {
+        if (e.inputType !== 'insertText' || (e.data !== ' ' && e.data !== '\u00a0')) return;
+        var el = e.target;
+        var isField = el && (el.tagName === 'TEXTAREA' || (el.tagName === 'INPUT' && el.type === 'text'));
+        var token, select;
+        if (isField) {
+            var end = el.selectionStart;
+            token = tokenBefore(el.value.slice(0, end));
+            select = function() { el.setSelectionRange(end - token.length - 1, end); };
+        } else if (el && el.isContentEditable) {
+            var sel = window.getSelection();
+            if (!sel.rangeCount || !sel.isCollapsed) return;
+            var node = sel.focusNode, offset = sel.focusOffset;
+            if (!node || node.nodeType !== 3) return;
+            token = tokenBefore(node.data.slice(0, offset));
+            select = function() {
+                var range = document.createRange();
+                range.setStart(node, offset - token.length - 1);
+                range.setEnd(node, offset);
+                sel.removeAllRanges();
+                sel.addRange(range);
+            };
+        }
+        if (!token) return;
+        window.__TAURI_INTERNALS__.invoke('expand_snippet', { abbreviation: token.slice(prefix.length) })
+            .then(function(text) {
+                if (typeof text !== 'string') return;
+                el.focus();
+                select();
+                document.execCommand('insertText', false, text);
+            })
+            .catch(function() {});
+    }, true);
+})(__PREFIX__);
+"#;
+
+pub fn configure_builder(app: &AppHandle, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let cfg: SnippetSettings = settings::section(app, "snippets");
+    if !cfg.enabled || cfg.prefix.is_empty() {
+        return builder;
+    }
+    builder.initialization_script(&SNIPPETS_JS.replace("__PREFIX__", &crate::adapters::js_string(&cfg.prefix)))
+}
+
+/// Called by the injected listener; the expansion for `abbreviation` in the
+/// calling tab, if one is defined. Settings are read per call so edits apply
+/// to open tabs right away.
+#[tauri::command]
+pub fn expand_snippet(app: AppHandle, webview: tauri::Webview, abbreviation: String) -> Option<String> {
+    let cfg: SnippetSettings = settings::section(&app, "snippets");
+    if !cfg.enabled {
+        return None;
+    }
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    cfg.items
+        .into_iter()
+        .filter(|s| s.platforms.is_empty() || s.platforms.contains(&platform_id))
+        .find(|s| s.abbreviation == abbreviation)
+        .map(|s| s.text)
+}
@@ -0,0 +1,80 @@
+use tauri::{AppHandle, Webview, WebviewBuilder, Wry};
+
+use crate::platforms;
+
+/// Marks every editable element with the platform's spellcheck choice and
+/// language, including editors the page adds later. `lang` is what WebView2
+/// and WebKit go by when picking a dictionary for a field.
+const SPELLCHECK_JS: &str = r#"
+(function(enabled, lang) {
+    var apply = function(el) {
+        el.setAttribute('spellcheck', enabled ? 'true' : 'false');
+        if (lang && !el.hasAttribute('lang')) el.setAttribute('lang', lang);
+    };
+    var scan = function(root) {
+        if (!root.querySelectorAll) return;
+        if (root.matches && root.matches('textarea, input, [contenteditable]')) apply(root);
+        root.querySelectorAll('textarea, input, [contenteditable]').forEach(apply);
+    };
+    var start = function() {
+        scan(document.documentElement);
+        new MutationObserver(function(records) {
+            records.forEach(function(r) {
+                if (r.type === 'attributes') apply(r.target);
+                r.addedNodes.forEach(scan);
+            });
+        }).observe(document.documentElement, {
+            childList: true, subtree: true, attributes: true, attributeFilter: ['contenteditable'],
+        });
+    };
+    if (document.documentElement) start();
+    else document.addEventListener('DOMContentLoaded', start);
+})(__ENABLED__, __LANG__);
+"#;
+
+/// `(enabled, languages)` for a platform, or `None` when it keeps the defaults.
+fn options(app: &AppHandle, platform_id: &str) -> Option<(bool, Vec<String>)> {
+    let platform = platforms::load(app).into_iter().find(|p| p.id == platform_id)?;
+    let languages: Vec<String> = platform
+        .spellcheck_languages
+        .iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if platform.spellcheck.is_none() && languages.is_empty() {
+        return None;
+    }
+    Some((platform.spellcheck.unwrap_or(true), languages))
+}
+
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let Some((enabled, languages)) = options(app, platform_id) else {
+        return builder;
+    };
+    tracing::debug!("creating '{}' with spellcheck {} {:?}", platform_id, enabled, languages);
+    let lang = languages.first().map(|l| crate::adapters::js_string(l)).unwrap_or_else(|| "null".to_string());
+    builder.initialization_script(&SPELLCHECK_JS.replace("__ENABLED__", &enabled.to_string()).replace("__LANG__", &lang))
+}
+
+/// WebKitGTK checks spelling per web context rather than per field, and can
+/// use several dictionaries at once.
+pub fn apply(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    #[cfg(target_os = "linux")]
+    if let Some((enabled, languages)) = options(app, platform_id) {
+        webview
+            .with_webview(move |wv| {
+                use webkit2gtk::{WebContextExt, WebViewExt};
+                let Some(context) = wv.inner().context() else { return };
+                context.set_spell_checking_enabled(enabled);
+                if !languages.is_empty() {
+                    // WebKitGTK wants `en_US`, not `en-US`.
+                    let languages: Vec<String> = languages.iter().map(|l| l.replace('-', "_")).collect();
+                    let languages: Vec<&str> = languages.iter().map(String::as_str).collect();
+                    context.set_spell_checking_languages(&languages);
+                }
+            })
+            .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (app, platform_id, webview);
+}
@@ -0,0 +1,92 @@
+use tauri::window::Color;
+use tauri::{AppHandle, Webview, WebviewBuilder, Wry};
+
+use crate::{adapters, platforms, theme};
+
+/// Page background for sites without an adapter color, per scheme.
+const FALLBACK_LIGHT: &str = "#ffffff";
+const FALLBACK_DARK: &str = "#1e1e1e";
+
+/// Paints the page in the tab's background color from the first frame until
+/// the site's own first contentful paint, so a dark site doesn't show the
+/// engine's white document while its stylesheets load.
+const PLACEHOLDER_JS: &str = r#"
+(function(color) {
+    if (window.top !== window) return;
+    var style = document.createElement('style');
+    style.textContent = 'html, body { background-color: ' + color + ' !important; }';
+    var root = document.documentElement || document;
+    root.appendChild(style);
+    var done = false;
+    var remove = function() {
+        if (done) return;
+        done = true;
+        style.remove();
+    };
+    try {
+        new PerformanceObserver(function(list) {
+            if (list.getEntriesByName('first-contentful-paint').length) remove();
+        }).observe({ type: 'paint', buffered: true });
+    } catch (e) {}
+    window.addEventListener('load', remove);
+    setTimeout(remove, 10000);
+})(__COLOR__);
+"#;
+
+/// `#rrggbb` or `#rgb`.
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    let digits: Vec<u8> = match hex.len() {
+        6 => (0..3).map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()).collect::<Option<_>>()?,
+        3 => hex.chars().map(|c| c.to_digit(16).map(|d| (d * 17) as u8)).collect::<Option<_>>()?,
+        _ => return None,
+    };
+    Some(Color(digits[0], digits[1], digits[2], 255))
+}
+
+/// The platform's `backgroundColor`, else its site's color for the tab's
+/// light or dark scheme, else a neutral one matching the scheme.
+fn hex_for(app: &AppHandle, platform_id: &str, url: &str) -> String {
+    let platform = platforms::load(app).into_iter().find(|p| p.id == platform_id);
+    if let Some(color) = platform.as_ref().and_then(|p| p.background_color.clone()) {
+        if parse_hex(&color).is_some() {
+            return color;
+        }
+        tracing::warn!("ignoring invalid backgroundColor '{}' of '{}'", color, platform_id);
+    }
+    let url = platform.map(|p| p.url).unwrap_or_else(|| url.to_string());
+    let dark = theme::scheme(app, platform_id) == "dark";
+    let colors = adapters::for_url(&url).page_colors;
+    match (colors, dark) {
+        (Some(c), true) => c.dark,
+        (Some(c), false) => c.light,
+        (None, true) => FALLBACK_DARK,
+        (None, false) => FALLBACK_LIGHT,
+    }
+    .to_string()
+}
+
+fn script(hex: &str) -> String {
+    PLACEHOLDER_JS.replace("__COLOR__", &adapters::js_string(hex))
+}
+
+/// Native background shown before anything is painted, plus the placeholder
+/// for every page the tab loads.
+pub fn configure_builder(app: &AppHandle, platform_id: &str, url: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let hex = hex_for(app, platform_id, url);
+    let builder = builder.initialization_script(&script(&hex));
+    match parse_hex(&hex) {
+        Some(color) => builder.background_color(color),
+        None => builder,
+    }
+}
+
+/// Re-color a live webview, e.g. after its scheme changed. Takes effect for
+/// the native background now and the placeholder from the next navigation.
+pub fn apply(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    let url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+    let Some(color) = parse_hex(&hex_for(app, platform_id, &url)) else { return };
+    if let Err(e) = webview.set_background_color(Some(color)) {
+        tracing::warn!("failed to set background color of '{}': {}", platform_id, e);
+    }
+}
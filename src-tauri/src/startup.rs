@@ -0,0 +1,101 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// If the frontend never reports its first paint (e.g. it failed to load),
+/// deferred initialization still runs after this long.
+const DEFERRED_FALLBACK: Duration = Duration::from_secs(5);
+
+static START: OnceLock<Instant> = OnceLock::new();
+static MARKS: Mutex<Vec<StartupMark>> = Mutex::new(Vec::new());
+static DEFERRED_DONE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupMark {
+    pub stage: String,
+    /// Milliseconds since `run()` started.
+    pub at_ms: u64,
+}
+
+/// Start the clock. Called first thing in `run()`.
+pub fn begin() {
+    let _ = START.set(Instant::now());
+}
+
+/// Record that a startup stage finished, logging total and per-stage time.
+pub fn mark(stage: &str) {
+    let at_ms = START.get().map(|s| s.elapsed().as_millis() as u64).unwrap_or(0);
+    let mut marks = MARKS.lock().unwrap();
+    let delta = at_ms - marks.last().map(|m| m.at_ms).unwrap_or(0);
+    tracing::info!("startup: {} at {}ms (+{}ms)", stage, at_ms, delta);
+    marks.push(StartupMark { stage: stage.to_string(), at_ms });
+}
+
+/// Everything that isn't needed to show the first tab: servers, background
+/// watchers, the update check and OS integration refreshes. Runs once, on the
+/// main thread, after the frontend's first paint.
+fn run_deferred(app: &AppHandle) {
+    if DEFERRED_DONE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = app.plugin(tauri_plugin_dialog::init()) {
+        tracing::warn!("failed to register dialog plugin: {}", e);
+    }
+    crate::autorefresh::init(app);
+    crate::cache_quota::init(app);
+    crate::connectivity::init(app);
+    crate::http_api::init(app);
+    crate::keepalive::init(app);
+    crate::heartbeat::init(app);
+    crate::suspend::init(app);
+    crate::event_bridge::init(app);
+    #[cfg(desktop)]
+    {
+        crate::idle::init(app);
+        crate::memory::init(app);
+        crate::power::init(app);
+        crate::sync::init(app);
+        crate::updater::init(app);
+    }
+    #[cfg(windows)]
+    if let Err(e) = crate::win32::install_send_to_shortcut() {
+        tracing::warn!("failed to install Send To shortcut: {}", e);
+    }
+    crate::preconnect::init(app);
+    crate::recent::refresh(app);
+    crate::trash::purge_expired(app);
+    mark("deferred init");
+}
+
+/// Arm the fallback for `run_deferred` in case `app_ready` never arrives.
+pub fn schedule_deferred(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(DEFERRED_FALLBACK);
+        if DEFERRED_DONE.load(Ordering::SeqCst) {
+            return;
+        }
+        tracing::warn!("no first paint after {:?}, running deferred init anyway", DEFERRED_FALLBACK);
+        let handle = app.clone();
+        let _ = app.run_on_main_thread(move || run_deferred(&handle));
+    });
+}
+
+/// Sent by the frontend once it has rendered.
+#[tauri::command]
+pub fn app_ready(app: AppHandle) {
+    if DEFERRED_DONE.load(Ordering::SeqCst) {
+        return;
+    }
+    mark("first paint");
+    let handle = app.clone();
+    let _ = app.run_on_main_thread(move || run_deferred(&handle));
+}
+
+#[tauri::command]
+pub fn get_startup_profile() -> Vec<StartupMark> {
+    MARKS.lock().unwrap().clone()
+}
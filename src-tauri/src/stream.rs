@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Webview, WebviewBuilder, Wry};
+
+use crate::{adapters, ai_window_manager, settings};
+
+/// Longest tail of the answer sent per update; enough for a preview.
+const PREVIEW_CHARS: usize = 2000;
+
+/// `"responseStream"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct ResponseStreamSettings {
+    enabled: bool,
+}
+
+impl Default for ResponseStreamSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Mirrors the latest assistant message while it renders. Mutations are
+/// coalesced to one report per 250ms, and the answer counts as finished
+/// after 1.5s without a change. Whatever is on the page when the observer
+/// starts (a reopened conversation) is taken as a baseline, not reported.
+const STREAM_JS: &str = r#"
+(function(selector, maxChars) {
+    if (window.top !== window || window.__anybrainStream) return;
+    var s = window.__anybrainStream = { last: null, count: 0, timer: null, idle: null, streaming: false };
+    var read = function() {
+        var nodes = document.querySelectorAll(selector);
+        return { count: nodes.length, text: nodes.length ? nodes[nodes.length - 1].innerText : '' };
+    };
+    var report = function(text, done) {
+        window.__TAURI_INTERNALS__.invoke('report_response', {
+            text: text.length > maxChars ? text.slice(-maxChars) : text,
+            length: text.length,
+            done: done,
+        }).catch(function() {});
+    };
+    var check = function() {
+        s.timer = null;
+        var now = read();
+        if (s.last === null) {
+            s.last = now.text;
+            s.count = now.count;
+            return;
+        }
+        if (now.text === s.last && now.count === s.count) return;
+        s.last = now.text;
+        s.count = now.count;
+        if (!now.text) return;
+        s.streaming = true;
+        report(now.text, false);
+        clearTimeout(s.idle);
+        s.idle = setTimeout(function() {
+            if (!s.streaming) return;
+            s.streaming = false;
+            report(s.last, true);
+        }, 1500);
+    };
+    var start = function() {
+        check();
+        new MutationObserver(function() {
+            if (!s.timer) s.timer = setTimeout(check, 250);
+        }).observe(document.body, { childList: true, subtree: true, characterData: true });
+    };
+    if (document.body) start();
+    else document.addEventListener('DOMContentLoaded', start);
+})(__SELECTOR__, __MAX__);
+"#;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ResponseStream<'a> {
+    platform_id: &'a str,
+    /// The end of the answer so far, at most `PREVIEW_CHARS` characters.
+    text: &'a str,
+    /// Length of the whole answer so far.
+    length: usize,
+    done: bool,
+}
+
+/// Sites without an adapter have no reliable answer selector; they're left alone.
+pub fn configure_builder(app: &AppHandle, url: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    let cfg: ResponseStreamSettings = settings::section(app, "responseStream");
+    let adapter = adapters::for_url(url);
+    if !cfg.enabled || adapter.key == "generic" {
+        return builder;
+    }
+    builder.initialization_script(
+        &STREAM_JS
+            .replace("__SELECTOR__", &adapters::js_string(adapter.response_selector))
+            .replace("__MAX__", &PREVIEW_CHARS.to_string()),
+    )
+}
+
+/// Called by the injected observer as the latest answer grows, and once more
+/// with `done` when it stops. Emits `response_stream` for live previews.
+#[tauri::command]
+pub fn report_response(app: AppHandle, webview: Webview, text: String, length: usize, done: bool) {
+    let platform_id = ai_window_manager::platform_for_label(&app, webview.label());
+    // The page trims already; don't trust it to.
+    let start = text.char_indices().rev().nth(PREVIEW_CHARS.saturating_sub(1)).map(|(i, _)| i).unwrap_or(0);
+    let _ = app.emit("response_stream", ResponseStream { platform_id: &platform_id, text: &text[start..], length, done });
+}
@@ -0,0 +1,153 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{ai_window_manager, settings};
+
+const WATCHDOG_TICK: Duration = Duration::from_secs(60);
+
+/// `"suspension"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SuspensionSettings {
+    /// Suspend hidden webviews after this many minutes of system idle; 0 disables.
+    pub idle_minutes: u64,
+    /// Act on webviews left hidden by `hide_all_webviews` this long with no
+    /// tab shown since; 0 disables.
+    pub hidden_all_minutes: u64,
+    /// `"suspend"` them, or only `"report"` them in the log and diagnostics.
+    pub hidden_all_action: String,
+}
+
+impl Default for SuspensionSettings {
+    fn default() -> Self {
+        Self { idle_minutes: 30, hidden_all_minutes: 10, hidden_all_action: "suspend".to_string() }
+    }
+}
+
+/// Suspended webviews are closed outright (freeing their renderer process);
+/// the URL they were on is kept so the next `create_or_show_webview` resumes
+/// the same conversation instead of the platform's home page.
+#[derive(Default)]
+pub struct SuspendState {
+    resume_urls: Mutex<HashMap<String, String>>,
+}
+
+/// Suspend one child webview. The visible one is never suspended.
+/// Returns whether anything was closed.
+pub fn suspend(app: &AppHandle, platform_id: &str, reason: &str) -> Result<bool, String> {
+    if ai_window_manager::active_platform(app).as_deref() == Some(platform_id) {
+        return Ok(false);
+    }
+    let Some(webview) = ai_window_manager::get_webview(app, platform_id) else {
+        return Ok(false);
+    };
+    if let Ok(url) = webview.url() {
+        app.state::<SuspendState>()
+            .resume_urls
+            .lock()
+            .unwrap()
+            .insert(platform_id.to_string(), url.to_string());
+    }
+    ai_window_manager::close_webview(app, platform_id)?;
+    tracing::info!("suspended '{}' ({})", platform_id, reason);
+    let _ = app.emit(
+        "webview_suspended",
+        serde_json::json!({ "platformId": platform_id, "reason": reason }),
+    );
+    Ok(true)
+}
+
+/// Platforms whose webviews exist but aren't on screen. Webviews parked in
+/// the recycle pool belong to no platform and aren't listed, and tabs in
+/// their own window count as on screen.
+pub fn hidden_webviews(app: &AppHandle) -> Vec<String> {
+    let active = ai_window_manager::active_platform(app);
+    app.webviews()
+        .iter()
+        .filter(|(label, webview)| ai_window_manager::is_main_child(webview) && !crate::recycle::is_pooled(app, label))
+        .map(|(label, _)| label)
+        .map(|label| ai_window_manager::platform_for_label(app, label))
+        .filter(|id| Some(id.as_str()) != active.as_deref())
+        .collect()
+}
+
+/// Suspend every hidden webview, returning the ones that were closed. The
+/// recycle pool is emptied too.
+pub fn suspend_hidden(app: &AppHandle, reason: &str) -> Vec<String> {
+    crate::recycle::clear(app);
+    hidden_webviews(app)
+        .into_iter()
+        .filter(|id| matches!(suspend(app, id, reason), Ok(true)))
+        .collect()
+}
+
+/// URL to reopen a suspended webview at, consumed on use.
+pub fn take_resume_url(app: &AppHandle, platform_id: &str) -> Option<String> {
+    app.try_state::<SuspendState>()?.resume_urls.lock().unwrap().remove(platform_id)
+}
+
+/// Drop the resume URL of a tab that was closed for good.
+pub fn forget(app: &AppHandle, platform_id: &str) {
+    take_resume_url(app, platform_id);
+}
+
+pub fn is_suspended(app: &AppHandle, platform_id: &str) -> bool {
+    app.try_state::<SuspendState>()
+        .is_some_and(|s| s.resume_urls.lock().unwrap().contains_key(platform_id))
+}
+
+#[tauri::command]
+pub fn suspend_webview(app: AppHandle, platform_id: String) -> Result<bool, String> {
+    suspend(&app, &platform_id, "manual")
+}
+
+fn watchdog_tick(app: &AppHandle) {
+    let cfg: SuspensionSettings = settings::section(app, "suspension");
+    if cfg.hidden_all_minutes == 0 {
+        return;
+    }
+    let limit = Duration::from_secs(cfg.hidden_all_minutes * 60);
+    let stale: Vec<String> = ai_window_manager::hidden_by_hide_all(app)
+        .into_iter()
+        .filter(|(_, hidden_for)| *hidden_for >= limit)
+        .map(|(label, _)| ai_window_manager::platform_for_label(app, &label))
+        .collect();
+    if stale.is_empty() {
+        return;
+    }
+    if cfg.hidden_all_action == "report" {
+        tracing::warn!("{} webviews hidden by hide_all_webviews for over {:?}: {:?}", stale.len(), limit, stale);
+        return;
+    }
+    for platform_id in stale {
+        if let Err(e) = suspend(app, &platform_id, "hidden too long") {
+            tracing::warn!("failed to suspend '{}': {}", platform_id, e);
+        }
+    }
+}
+
+/// Watch for webviews `hide_all_webviews` hid and nothing showed again, e.g.
+/// after a frontend bug, so they don't hold memory unseen. Settings are re-read every tick.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCHDOG_TICK);
+        watchdog_tick(&app);
+    });
+}
+
+/// Platforms with a suspended webview, sorted by id.
+pub fn suspended_platforms(app: &AppHandle) -> Vec<String> {
+    let Some(state) = app.try_state::<SuspendState>() else { return Vec::new() };
+    let mut ids: Vec<String> = state.resume_urls.lock().unwrap().keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+#[tauri::command]
+pub fn list_suspended_webviews(app: AppHandle) -> Vec<String> {
+    suspended_platforms(&app)
+}
@@ -0,0 +1,499 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use crate::{keychain, platforms, settings, workspaces};
+
+const TICK: Duration = Duration::from_secs(5 * 60);
+const PASSPHRASE_ACCOUNT: &str = "sync:passphrase";
+/// Subfolder of the user's sync folder that holds one file per device.
+const FOLDER_NAME: &str = "AnyBrain Sync";
+const FILE_SUFFIX: &str = ".anybrain-sync";
+/// The platform list travels as this document; settings sections as `settings.<key>`.
+const PLATFORMS_DOC: &str = "platforms";
+/// Settings that describe this machine rather than the user's setup.
+const LOCAL_SECTIONS: &[&str] = &[
+    "sync",
+    "appLock",
+    "autostart",
+    "batterySaver",
+    "cacheQuota",
+    "crashReporting",
+    "dns",
+    "eventBridge",
+    "hardwareAcceleration",
+    "httpApi",
+    "logging",
+    "proxy",
+    "updater",
+    "useSystemProxy",
+    "webview2",
+];
+
+/// `"sync"` section of settings.json. The passphrase lives in the keychain.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct SyncSettings {
+    enabled: bool,
+    /// A folder some other tool keeps in sync: iCloud Drive, Dropbox, Syncthing.
+    folder: String,
+}
+
+/// A synced document as of its last change on any device.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Doc {
+    value: Value,
+    modified_at: u64,
+    device: String,
+}
+
+/// Decrypted contents of one device's file.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+    device: String,
+    written_at: u64,
+    docs: BTreeMap<String, Doc>,
+}
+
+/// On-disk envelope. Each file has its own salt and nonce.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    v: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// sync_state.json: this device's id and the documents as last synced, used
+/// to tell local edits from remote ones.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct SyncState {
+    device_id: String,
+    docs: BTreeMap<String, Doc>,
+    last_sync: Option<u64>,
+    last_error: Option<String>,
+}
+
+/// A document changed on this device and on another since the last sync.
+/// The newer change is kept; the other is reported so nothing vanishes silently.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub doc: String,
+    pub kept_device: String,
+    pub discarded_device: String,
+    pub discarded: Value,
+}
+
+/// Outcome of merging other devices' documents into this one's.
+#[derive(Debug, Default)]
+struct Merged {
+    /// Documents last changed on another device, to be written here.
+    remote: BTreeMap<String, Doc>,
+    conflicts: Vec<SyncConflict>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub folder: String,
+    pub has_passphrase: bool,
+    pub device_id: String,
+    pub last_sync: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// Serializes sync runs (timer vs. `sync_now`).
+static RUNNING: Mutex<()> = Mutex::new(());
+
+fn state_file_path(app: &AppHandle) -> PathBuf {
+    workspaces::dir(app).join("sync_state.json")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn load_state(app: &AppHandle) -> SyncState {
+    let mut state: SyncState = crate::persist::read_to_string(&state_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    if state.device_id.is_empty() {
+        use rand::distributions::Alphanumeric;
+        use rand::Rng;
+        state.device_id = rand::thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect();
+    }
+    state
+}
+
+fn save_state(app: &AppHandle, state: &SyncState) {
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        crate::persist::write(state_file_path(app), json);
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default().hash_password_into(passphrase.as_bytes(), salt, &mut key).map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?.into());
+    let ciphertext = cipher.encrypt(XNonce::from_slice(&nonce), plaintext).map_err(|_| "encryption failed".to_string())?;
+    let envelope =
+        Envelope { v: 1, salt: BASE64.encode(salt), nonce: BASE64.encode(nonce), ciphertext: BASE64.encode(ciphertext) };
+    serde_json::to_string(&envelope).map_err(|e| e.to_string())
+}
+
+fn decrypt(passphrase: &str, data: &str) -> Result<Vec<u8>, String> {
+    let envelope: Envelope = serde_json::from_str(data).map_err(|e| e.to_string())?;
+    if envelope.v != 1 {
+        return Err(format!("unsupported sync file version {}", envelope.v));
+    }
+    let decode = |s: &str| BASE64.decode(s).map_err(|e| e.to_string());
+    let (salt, nonce, ciphertext) = (decode(&envelope.salt)?, decode(&envelope.nonce)?, decode(&envelope.ciphertext)?);
+    if nonce.len() != 24 {
+        return Err("malformed sync file".to_string());
+    }
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?.into());
+    // A wrong passphrase and a tampered file look the same here.
+    cipher.decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice()).map_err(|_| "wrong passphrase or corrupted file".to_string())
+}
+
+/// Current local values of every synced document.
+fn local_docs(app: &AppHandle) -> BTreeMap<String, Value> {
+    let mut docs = BTreeMap::new();
+    let list = serde_json::to_value(platforms::load(app)).unwrap_or(Value::Array(Vec::new()));
+    docs.insert(PLATFORMS_DOC.to_string(), list);
+    if let Value::Object(sections) = settings::read_value(app) {
+        for (key, value) in sections {
+            if !LOCAL_SECTIONS.contains(&key.as_str()) {
+                docs.insert(format!("settings.{}", key), value);
+            }
+        }
+    }
+    docs
+}
+
+/// Write a document that won on another device into this one.
+fn apply(app: &AppHandle, name: &str, value: &Value) -> Result<(), String> {
+    if name == PLATFORMS_DOC {
+        let list: Vec<platforms::Platform> = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+        return platforms::update(app, move |current| {
+            *current = list;
+            Ok(())
+        });
+    }
+    let Some(key) = name.strip_prefix("settings.") else {
+        return Ok(());
+    };
+    let mut patch = serde_json::Map::new();
+    patch.insert(key.to_string(), value.clone());
+    settings::merge(app, Value::Object(patch))
+}
+
+fn read_snapshots(dir: &Path, own_file: &Path, passphrase: &str) -> Vec<Snapshot> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p != own_file && p.to_string_lossy().ends_with(FILE_SUFFIX))
+        .filter_map(|path| {
+            let snapshot = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|data| decrypt(passphrase, &data))
+                .and_then(|plain| serde_json::from_slice::<Snapshot>(&plain).map_err(|e| e.to_string()));
+            match snapshot {
+                Ok(snapshot) => Some(snapshot),
+                Err(e) => {
+                    tracing::warn!("skipping sync file {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Merge other devices' snapshots, last writer wins per document. `base` is
+/// the documents as of the last sync, `local` the current ones with edits
+/// made here stamped.
+fn merge(base: &BTreeMap<String, Doc>, local: &BTreeMap<String, Doc>, snapshots: Vec<Snapshot>) -> Merged {
+    let mut docs = local.clone();
+    let mut merged = Merged::default();
+    for snapshot in snapshots {
+        for (name, remote) in snapshot.docs {
+            let current = docs.get(&name);
+            if current.is_some_and(|current| current.value == remote.value) {
+                continue;
+            }
+            // Changed on both sides since the last sync: the later edit wins.
+            let edited_here = local.get(&name) != base.get(&name);
+            let concurrent =
+                edited_here && base.get(&name).map_or(true, |base| remote.modified_at > base.modified_at);
+            let remote_wins = current.map_or(true, |current| remote.modified_at > current.modified_at);
+            if let (true, Some(current)) = (concurrent, current) {
+                let (kept, discarded) = if remote_wins { (&remote, current) } else { (current, &remote) };
+                merged.conflicts.push(SyncConflict {
+                    doc: name.clone(),
+                    kept_device: kept.device.clone(),
+                    discarded_device: discarded.device.clone(),
+                    discarded: discarded.value.clone(),
+                });
+            }
+            if remote_wins {
+                merged.remote.insert(name.clone(), remote.clone());
+                docs.insert(name, remote);
+            }
+        }
+    }
+    merged
+}
+
+/// One round: stamp local edits, merge every other device's newest documents,
+/// then publish this device's merged view.
+fn run(app: &AppHandle, cfg: &SyncSettings, passphrase: &str, state: &mut SyncState) -> Result<Vec<SyncConflict>, String> {
+    let dir = Path::new(&cfg.folder).join(FOLDER_NAME);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let own_file = dir.join(format!("{}{}", state.device_id, FILE_SUFFIX));
+    let now = now_millis();
+
+    // Documents edited here since the last sync get a fresh timestamp.
+    let base = state.docs.clone();
+    for (name, value) in local_docs(app) {
+        if state.docs.get(&name).map(|d| &d.value) != Some(&value) {
+            state.docs.insert(name, Doc { value, modified_at: now, device: state.device_id.clone() });
+        }
+    }
+
+    let merged = merge(&base, &state.docs, read_snapshots(&dir, &own_file, passphrase));
+    for (name, remote) in merged.remote {
+        apply(app, &name, &remote.value)?;
+        state.docs.insert(name, remote);
+    }
+
+    let snapshot = Snapshot { device: state.device_id.clone(), written_at: now, docs: state.docs.clone() };
+    let plain = serde_json::to_vec(&snapshot).map_err(|e| e.to_string())?;
+    let tmp = own_file.with_extension("tmp");
+    std::fs::write(&tmp, encrypt(passphrase, &plain)?)
+        .and_then(|_| std::fs::rename(&tmp, &own_file))
+        .map_err(|e| format!("{}: {}", own_file.display(), e))?;
+    Ok(merged.conflicts)
+}
+
+/// Sync once if enabled and configured. Conflicts are sent as `sync_conflict`.
+fn sync(app: &AppHandle) -> Result<(), String> {
+    let cfg: SyncSettings = settings::section(app, "sync");
+    if !cfg.enabled || cfg.folder.trim().is_empty() {
+        return Err("sync is not set up".to_string());
+    }
+    let passphrase = keychain::get_secret(PASSPHRASE_ACCOUNT).ok_or("no sync passphrase set")?;
+    let _running = RUNNING.lock().unwrap();
+    let mut state = load_state(app);
+    let result = run(app, &cfg, &passphrase, &mut state);
+    match &result {
+        Ok(conflicts) => {
+            state.last_sync = Some(now_millis());
+            state.last_error = None;
+            for conflict in conflicts {
+                tracing::info!("sync conflict on '{}': kept {}", conflict.doc, conflict.kept_device);
+                let _ = app.emit("sync_conflict", conflict);
+            }
+        }
+        Err(e) => {
+            tracing::warn!("sync failed: {}", e);
+            state.last_error = Some(e.clone());
+        }
+    }
+    save_state(app, &state);
+    result.map(|_| ())
+}
+
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        let cfg: SyncSettings = settings::section(&app, "sync");
+        if cfg.enabled && !crate::flight::active(&app) && !crate::guest::active() {
+            let _ = sync(&app);
+        }
+        std::thread::sleep(TICK);
+    });
+}
+
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || sync(&app)).await.map_err(|e| e.to_string())?
+}
+
+/// Store the passphrase that encrypts the sync files. Every device must use
+/// the same one; it never leaves the keychain.
+#[tauri::command]
+pub fn set_sync_passphrase(passphrase: String) -> Result<(), String> {
+    if passphrase.chars().count() < 8 {
+        return Err("passphrase must be at least 8 characters".to_string());
+    }
+    keychain::set_secret(PASSPHRASE_ACCOUNT, &passphrase)
+}
+
+#[tauri::command]
+pub fn get_sync_status(app: AppHandle) -> SyncStatus {
+    let cfg: SyncSettings = settings::section(&app, "sync");
+    let state = load_state(&app);
+    SyncStatus {
+        enabled: cfg.enabled,
+        folder: cfg.folder,
+        has_passphrase: keychain::get_secret(PASSPHRASE_ACCOUNT).is_some(),
+        device_id: state.device_id,
+        last_sync: state.last_sync,
+        last_error: state.last_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tamper(data: &str, f: impl FnOnce(&mut Envelope)) -> String {
+        let mut envelope: Envelope = serde_json::from_str(data).unwrap();
+        f(&mut envelope);
+        serde_json::to_string(&envelope).unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        let plaintext = br#"{"platforms":[{"id":"claude"}]}"#;
+        let data = encrypt("correct horse", plaintext).unwrap();
+        assert_eq!(decrypt("correct horse", &data).unwrap(), plaintext);
+        assert_eq!(decrypt("correct horse", &encrypt("correct horse", b"").unwrap()).unwrap(), b"");
+    }
+
+    #[test]
+    fn each_file_gets_its_own_salt_and_nonce() {
+        let a: Envelope = serde_json::from_str(&encrypt("pass", b"same").unwrap()).unwrap();
+        let b: Envelope = serde_json::from_str(&encrypt("pass", b"same").unwrap()).unwrap();
+        assert_ne!(a.salt, b.salt);
+        assert_ne!(a.nonce, b.nonce);
+        assert_ne!(a.ciphertext, b.ciphertext);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let data = encrypt("right", b"secret").unwrap();
+        assert_eq!(decrypt("wrong", &data).unwrap_err(), "wrong passphrase or corrupted file");
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails() {
+        let data = encrypt("pass", b"secret").unwrap();
+        let data = tamper(&data, |e| {
+            let mut bytes = BASE64.decode(&e.ciphertext).unwrap();
+            bytes[0] ^= 1;
+            e.ciphertext = BASE64.encode(bytes);
+        });
+        assert!(decrypt("pass", &data).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_version_and_malformed_files() {
+        let data = encrypt("pass", b"secret").unwrap();
+        assert!(decrypt("pass", &tamper(&data, |e| e.v = 2)).unwrap_err().contains("version 2"));
+        assert_eq!(decrypt("pass", &tamper(&data, |e| e.nonce = BASE64.encode([0u8; 12]))).unwrap_err(), "malformed sync file");
+        assert!(decrypt("pass", "not json").is_err());
+    }
+
+    fn doc(value: &str, modified_at: u64, device: &str) -> Doc {
+        Doc { value: Value::from(value), modified_at, device: device.to_string() }
+    }
+
+    fn docs(entries: &[(&str, Doc)]) -> BTreeMap<String, Doc> {
+        entries.iter().map(|(name, doc)| (name.to_string(), doc.clone())).collect()
+    }
+
+    fn snapshot(device: &str, entries: &[(&str, Doc)]) -> Snapshot {
+        Snapshot { device: device.to_string(), written_at: 0, docs: docs(entries) }
+    }
+
+    #[test]
+    fn newer_remote_edit_wins_without_conflict() {
+        let base = docs(&[("platforms", doc("a", 1, "here"))]);
+        let merged = merge(&base, &base, vec![snapshot("there", &[("platforms", doc("b", 2, "there"))])]);
+        assert_eq!(merged.remote, docs(&[("platforms", doc("b", 2, "there"))]));
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn documents_new_to_this_device_are_taken() {
+        let remote = snapshot("there", &[("settings.tts", doc("x", 5, "there"))]);
+        let merged = merge(&BTreeMap::new(), &BTreeMap::new(), vec![remote]);
+        assert_eq!(merged.remote, docs(&[("settings.tts", doc("x", 5, "there"))]));
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn stale_or_identical_remote_is_ignored() {
+        let base = docs(&[("platforms", doc("a", 1, "here"))]);
+        let local = docs(&[("platforms", doc("b", 3, "here"))]);
+        let merged = merge(&base, &local, vec![snapshot("there", &[("platforms", doc("a", 1, "here"))])]);
+        assert!(merged.remote.is_empty());
+        assert!(merged.conflicts.is_empty());
+
+        let merged = merge(&base, &base, vec![snapshot("there", &[("platforms", doc("a", 4, "there"))])]);
+        assert!(merged.remote.is_empty());
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn concurrent_edits_keep_the_later_one_and_report_the_other() {
+        let base = docs(&[("platforms", doc("a", 1, "here"))]);
+        let local = docs(&[("platforms", doc("mine", 3, "here"))]);
+
+        let merged = merge(&base, &local, vec![snapshot("there", &[("platforms", doc("theirs", 4, "there"))])]);
+        assert_eq!(merged.remote, docs(&[("platforms", doc("theirs", 4, "there"))]));
+        assert_eq!(merged.conflicts.len(), 1);
+        let conflict = &merged.conflicts[0];
+        assert_eq!((conflict.kept_device.as_str(), conflict.discarded_device.as_str()), ("there", "here"));
+        assert_eq!(conflict.discarded, Value::from("mine"));
+
+        let merged = merge(&base, &local, vec![snapshot("there", &[("platforms", doc("theirs", 2, "there"))])]);
+        assert!(merged.remote.is_empty());
+        assert_eq!(merged.conflicts.len(), 1);
+        let conflict = &merged.conflicts[0];
+        assert_eq!((conflict.kept_device.as_str(), conflict.discarded_device.as_str()), ("here", "there"));
+        assert_eq!(conflict.discarded, Value::from("theirs"));
+    }
+
+    #[test]
+    fn newest_of_several_devices_wins() {
+        let base = docs(&[("platforms", doc("a", 1, "here"))]);
+        let merged = merge(
+            &base,
+            &base,
+            vec![
+                snapshot("laptop", &[("platforms", doc("b", 5, "laptop"))]),
+                snapshot("phone", &[("platforms", doc("c", 3, "phone"))]),
+            ],
+        );
+        assert_eq!(merged.remote, docs(&[("platforms", doc("b", 5, "laptop"))]));
+        assert!(merged.conflicts.is_empty());
+    }
+}
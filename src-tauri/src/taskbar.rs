@@ -0,0 +1,61 @@
+use std::sync::Mutex;
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Manager};
+
+/// Downloads since the taskbar was last idle. The engines don't report byte
+/// progress, so the aggregate is the share of the batch that has finished.
+#[derive(Default)]
+pub struct TaskbarState {
+    batch: Mutex<Batch>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Batch {
+    started: u32,
+    finished: u32,
+}
+
+fn update(app: &AppHandle, batch: Batch) {
+    let Some(window) = app.get_window("main") else { return };
+    let running = batch.started - batch.finished;
+    let progress = match (running, batch.finished) {
+        (0, _) => ProgressBarState { status: Some(ProgressBarStatus::None), progress: None },
+        // Nothing to measure against until one of the batch completes.
+        (_, 0) => ProgressBarState { status: Some(ProgressBarStatus::Indeterminate), progress: None },
+        _ => ProgressBarState {
+            status: Some(ProgressBarStatus::Normal),
+            progress: Some((batch.finished * 100 / batch.started) as u64),
+        },
+    };
+    if let Err(e) = window.set_progress_bar(progress) {
+        tracing::debug!("failed to set taskbar progress: {}", e);
+    }
+    #[cfg(target_os = "macos")]
+    let _ = window.set_badge_count((running > 0).then_some(running as i64));
+}
+
+/// Called from the download handler when a download begins.
+pub fn on_download_started(app: &AppHandle) {
+    let Some(state) = app.try_state::<TaskbarState>() else { return };
+    let batch = {
+        let mut batch = state.batch.lock().unwrap();
+        batch.started += 1;
+        *batch
+    };
+    update(app, batch);
+}
+
+/// Called from the download handler when a download completes or fails.
+pub fn on_download_finished(app: &AppHandle) {
+    let Some(state) = app.try_state::<TaskbarState>() else { return };
+    let batch = {
+        let mut batch = state.batch.lock().unwrap();
+        batch.finished = (batch.finished + 1).min(batch.started);
+        let current = *batch;
+        if batch.finished == batch.started {
+            *batch = Batch::default();
+        }
+        current
+    };
+    update(app, batch);
+}
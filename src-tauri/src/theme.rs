@@ -0,0 +1,165 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, Manager, Theme, Webview, WebviewBuilder, Wry};
+
+use crate::{ai_window_manager, settings};
+
+/// `"appearance"` section of settings.json.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct AppearanceSettings {
+    /// Platform id -> `"system"` (default), `"dark"` or `"light"`.
+    platforms: HashMap<String, String>,
+}
+
+/// Answers `prefers-color-scheme` queries made through `matchMedia` with the
+/// scheme picked by Rust and notifies their change listeners when it flips.
+/// Engines that support a native override (WKWebView, WebView2) get one too,
+/// which also covers CSS media queries.
+const THEME_JS: &str = r#"
+(function(scheme) {
+    var s = window.__anybrainTheme;
+    if (!s) {
+        s = window.__anybrainTheme = { scheme: null, lists: [] };
+        var origMatchMedia = window.matchMedia.bind(window);
+        var re = /prefers-color-scheme\s*:\s*(dark|light)/i;
+        window.matchMedia = function(query) {
+            var m = re.exec(query);
+            if (!m) return origMatchMedia(query);
+            var want = m[1].toLowerCase(), listeners = [];
+            var list = {
+                media: query,
+                onchange: null,
+                get matches() { return s.scheme ? s.scheme === want : origMatchMedia(query).matches; },
+                addListener: function(fn) { listeners.push(fn); },
+                removeListener: function(fn) { listeners = listeners.filter(function(l) { return l !== fn; }); },
+                addEventListener: function(type, fn) { if (type === 'change') listeners.push(fn); },
+                removeEventListener: function(type, fn) { if (type === 'change') list.removeListener(fn); },
+                dispatchEvent: function() { return true; },
+                __notify: function() {
+                    var event = { matches: list.matches, media: query };
+                    if (typeof list.onchange === 'function') list.onchange(event);
+                    listeners.forEach(function(fn) { try { (fn.handleEvent ? fn.handleEvent.bind(fn) : fn).call(list, event); } catch (e) {} });
+                },
+            };
+            s.lists.push(list);
+            return list;
+        };
+        s.paint = function() {
+            if (document.documentElement) document.documentElement.style.colorScheme = s.scheme || '';
+        };
+        document.addEventListener('DOMContentLoaded', s.paint);
+    }
+    if (s.scheme === scheme) return;
+    s.scheme = scheme;
+    s.paint();
+    s.lists.forEach(function(list) { list.__notify(); });
+})(__SCHEME__);
+"#;
+
+fn mode(app: &AppHandle, platform_id: &str) -> String {
+    let cfg: AppearanceSettings = settings::section(app, "appearance");
+    cfg.platforms.get(platform_id).cloned().unwrap_or_else(|| "system".to_string())
+}
+
+fn system_theme(app: &AppHandle) -> Theme {
+    app.get_window("main").and_then(|w| w.theme().ok()).unwrap_or(Theme::Light)
+}
+
+/// `"dark"` or `"light"` for the platform, following the OS unless forced.
+pub fn scheme(app: &AppHandle, platform_id: &str) -> &'static str {
+    match mode(app, platform_id).as_str() {
+        "dark" => "dark",
+        "light" => "light",
+        _ if system_theme(app) == Theme::Dark => "dark",
+        _ => "light",
+    }
+}
+
+fn script(scheme: &str) -> String {
+    THEME_JS.replace("__SCHEME__", &crate::adapters::js_string(scheme))
+}
+
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    builder.initialization_script(&script(scheme(app, platform_id)))
+}
+
+/// Push the platform's scheme into a live webview.
+pub fn apply(app: &AppHandle, platform_id: &str, webview: &Webview) {
+    let forced = match mode(app, platform_id).as_str() {
+        "dark" => Some(Theme::Dark),
+        "light" => Some(Theme::Light),
+        _ => None,
+    };
+    set_native(webview, forced);
+    let _ = webview.eval(&script(scheme(app, platform_id)));
+    crate::splash::apply(app, platform_id, webview);
+}
+
+#[cfg(target_os = "macos")]
+fn set_native(webview: &Webview, forced: Option<Theme>) {
+    webview
+        .with_webview(move |wv| unsafe { crate::macos::set_view_appearance(wv.inner(), forced.map(|t| t == Theme::Dark)) })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+#[cfg(windows)]
+fn set_native(webview: &Webview, forced: Option<Theme>) {
+    webview
+        .with_webview(move |wv| unsafe {
+            use webview2_com::Microsoft::Web::WebView2::Win32::*;
+            use windows_core::Interface;
+            let Ok(core) = wv.controller().CoreWebView2() else { return };
+            let Ok(profile) = core.cast::<ICoreWebView2_13>().and_then(|c| c.Profile()) else { return };
+            let scheme = match forced {
+                Some(Theme::Dark) => COREWEBVIEW2_PREFERRED_COLOR_SCHEME_DARK,
+                Some(_) => COREWEBVIEW2_PREFERRED_COLOR_SCHEME_LIGHT,
+                None => COREWEBVIEW2_PREFERRED_COLOR_SCHEME_AUTO,
+            };
+            if let Err(e) = profile.SetPreferredColorScheme(scheme) {
+                tracing::warn!("failed to set color scheme: {}", e);
+            }
+        })
+        .unwrap_or_else(|e| tracing::warn!("with_webview error: {}", e));
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn set_native(_webview: &Webview, _forced: Option<Theme>) {}
+
+/// Called from the main window's `ThemeChanged` event: every tab following
+/// the system switches together.
+pub fn on_system_theme_changed(app: &AppHandle, theme: Theme) {
+    tracing::info!("system theme changed to {:?}", theme);
+    for (label, webview) in app.webviews() {
+        if label == "main" {
+            continue;
+        }
+        let platform_id = ai_window_manager::platform_for_label(app, &label);
+        if mode(app, &platform_id) == "system" {
+            apply(app, &platform_id, &webview);
+        }
+    }
+    let _ = app.emit("theme_changed", serde_json::json!({ "theme": if theme == Theme::Dark { "dark" } else { "light" } }));
+}
+
+/// Force a platform dark or light, or back to `"system"`. Saved in settings
+/// and applied to the open tab straight away.
+#[tauri::command]
+pub fn set_platform_theme(app: AppHandle, platform_id: String, mode: String) -> Result<(), String> {
+    if !matches!(mode.as_str(), "system" | "dark" | "light") {
+        return Err(format!("Unknown theme mode '{}'", mode));
+    }
+    let mut value = settings::read_value(&app).get("appearance").cloned().unwrap_or_else(|| serde_json::json!({}));
+    if !value.is_object() {
+        value = serde_json::json!({});
+    }
+    if !value["platforms"].is_object() {
+        value["platforms"] = serde_json::json!({});
+    }
+    value["platforms"][&platform_id] = serde_json::Value::String(mode);
+    settings::write_section(&app, "appearance", &value)?;
+    if let Some(webview) = ai_window_manager::get_webview(&app, &platform_id) {
+        apply(&app, &platform_id, &webview);
+    }
+    Ok(())
+}
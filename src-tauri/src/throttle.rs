@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Webview};
+
+use crate::settings;
+
+/// `"backgroundThrottling"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct ThrottleSettings {
+    enabled: bool,
+    /// Platform ids that must keep running at full speed while hidden, e.g.
+    /// sites that stop generating when they think the tab is in the background.
+    exempt: Vec<String>,
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        Self { enabled: true, exempt: Vec::new() }
+    }
+}
+
+/// Hidden child webviews are only hidden natively, so pages keep animating
+/// spinners and polling as if they were on screen. This reports the page as
+/// hidden, slows animation frames to 1/s and lets intervals fire at most once
+/// a second until the webview is shown again. Safe to run repeatedly.
+const THROTTLE_JS: &str = r#"
+(function(hidden) {
+    var s = window.__anybrainThrottle;
+    if (!s) {
+        if (!hidden) return;
+        s = window.__anybrainThrottle = { hidden: false };
+        var raf = window.requestAnimationFrame.bind(window);
+        var setInterval = window.setInterval.bind(window);
+        Object.defineProperty(document, 'visibilityState', {
+            configurable: true,
+            get: function() { return s.hidden ? 'hidden' : 'visible'; }
+        });
+        Object.defineProperty(document, 'hidden', {
+            configurable: true,
+            get: function() { return s.hidden; }
+        });
+        window.requestAnimationFrame = function(cb) {
+            if (!s.hidden) return raf(cb);
+            return window.setTimeout(function() { cb(performance.now()); }, 1000);
+        };
+        window.setInterval = function(fn, delay) {
+            var args = Array.prototype.slice.call(arguments, 2);
+            var last = 0;
+            return setInterval(function() {
+                if (s.hidden && Date.now() - last < 1000) return;
+                last = Date.now();
+                typeof fn === 'function' ? fn.apply(this, args) : eval(fn);
+            }, delay);
+        };
+    }
+    if (s.hidden === hidden) return;
+    s.hidden = hidden;
+    document.dispatchEvent(new Event('visibilitychange'));
+})(__HIDDEN__);
+"#;
+
+fn applies_to(app: &AppHandle, platform_id: &str) -> bool {
+    let cfg: ThrottleSettings = settings::section(app, "backgroundThrottling");
+    cfg.enabled && !cfg.exempt.iter().any(|id| id == platform_id)
+}
+
+fn set_hidden(webview: &Webview, hidden: bool) {
+    let _ = webview.eval(&THROTTLE_JS.replace("__HIDDEN__", if hidden { "true" } else { "false" }));
+}
+
+/// Called right after a child webview is hidden.
+pub fn on_hidden(app: &AppHandle, webview: &Webview) {
+    if applies_to(app, &crate::ai_window_manager::platform_for_label(app, webview.label())) {
+        set_hidden(webview, true);
+    }
+}
+
+/// Called right after a child webview is shown. Always restores, so a platform
+/// exempted while hidden doesn't stay throttled.
+pub fn on_shown(webview: &Webview) {
+    set_hidden(webview, false);
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{ai_window_manager, settings};
+
+/// Height of the frontend's chrome (tab bar, plus the titlebar in custom
+/// mode) in logical pixels, as f64 bits. Child webviews sit below it.
+static CHROME_HEIGHT: AtomicU64 = AtomicU64::new(0);
+/// Cleared in zen mode, when the frontend hides its chrome and child webviews
+/// take the whole window. The measured height is kept for coming back.
+static CHROME_VISIBLE: AtomicBool = AtomicBool::new(true);
+
+/// macOS keeps its traffic lights in custom mode; this is the room they need.
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHTS_WIDTH: f64 = 78.0;
+
+/// `"titlebar"` section of settings.json. Read at startup.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct TitlebarSettings {
+    /// `"native"` or `"custom"`: the frontend draws the titlebar itself and
+    /// the window loses its native one (macOS keeps the traffic lights).
+    mode: String,
+}
+
+impl Default for TitlebarSettings {
+    fn default() -> Self {
+        Self { mode: "native".to_string() }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TitlebarLayout {
+    mode: String,
+    /// Whether the OS still draws window controls inside the window.
+    native_controls: bool,
+    /// Logical pixels to keep clear on each side for those controls.
+    controls_inset_left: f64,
+    controls_inset_right: f64,
+    chrome_height: f64,
+    chrome_visible: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct DragRegion {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Starts a window drag on presses inside the regions Rust was given, skipping
+/// interactive elements. Double-click toggles maximize, like a native titlebar.
+const DRAG_JS: &str = r#"
+(function(regions) {
+    window.__anybrainDragRegions = regions;
+    if (window.__anybrainDragInstalled) return;
+    window.__anybrainDragInstalled = true;
+    var inRegion = function(e) {
+        if (e.button !== 0 || e.target.closest('button, a, input, select, textarea, [data-no-drag]')) return false;
+        return window.__anybrainDragRegions.some(function(r) {
+            return e.clientX >= r.x && e.clientX < r.x + r.width && e.clientY >= r.y && e.clientY < r.y + r.height;
+        });
+    };
+    document.addEventListener('mousedown', function(e) {
+        if (!inRegion(e)) return;
+        var invoke = window.__TAURI_INTERNALS__.invoke;
+        invoke(e.detail === 2 ? 'window_control' : 'start_window_drag', e.detail === 2 ? { action: 'toggle_maximize' } : {});
+    });
+})(__REGIONS__);
+"#;
+
+/// Logical height child webviews are placed below; none in zen mode.
+pub fn chrome_height() -> f64 {
+    if !chrome_visible() {
+        return 0.0;
+    }
+    match f64::from_bits(CHROME_HEIGHT.load(Ordering::Relaxed)) {
+        h if h > 0.0 => h,
+        _ => ai_window_manager::TAB_BAR_LOGICAL_HEIGHT,
+    }
+}
+
+pub fn chrome_visible() -> bool {
+    CHROME_VISIBLE.load(Ordering::Relaxed)
+}
+
+fn mode(app: &AppHandle) -> String {
+    let cfg: TitlebarSettings = settings::section(app, "titlebar");
+    cfg.mode
+}
+
+/// Configure the main window's decorations for the saved mode. Runs in setup;
+/// switching modes takes effect on the next launch.
+pub fn init(app: &AppHandle) {
+    if mode(app) != "custom" {
+        return;
+    }
+    let Some(window) = app.get_window("main") else { return };
+    #[cfg(target_os = "macos")]
+    let result = window.set_title_bar_style(tauri::TitleBarStyle::Overlay);
+    #[cfg(not(target_os = "macos"))]
+    let result = window.set_decorations(false);
+    match result {
+        Ok(()) => tracing::info!("using custom titlebar"),
+        Err(e) => tracing::warn!("failed to hide native titlebar: {}", e),
+    }
+}
+
+#[tauri::command]
+pub fn get_titlebar_layout(app: AppHandle) -> TitlebarLayout {
+    let mode = mode(&app);
+    let custom = mode == "custom";
+    #[cfg(target_os = "macos")]
+    let (native_controls, inset_left) = (custom, if custom { TRAFFIC_LIGHTS_WIDTH } else { 0.0 });
+    #[cfg(not(target_os = "macos"))]
+    let (native_controls, inset_left) = (false, 0.0);
+    TitlebarLayout {
+        mode,
+        native_controls,
+        controls_inset_left: inset_left,
+        controls_inset_right: 0.0,
+        chrome_height: chrome_height(),
+        chrome_visible: chrome_visible(),
+    }
+}
+
+/// Tell Rust how tall the frontend's chrome is so child webviews start below
+/// it; every child is re-fitted at once.
+#[tauri::command]
+pub fn set_chrome_height(app: AppHandle, height: f64) -> Result<(), String> {
+    if !(0.0..=400.0).contains(&height) {
+        return Err(format!("Unreasonable chrome height {}", height));
+    }
+    CHROME_HEIGHT.store(height.to_bits(), Ordering::Relaxed);
+    let window = app.get_window("main").ok_or("Main window not found")?;
+    let (position, size) = ai_window_manager::compute_child_bounds(&window);
+    ai_window_manager::reposition_all_children(&app, position, size);
+    Ok(())
+}
+
+/// Hide the frontend's chrome for a distraction-free view, or bring it back.
+/// Children are re-fitted to the new offset and every window is told through
+/// `chrome_visibility_changed`, so the menu and the frontend stay in step.
+#[tauri::command]
+pub fn set_chrome_visible(app: AppHandle, visible: bool) -> Result<(), String> {
+    if CHROME_VISIBLE.swap(visible, Ordering::Relaxed) == visible {
+        return Ok(());
+    }
+    let window = app.get_window("main").ok_or("Main window not found")?;
+    let (position, size) = ai_window_manager::compute_child_bounds(&window);
+    ai_window_manager::reposition_all_children(&app, position, size);
+    tracing::info!("zen mode {}", if visible { "off" } else { "on" });
+    let _ = app.emit("chrome_visibility_changed", serde_json::json!({ "visible": visible }));
+    Ok(())
+}
+
+/// Regions of the main webview (logical px, viewport coordinates) that drag
+/// the window. Replaces the previous set.
+#[tauri::command]
+pub fn set_drag_regions(app: AppHandle, regions: Vec<DragRegion>) -> Result<(), String> {
+    let webview = app.get_webview("main").ok_or("Main webview not found")?;
+    let regions = serde_json::to_string(&regions).map_err(|e| e.to_string())?;
+    webview.eval(&DRAG_JS.replace("__REGIONS__", &regions)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn start_window_drag(app: AppHandle) -> Result<(), String> {
+    let window = app.get_window("main").ok_or("Main window not found")?;
+    window.start_dragging().map_err(|e| e.to_string())
+}
+
+/// In-window controls for platforms where custom mode removes the native ones.
+#[tauri::command]
+pub fn window_control(app: AppHandle, action: String) -> Result<(), String> {
+    let window = app.get_window("main").ok_or("Main window not found")?;
+    let result = match action.as_str() {
+        "minimize" => window.minimize(),
+        "toggle_maximize" => match window.is_maximized() {
+            Ok(true) => window.unmaximize(),
+            _ => window.maximize(),
+        },
+        "close" => window.close(),
+        other => return Err(format!("Unknown window action '{}'", other)),
+    };
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::platforms::{self, Platform};
+use crate::{ai_window_manager, settings, workspaces};
+
+/// `"trash"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct TrashSettings {
+    /// Deleted items older than this are purged at startup.
+    retention_days: u64,
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self { retention_days: 7 }
+    }
+}
+
+/// What a tombstone stands for.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Deleted {
+    /// A platform entry and its place in the list.
+    #[serde(rename_all = "camelCase")]
+    Platform { platform: Platform, position: usize },
+    /// A `webdata/` store (cookies, logins, caches), moved into the trash
+    /// directory rather than deleted.
+    #[serde(rename_all = "camelCase")]
+    WebData { store_key: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tombstone {
+    pub id: String,
+    pub deleted_at: u64,
+    #[serde(flatten)]
+    pub item: Deleted,
+}
+
+/// Serializes read-modify-write of trash.json.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn trash_dir(app: &AppHandle) -> PathBuf {
+    workspaces::dir(app).join("trash")
+}
+
+fn trash_file_path(app: &AppHandle) -> PathBuf {
+    workspaces::dir(app).join("trash.json")
+}
+
+fn webdata_dir(app: &AppHandle, store_key: &str) -> PathBuf {
+    workspaces::dir(app).join("webdata").join(store_key)
+}
+
+fn new_id() -> String {
+    use rand::distributions::Alphanumeric;
+    use rand::Rng;
+    rand::thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn load(app: &AppHandle) -> Vec<Tombstone> {
+    crate::persist::read_to_string(&trash_file_path(app))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn store(app: &AppHandle, tombstones: &[Tombstone]) {
+    if let Ok(json) = serde_json::to_string_pretty(tombstones) {
+        crate::persist::write(trash_file_path(app), json);
+    }
+}
+
+fn push(app: &AppHandle, item: Deleted) -> Tombstone {
+    let tombstone = Tombstone { id: new_id(), deleted_at: now_millis(), item };
+    let _guard = LOCK.lock().unwrap();
+    let mut tombstones = load(app);
+    tombstones.push(tombstone.clone());
+    store(app, &tombstones);
+    tombstone
+}
+
+fn take(app: &AppHandle, id: &str) -> Result<Tombstone, String> {
+    let _guard = LOCK.lock().unwrap();
+    let mut tombstones = load(app);
+    let index = tombstones.iter().position(|t| t.id == id).ok_or_else(|| format!("'{}' is not in the trash", id))?;
+    let tombstone = tombstones.remove(index);
+    store(app, &tombstones);
+    Ok(tombstone)
+}
+
+/// Close every tab whose pages live in `store_key`, so its files can be moved.
+fn close_store(app: &AppHandle, store_key: &str) {
+    let labels: Vec<String> = app
+        .webviews()
+        .values()
+        .filter(|w| w.label() != "main")
+        .filter(|w| w.url().is_ok_and(|u| ai_window_manager::data_store_key(u.as_str()) == store_key))
+        .map(|w| w.label().to_string())
+        .collect();
+    for label in labels {
+        let platform_id = ai_window_manager::platform_for_label(app, &label);
+        let _ = ai_window_manager::close_webview(app, &platform_id);
+    }
+}
+
+/// Remove a platform from the saved list, keeping it restorable until purged.
+/// Its web data is left alone.
+#[tauri::command]
+pub fn remove_platform(app: AppHandle, platform_id: String) -> Result<Tombstone, String> {
+    let id = platform_id.clone();
+    let (platform, position) = platforms::update(&app, move |list| {
+        let position = list.iter().position(|p| p.id == id).ok_or_else(|| format!("Platform '{}' not found", id))?;
+        Ok((list.remove(position), position))
+    })?;
+    let _ = ai_window_manager::close_webview(&app, &platform_id);
+    tracing::info!("moved platform '{}' to the trash", platform_id);
+    Ok(push(&app, Deleted::Platform { platform, position }))
+}
+
+/// Sign a platform out by setting its web data aside. Platforms on the same
+/// host share the data, so their tabs close too.
+#[tauri::command]
+pub fn clear_platform_data(app: AppHandle, platform_id: String) -> Result<Tombstone, String> {
+    let platform = platforms::load(&app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .ok_or_else(|| format!("Platform '{}' not found", platform_id))?;
+    let store_key = ai_window_manager::data_store_key(&platform.url);
+    let source = webdata_dir(&app, &store_key);
+    if !source.exists() {
+        return Err(format!("'{}' has no saved data", platform_id));
+    }
+    close_store(&app, &store_key);
+    let tombstone = push(&app, Deleted::WebData { store_key: store_key.clone() });
+    let target = trash_dir(&app).join(&tombstone.id);
+    let moved = std::fs::create_dir_all(trash_dir(&app)).and_then(|_| std::fs::rename(&source, &target));
+    if let Err(e) = moved {
+        let _ = take(&app, &tombstone.id);
+        return Err(format!("failed to move {}: {}", source.display(), e));
+    }
+    tracing::info!("moved web data '{}' to the trash", store_key);
+    Ok(tombstone)
+}
+
+/// Put a deleted item back. Restored platforms return to their old position;
+/// restored web data replaces whatever the platform collected since.
+#[tauri::command]
+pub fn undo_delete(app: AppHandle, id: String) -> Result<(), String> {
+    let tombstone = take(&app, &id)?;
+    match &tombstone.item {
+        Deleted::Platform { platform, position } => {
+            let (platform, position) = (platform.clone(), *position);
+            platforms::update(&app, move |list| {
+                if !list.iter().any(|p| p.id == platform.id) {
+                    list.insert(position.min(list.len()), platform);
+                }
+                Ok(())
+            })?;
+        }
+        Deleted::WebData { store_key } => {
+            close_store(&app, store_key);
+            let target = webdata_dir(&app, store_key);
+            let source = trash_dir(&app).join(&tombstone.id);
+            let restored = (|| {
+                if target.exists() {
+                    std::fs::remove_dir_all(&target)?;
+                }
+                std::fs::rename(&source, &target)
+            })();
+            if let Err(e) = restored {
+                // Keep it in the trash so nothing is lost.
+                let _guard = LOCK.lock().unwrap();
+                let mut tombstones = load(&app);
+                tombstones.push(tombstone.clone());
+                store(&app, &tombstones);
+                return Err(format!("failed to restore {}: {}", target.display(), e));
+            }
+        }
+    }
+    tracing::info!("restored '{}' from the trash", id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_trash(app: AppHandle) -> Vec<Tombstone> {
+    let mut tombstones = load(&app);
+    tombstones.reverse();
+    tombstones
+}
+
+fn purge_matching(app: &AppHandle, doomed: impl Fn(&Tombstone) -> bool) -> usize {
+    let purged: Vec<Tombstone> = {
+        let _guard = LOCK.lock().unwrap();
+        let (purged, kept): (Vec<_>, Vec<_>) = load(app).into_iter().partition(|t| doomed(t));
+        if purged.is_empty() {
+            return 0;
+        }
+        store(app, &kept);
+        purged
+    };
+    for tombstone in &purged {
+        if let Deleted::WebData { .. } = tombstone.item {
+            let path = trash_dir(app).join(&tombstone.id);
+            if let Err(e) = std::fs::remove_dir_all(&path) {
+                tracing::warn!("failed to delete {}: {}", path.display(), e);
+            }
+        }
+    }
+    purged.len()
+}
+
+/// Permanently delete one item, or everything in the trash when `id` is omitted.
+#[tauri::command]
+pub fn purge_trash(app: AppHandle, id: Option<String>) -> usize {
+    purge_matching(&app, |t| id.is_none() || id.as_deref() == Some(t.id.as_str()))
+}
+
+/// Purge items past the retention period. Runs with the deferred startup work.
+pub fn purge_expired(app: &AppHandle) {
+    if crate::guest::active() {
+        return;
+    }
+    let cfg: TrashSettings = settings::section(app, "trash");
+    let cutoff = now_millis().saturating_sub(cfg.retention_days * 24 * 60 * 60 * 1000);
+    let purged = purge_matching(app, |t| t.deleted_at < cutoff);
+    if purged > 0 {
+        tracing::info!("purged {} expired items from the trash", purged);
+    }
+}
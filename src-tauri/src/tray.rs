@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter};
+
+use crate::platforms;
+use crate::settings;
+
+const TRAY_ID: &str = "main";
+const PLATFORM_PREFIX: &str = "platform:";
+
+/// `"tray"` section of settings.json.
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraySettings {
+    /// Hide the main window instead of quitting when it is closed.
+    pub close_to_tray: bool,
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    for platform in platforms::load(app).iter().filter(|p| !p.hidden) {
+        let item = MenuItem::with_id(app, format!("{}{}", PLATFORM_PREFIX, platform.id), &platform.name, true, None::<&str>)?;
+        menu.append(&item)?;
+    }
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, "toggle", "显示/隐藏窗口", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app)?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Brainer")
+        .menu(&menu)
+        .on_menu_event(|app, event| {
+            let id = event.id().as_ref();
+            if let Some(platform_id) = id.strip_prefix(PLATFORM_PREFIX) {
+                crate::show_main_window(app);
+                let _ = app.emit("activate_platform", platform_id.to_string());
+                return;
+            }
+            match id {
+                "toggle" => crate::toggle_main_window(app),
+                "quit" => app.exit(0),
+                _ => {}
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuild the platform list in the tray menu after platforms.json changes.
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => tracing::warn!("failed to rebuild menu: {}", e),
+    }
+}
@@ -0,0 +1,171 @@
+use serde::Deserialize;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::adapters;
+use crate::bridge;
+use crate::settings;
+
+/// `"tts"` section of settings.json.
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct TtsSettings {
+    voice: Option<String>,
+    /// Words per minute.
+    rate: Option<u32>,
+}
+
+#[derive(Default)]
+pub struct TtsState {
+    child: Mutex<Option<Child>>,
+}
+
+/// Build the platform speech command. Text is fed through stdin so long answers
+/// don't hit argument length limits.
+fn speech_command(cfg: &TtsSettings) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("say");
+        if let Some(voice) = &cfg.voice {
+            cmd.arg("-v").arg(voice);
+        }
+        if let Some(rate) = cfg.rate {
+            cmd.arg("-r").arg(rate.to_string());
+        }
+        cmd
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let mut script = String::from("Add-Type -AssemblyName System.Speech; $s = New-Object System.Speech.Synthesis.SpeechSynthesizer;");
+        if let Some(voice) = &cfg.voice {
+            script.push_str(&format!(" $s.SelectVoice('{}');", voice.replace('\'', "''")));
+        }
+        if let Some(rate) = cfg.rate {
+            // SpeechSynthesizer.Rate is -10..10 with 0 ≈ 180 wpm
+            let r = ((rate as i32 - 180) / 20).clamp(-10, 10);
+            script.push_str(&format!(" $s.Rate = {};", r));
+        }
+        script.push_str(" $s.Speak([Console]::In.ReadToEnd());");
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-Command", script.as_str()]);
+        cmd
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let mut cmd = Command::new("espeak-ng");
+        cmd.arg("--stdin");
+        if let Some(voice) = &cfg.voice {
+            cmd.arg("-v").arg(voice);
+        }
+        if let Some(rate) = cfg.rate {
+            cmd.arg("-s").arg(rate.to_string());
+        }
+        cmd
+    }
+}
+
+fn stop_current(state: &TtsState) {
+    if let Some(mut child) = state.child.lock().unwrap().take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+fn speak(app: &AppHandle, text: &str) -> Result<(), String> {
+    let state = app.state::<TtsState>();
+    stop_current(&state);
+
+    let cfg: TtsSettings = settings::section(app, "tts");
+    let mut child = speech_command(&cfg)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start speech synthesizer: {}", e))?;
+    // Fed from its own thread: the synthesizer reads as it speaks, so a long
+    // answer fills the pipe and the write would block the caller until it's done.
+    if let Some(mut stdin) = child.stdin.take() {
+        let text = text.to_string();
+        std::thread::spawn(move || {
+            // Fails with a broken pipe when the utterance is stopped early.
+            if let Err(e) = stdin.write_all(text.as_bytes()) {
+                tracing::debug!("stopped feeding synthesizer: {}", e);
+            }
+            // stdin dropped here so the synthesizer sees EOF
+        });
+    }
+    let pid = child.id();
+    tracing::info!("speaking {} chars (pid={})", text.len(), pid);
+    *state.child.lock().unwrap() = Some(child);
+    let _ = app.emit("tts_started", ());
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(250));
+        let state = app_handle.state::<TtsState>();
+        let mut slot = state.child.lock().unwrap();
+        let finished = match slot.as_mut() {
+            Some(child) if child.id() == pid => !matches!(child.try_wait(), Ok(None)),
+            // Replaced by a newer utterance or stopped explicitly
+            _ => break,
+        };
+        if finished {
+            slot.take();
+            drop(slot);
+            let _ = app_handle.emit("tts_finished", ());
+            break;
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn speak_text(app: AppHandle, text: String) -> Result<(), String> {
+    speak(&app, &text)
+}
+
+/// Read the latest assistant answer of `platform_id` aloud.
+#[tauri::command]
+pub async fn speak_last_response(app: AppHandle, platform_id: String) -> Result<(), String> {
+    let webview = crate::ai_window_manager::get_webview(&app, &platform_id)
+        .ok_or_else(|| format!("Webview '{}' not found", platform_id))?;
+    let current_url = webview.url().map(|u| u.to_string()).unwrap_or_default();
+    let adapter = adapters::for_url(&current_url);
+    let value = bridge::eval_with_result(&app, &platform_id, &adapters::last_response_js(adapter), Duration::from_secs(5)).await?;
+    let text = value.as_str().filter(|t| !t.trim().is_empty()).ok_or("No response found on page")?;
+    speak(&app, text)
+}
+
+#[tauri::command]
+pub fn stop_speaking(app: AppHandle) -> Result<(), String> {
+    stop_current(&app.state::<TtsState>());
+    let _ = app.emit("tts_finished", ());
+    Ok(())
+}
+
+/// Toggle pause. Suspends the synthesizer process with SIGSTOP/SIGCONT; not
+/// available on Windows where the PowerShell host can't be suspended this way.
+#[tauri::command]
+pub fn pause_speaking(app: AppHandle, paused: bool) -> Result<(), String> {
+    let state = app.state::<TtsState>();
+    let slot = state.child.lock().unwrap();
+    let child = slot.as_ref().ok_or("Nothing is being spoken")?;
+    #[cfg(unix)]
+    {
+        let signal = if paused { "-STOP" } else { "-CONT" };
+        let pid = child.id().to_string();
+        Command::new("kill")
+            .args([signal, pid.as_str()])
+            .status()
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (child, paused);
+        Err("Pausing speech is not supported on this platform".to_string())
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use url::Url;
+
+use crate::settings;
+
+const STABLE_ENDPOINT: &str = "https://github.com/zalsay/AnyBrain/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str = "https://github.com/zalsay/AnyBrain/releases/download/beta/latest.json";
+
+/// `"updater"` section of settings.json.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct UpdaterSettings {
+    /// `"stable"` or `"beta"`.
+    channel: String,
+    /// Check once in the background at startup.
+    auto_check: bool,
+}
+
+impl Default for UpdaterSettings {
+    fn default() -> Self {
+        Self { channel: "stable".to_string(), auto_check: true }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub channel: String,
+    pub notes: Option<String>,
+    pub date: Option<String>,
+}
+
+/// An update found by `check_for_updates`, and its payload once downloaded.
+/// A downloaded update is installed when the app quits unless the user asks
+/// to restart into it sooner.
+#[derive(Default)]
+pub struct UpdaterState {
+    available: Mutex<Option<Update>>,
+    downloaded: Mutex<Option<(Update, Vec<u8>)>>,
+}
+
+fn endpoint(channel: &str) -> Result<Url, String> {
+    let raw = match channel {
+        "stable" => STABLE_ENDPOINT,
+        "beta" => BETA_ENDPOINT,
+        other => return Err(format!("Unknown update channel '{}'", other)),
+    };
+    Url::parse(raw).map_err(|e| e.to_string())
+}
+
+/// Whether tauri.conf.json carries the minisign public key releases are
+/// signed with. Without it every download fails verification, so the updater
+/// stays off until signing is set up.
+fn signing_configured(app: &AppHandle) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|key| key.as_str())
+        .is_some_and(|key| !key.trim().is_empty())
+}
+
+async fn check(app: &AppHandle) -> Result<Option<UpdateInfo>, String> {
+    if !signing_configured(app) {
+        return Err("Updates are disabled: no update signing key is configured".to_string());
+    }
+    let cfg: UpdaterSettings = settings::section(app, "updater");
+    let endpoint = endpoint(&cfg.channel)?;
+    let mut builder = app
+        .updater_builder()
+        .endpoints(vec![endpoint.clone()])
+        .map_err(|e| e.to_string())?;
+    let proxy = crate::proxy::current();
+    if let Some(url) = proxy.proxy.as_deref().filter(|_| !crate::proxy::is_bypassed(&proxy, endpoint.as_str())) {
+        builder = builder.proxy(Url::parse(url).map_err(|e| e.to_string())?);
+    }
+    let updater = builder
+        .build()
+        .map_err(|e| e.to_string())?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        tracing::info!("up to date on {} channel", cfg.channel);
+        return Ok(None);
+    };
+    let info = UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel: cfg.channel,
+        notes: update.body.clone(),
+        date: update.date.map(|d| d.to_string()),
+    };
+    tracing::info!("{} available ({} channel)", info.version, info.channel);
+    *app.state::<UpdaterState>().available.lock().unwrap() = Some(update);
+    let _ = app.emit("update_available", &info);
+    Ok(Some(info))
+}
+
+/// Kick off the startup check when enabled in settings and not in flight mode.
+pub fn init(app: &AppHandle) {
+    let cfg: UpdaterSettings = settings::section(app, "updater");
+    if !cfg.auto_check || crate::flight::active(app) {
+        return;
+    }
+    if !signing_configured(app) {
+        tracing::info!("updater disabled: no signing key configured");
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = check(&app).await {
+            tracing::warn!("check failed: {}", e);
+        }
+    });
+}
+
+/// Install a downloaded update on the way out. Called from the `Exit` run event.
+pub fn install_pending(app: &AppHandle) {
+    let Some(state) = app.try_state::<UpdaterState>() else {
+        return;
+    };
+    let Some((update, bytes)) = state.downloaded.lock().unwrap().take() else {
+        return;
+    };
+    tracing::info!("installing {} on quit", update.version);
+    if let Err(e) = update.install(bytes) {
+        tracing::warn!("install failed: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    check(&app).await
+}
+
+/// Download the update found by the last check, emitting `update_progress`
+/// along the way and `update_ready` when it can be installed.
+#[tauri::command]
+pub async fn download_update(app: AppHandle) -> Result<(), String> {
+    let update = app
+        .state::<UpdaterState>()
+        .available
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("No update available; check for updates first")?;
+
+    let progress_app = app.clone();
+    let mut downloaded: u64 = 0;
+    let bytes = update
+        .download(
+            move |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = progress_app.emit(
+                    "update_progress",
+                    serde_json::json!({ "downloaded": downloaded, "total": total }),
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let version = update.version.clone();
+    *app.state::<UpdaterState>().downloaded.lock().unwrap() = Some((update, bytes));
+    tracing::info!("{} downloaded, will install on quit", version);
+    let _ = app.emit("update_ready", version);
+    Ok(())
+}
+
+/// Install the downloaded update now and relaunch instead of waiting for quit.
+#[tauri::command]
+pub fn install_update_and_restart(app: AppHandle) -> Result<(), String> {
+    let (update, bytes) = app
+        .state::<UpdaterState>()
+        .downloaded
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No downloaded update to install")?;
+    update.install(bytes).map_err(|e| e.to_string())?;
+    app.restart();
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// Per-platform counters, kept in `usage.json` and never sent anywhere.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlatformUsage {
+    /// Times the platform was switched to.
+    pub activations: u64,
+    /// Seconds the platform was on screen in a focused window.
+    pub seconds: u64,
+    pub first_used: u64,
+    pub last_used: u64,
+}
+
+/// Loaded lazily from disk; `session` is the platform currently accruing time.
+#[derive(Default)]
+pub struct UsageState {
+    stats: Mutex<Option<HashMap<String, PlatformUsage>>>,
+    session: Mutex<Option<(String, Instant)>>,
+    /// Platform shown in the main window, tracked even while it's unfocused so
+    /// the session can resume on focus.
+    shown: Mutex<Option<String>>,
+    focused: Mutex<bool>,
+}
+
+fn usage_file_path(app: &AppHandle) -> PathBuf {
+    let dir = crate::workspaces::dir(app);
+    dir.join("usage.json")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn with_stats<R>(app: &AppHandle, f: impl FnOnce(&mut HashMap<String, PlatformUsage>) -> R) -> R {
+    let state = app.state::<UsageState>();
+    let mut stats = state.stats.lock().unwrap();
+    let stats = stats.get_or_insert_with(|| {
+        crate::persist::read_to_string(&usage_file_path(app))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    });
+    f(stats)
+}
+
+fn save(app: &AppHandle) {
+    if let Ok(json) = with_stats(app, |stats| serde_json::to_string_pretty(stats)) {
+        crate::persist::write(usage_file_path(app), json);
+    }
+}
+
+/// Close the running session (if any) and credit its time.
+fn end_session(app: &AppHandle) {
+    let Some((platform_id, started)) = app.state::<UsageState>().session.lock().unwrap().take() else {
+        return;
+    };
+    let elapsed = started.elapsed().as_secs();
+    if elapsed == 0 {
+        return;
+    }
+    with_stats(app, |stats| stats.entry(platform_id).or_default().seconds += elapsed);
+    save(app);
+}
+
+/// Credit the running session and drop the loaded stats so the next access
+/// reads the active workspace's file.
+pub(crate) fn unload(app: &AppHandle) {
+    end_session(app);
+    *app.state::<UsageState>().stats.lock().unwrap() = None;
+}
+
+fn start_session(app: &AppHandle) {
+    let state = app.state::<UsageState>();
+    if !*state.focused.lock().unwrap() {
+        return;
+    }
+    if let Some(platform_id) = state.shown.lock().unwrap().clone() {
+        *state.session.lock().unwrap() = Some((platform_id, Instant::now()));
+    }
+}
+
+/// Called whenever the visible child webview changes (`None` when all are hidden).
+pub fn on_active_changed(app: &AppHandle, platform_id: Option<&str>) {
+    let Some(state) = app.try_state::<UsageState>() else {
+        return;
+    };
+    let previous = state.shown.lock().unwrap().clone();
+    if previous.as_deref() == platform_id {
+        return;
+    }
+    end_session(app);
+    *state.shown.lock().unwrap() = platform_id.map(str::to_string);
+    let Some(platform_id) = platform_id else {
+        return;
+    };
+    // Temporary tabs aren't saved platforms and would only clutter the stats.
+    if !platform_id.starts_with("tmp-") {
+        let now = now_millis();
+        with_stats(app, |stats| {
+            let entry = stats.entry(platform_id.to_string()).or_default();
+            entry.activations += 1;
+            entry.last_used = now;
+            if entry.first_used == 0 {
+                entry.first_used = now;
+            }
+        });
+        save(app);
+        crate::recent::refresh(app);
+    }
+    start_session(app);
+}
+
+/// Main window focus changes pause and resume time tracking.
+pub fn on_focus_changed(app: &AppHandle, focused: bool) {
+    let Some(state) = app.try_state::<UsageState>() else {
+        return;
+    };
+    *state.focused.lock().unwrap() = focused;
+    if focused {
+        if state.session.lock().unwrap().is_none() {
+            start_session(app);
+        }
+    } else {
+        end_session(app);
+    }
+}
+
+/// Credit the running session before the app exits.
+pub fn flush(app: &AppHandle) {
+    if app.try_state::<UsageState>().is_some() {
+        end_session(app);
+    }
+}
+
+/// Snapshot of all counters, including time accrued by the running session.
+pub fn stats(app: &AppHandle) -> HashMap<String, PlatformUsage> {
+    let mut stats = with_stats(app, |stats| stats.clone());
+    if let Some((platform_id, started)) = app.state::<UsageState>().session.lock().unwrap().as_ref() {
+        stats.entry(platform_id.clone()).or_default().seconds += started.elapsed().as_secs();
+    }
+    stats
+}
+
+#[tauri::command]
+pub fn get_usage_stats(app: AppHandle) -> HashMap<String, PlatformUsage> {
+    stats(&app)
+}
+
+#[tauri::command]
+pub fn reset_usage_stats(app: AppHandle) -> Result<(), String> {
+    end_session(&app);
+    with_stats(&app, |stats| stats.clear());
+    save(&app);
+    crate::recent::refresh(&app);
+    start_session(&app);
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::settings;
+
+/// The `vibrancy` key of the `"appearance"` section of settings.json.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct VibrancySettings {
+    vibrancy: bool,
+}
+
+/// macOS vibrancy or Windows acrylic behind the main webview, which is made
+/// transparent so the frontend can let it show through the tab bar.
+#[cfg(any(target_os = "macos", windows))]
+fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    use tauri::utils::config::WindowEffectsConfig;
+    use tauri::window::{Color, Effect, EffectState};
+    use tauri::Manager;
+
+    let window = app.get_window("main").ok_or("Main window not found")?;
+    let webview = app.get_webview("main").ok_or("Main webview not found")?;
+    if !enabled {
+        window.set_effects(None).map_err(|e| e.to_string())?;
+        return webview.set_background_color(None).map_err(|e| e.to_string());
+    }
+    #[cfg(target_os = "macos")]
+    let effect = Effect::Sidebar;
+    #[cfg(windows)]
+    let effect = Effect::Acrylic;
+    window
+        .set_effects(WindowEffectsConfig {
+            effects: vec![effect],
+            state: Some(EffectState::FollowsWindowActiveState),
+            radius: None,
+            color: None,
+        })
+        .map_err(|e| e.to_string())?;
+    webview.set_background_color(Some(Color(0, 0, 0, 0))).map_err(|e| e.to_string())
+}
+
+/// Linux has no compositor-independent equivalent.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn apply(_app: &AppHandle, _enabled: bool) -> Result<(), String> {
+    Err("Vibrancy isn't supported on this platform".to_string())
+}
+
+/// Apply the saved setting. Runs in setup, once the main window exists.
+pub fn init(app: &AppHandle) {
+    let cfg: VibrancySettings = settings::section(app, "appearance");
+    if !cfg.vibrancy {
+        return;
+    }
+    match apply(app, true) {
+        Ok(()) => tracing::info!("window vibrancy on"),
+        Err(e) => tracing::warn!("failed to apply vibrancy: {}", e),
+    }
+}
+
+/// Turn the translucent background on or off and save the choice. Emits
+/// `vibrancy_changed` so the frontend can switch its tab bar styles.
+#[tauri::command]
+pub fn set_vibrancy(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply(&app, enabled)?;
+    let mut appearance = settings::read_value(&app).get("appearance").cloned().unwrap_or_else(|| serde_json::json!({}));
+    if !appearance.is_object() {
+        appearance = serde_json::json!({});
+    }
+    appearance["vibrancy"] = serde_json::Value::Bool(enabled);
+    settings::write_section(&app, "appearance", &appearance)?;
+    let _ = app.emit("vibrancy_changed", serde_json::json!({ "enabled": enabled }));
+    Ok(())
+}
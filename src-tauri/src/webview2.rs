@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, WebviewBuilder, Wry};
+
+/// WebView2 defaults that tauri passes when no extra arguments are given;
+/// overriding the arguments replaces them, so they're repeated here.
+#[cfg(windows)]
+const DEFAULT_ARGS: &str = "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection";
+
+/// `webview2` field of a platform, read on Windows only. WebView2 fixes these
+/// when a user data folder's environment is first created, so platforms that
+/// share a host (and so `webdata/<host>`) but want different options each
+/// need their own `userDataFolder`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Webview2Options {
+    /// Replaces `webdata/<host>`. Relative paths are under the workspace's webdata directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_data_folder: Option<String>,
+    /// Appended to the default arguments, e.g. `--lang=de --force-dark-mode`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser_args: Option<String>,
+    /// Runtime channel to load; falls back to the next installed one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<Channel>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Channel {
+    Stable,
+    Beta,
+    Dev,
+    Canary,
+}
+
+#[cfg(windows)]
+impl Channel {
+    /// Value of `WEBVIEW2_RELEASE_CHANNELS`.
+    fn env_value(self) -> &'static str {
+        match self {
+            Channel::Stable => "0",
+            Channel::Beta => "1",
+            Channel::Dev => "2",
+            Channel::Canary => "3",
+        }
+    }
+}
+
+#[cfg(windows)]
+fn options(app: &AppHandle, platform_id: &str) -> Webview2Options {
+    crate::platforms::load(app)
+        .into_iter()
+        .find(|p| p.id == platform_id)
+        .and_then(|p| p.webview2)
+        .unwrap_or_default()
+}
+
+/// `platform_id`'s user data folder, `default` unless it sets its own.
+pub fn data_directory(app: &AppHandle, platform_id: &str, default: PathBuf) -> PathBuf {
+    #[cfg(windows)]
+    if let Some(folder) = options(app, platform_id).user_data_folder.filter(|f| !f.trim().is_empty()) {
+        return crate::workspaces::dir(app).join("webdata").join(folder.trim());
+    }
+    #[cfg(not(windows))]
+    let _ = (app, platform_id);
+    default
+}
+
+/// Browser arguments: the defaults, GPU switches and the platform's own.
+pub fn configure_builder(app: &AppHandle, platform_id: &str, builder: WebviewBuilder<Wry>) -> WebviewBuilder<Wry> {
+    #[cfg(windows)]
+    {
+        let mut args = DEFAULT_ARGS.to_string();
+        for extra in [crate::gpu::browser_args(app, platform_id), options(app, platform_id).browser_args] {
+            if let Some(extra) = extra.filter(|a| !a.trim().is_empty()) {
+                args.push(' ');
+                args.push_str(extra.trim());
+            }
+        }
+        if args != DEFAULT_ARGS {
+            return builder.additional_browser_args(&args);
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = (app, platform_id);
+    builder
+}
+
+/// Run `create` with `platform_id`'s channel selected. WebView2 reads the
+/// variable while creating the environment, which happens inside `add_child`
+/// on the main thread, so it's restored right after.
+pub fn with_channel<R>(app: &AppHandle, platform_id: &str, create: impl FnOnce() -> R) -> R {
+    #[cfg(windows)]
+    if let Some(channel) = options(app, platform_id).channel {
+        const VAR: &str = "WEBVIEW2_RELEASE_CHANNELS";
+        let previous = std::env::var_os(VAR);
+        std::env::set_var(VAR, channel.env_value());
+        let result = create();
+        match previous {
+            Some(value) => std::env::set_var(VAR, value),
+            None => std::env::remove_var(VAR),
+        }
+        return result;
+    }
+    #[cfg(not(windows))]
+    let _ = (app, platform_id);
+    create()
+}
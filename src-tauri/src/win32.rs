@@ -0,0 +1,65 @@
+use windows::core::{Interface, HSTRING, PROPVARIANT};
+use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, IPersistFile, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED};
+use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+use windows::Win32::UI::Shell::{DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink};
+
+/// Replace the taskbar jump list with a "Recent" category. Each entry relaunches
+/// the executable with `--platform <id>`, which the single-instance handler
+/// forwards to the running app.
+pub fn set_jump_list(items: &[(String, String)]) -> windows::core::Result<()> {
+    let Ok(exe) = std::env::current_exe() else {
+        return Ok(());
+    };
+    let exe = HSTRING::from(exe.as_os_str());
+    unsafe {
+        // Already initialized on the UI thread by WebView2; the result only matters elsewhere.
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let list: ICustomDestinationList = CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+        let mut max_slots = 0u32;
+        let _removed: IObjectArray = list.BeginList(&mut max_slots)?;
+
+        let collection: IObjectCollection = CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+        for (id, title) in items.iter().take(max_slots as usize) {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(&exe)?;
+            link.SetArguments(&HSTRING::from(format!("--platform \"{}\"", id)))?;
+            link.SetIconLocation(&exe, 0)?;
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &PROPVARIANT::from(title.as_str()))?;
+            store.Commit()?;
+            collection.AddObject(&link)?;
+        }
+
+        let array: IObjectArray = collection.cast()?;
+        list.AppendCategory(&HSTRING::from("Recent"), &array)?;
+        list.CommitList()?;
+    }
+    Ok(())
+}
+
+/// Add "AnyBrain" to Explorer's Send To menu. Windows' share contract needs a
+/// packaged (MSIX) app, so for regular installs Send To is the closest entry
+/// point; Explorer appends the chosen file to `--share-file`.
+pub fn install_send_to_shortcut() -> windows::core::Result<()> {
+    let (Ok(exe), Some(roaming)) = (std::env::current_exe(), dirs::data_dir()) else {
+        return Ok(());
+    };
+    let target = roaming.join(r"Microsoft\Windows\SendTo\AnyBrain.lnk");
+    if target.exists() {
+        return Ok(());
+    }
+    let exe = HSTRING::from(exe.as_os_str());
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.SetPath(&exe)?;
+        link.SetArguments(&HSTRING::from("--share-file"))?;
+        link.SetIconLocation(&exe, 0)?;
+        let file: IPersistFile = link.cast()?;
+        file.Save(&HSTRING::from(target.as_os_str()), true)?;
+    }
+    Ok(())
+}
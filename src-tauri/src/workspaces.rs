@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_window_manager;
+
+/// The workspace that predates workspaces; it keeps its files at the top of
+/// the data directory so existing installs carry on unchanged.
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub name: String,
+    /// Directory under `workspaces/` holding this workspace's platforms,
+    /// settings and web data.
+    pub data_prefix: String,
+}
+
+/// Contents of `workspaces.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Workspaces {
+    pub active: String,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_WORKSPACE.to_string(),
+            workspaces: vec![Workspace { name: DEFAULT_WORKSPACE.to_string(), data_prefix: String::new() }],
+        }
+    }
+}
+
+/// Loaded lazily from disk.
+static WORKSPACES: Mutex<Option<Workspaces>> = Mutex::new(None);
+
+fn workspaces_file_path(app: &AppHandle) -> PathBuf {
+    let dir = app.path().app_local_data_dir().unwrap();
+    dir.join("workspaces.json")
+}
+
+/// workspaces.json under the data directory `root`; missing or malformed files
+/// yield the default workspace alone.
+fn load(root: &Path) -> Workspaces {
+    crate::persist::read_to_string(&root.join("workspaces.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn with_workspaces<R>(app: &AppHandle, f: impl FnOnce(&mut Workspaces) -> R) -> R {
+    let mut workspaces = WORKSPACES.lock().unwrap();
+    let workspaces = workspaces.get_or_insert_with(|| load(&app.path().app_local_data_dir().unwrap()));
+    f(workspaces)
+}
+
+/// The active workspace's directory under the data directory `root`.
+fn active_dir(root: &Path, workspaces: &Workspaces) -> PathBuf {
+    let prefix = workspaces
+        .workspaces
+        .iter()
+        .find(|ws| ws.name == workspaces.active)
+        .map(|ws| ws.data_prefix.as_str())
+        .unwrap_or_default();
+    if prefix.is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join("workspaces").join(prefix)
+    }
+}
+
+fn save(app: &AppHandle) {
+    if let Ok(json) = with_workspaces(app, |w| serde_json::to_string_pretty(w)) {
+        crate::persist::write(workspaces_file_path(app), json);
+    }
+}
+
+/// Directory holding the active workspace's platforms.json, settings.json and
+/// `webdata/`.
+pub fn dir(app: &AppHandle) -> PathBuf {
+    let root = app.path().app_local_data_dir().unwrap();
+    with_workspaces(app, |w| active_dir(&root, w))
+}
+
+/// `dir` for code that runs before there's an app handle (CLI flags, early
+/// startup), reading workspaces.json from the data directory `root`.
+pub fn dir_in(root: &Path) -> PathBuf {
+    active_dir(root, &load(root))
+}
+
+/// Directory name for a new workspace: lowercase letters, digits and dashes.
+fn data_prefix_for(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+#[tauri::command]
+pub fn list_workspaces(app: AppHandle) -> Workspaces {
+    with_workspaces(&app, |w| w.clone())
+}
+
+#[tauri::command]
+pub fn create_workspace(app: AppHandle, name: String) -> Result<Workspace, String> {
+    let name = name.trim().to_string();
+    let mut prefix = data_prefix_for(&name);
+    if prefix.is_empty() {
+        return Err("Workspace name needs at least one letter or digit".to_string());
+    }
+    let workspace = with_workspaces(&app, |w| {
+        if w.workspaces.iter().any(|ws| ws.name.eq_ignore_ascii_case(&name)) {
+            return Err(format!("Workspace '{}' already exists", name));
+        }
+        // Names differing only in punctuation still get their own directory.
+        let base = prefix.clone();
+        let mut n = 2;
+        while w.workspaces.iter().any(|ws| ws.data_prefix == prefix) {
+            prefix = format!("{}-{}", base, n);
+            n += 1;
+        }
+        let workspace = Workspace { name, data_prefix: prefix };
+        w.workspaces.push(workspace.clone());
+        Ok(workspace)
+    })?;
+    save(&app);
+    tracing::info!("created workspace '{}'", workspace.name);
+    Ok(workspace)
+}
+
+/// Delete a workspace along with its platforms, settings and web data. The
+/// active and default workspaces can't be deleted.
+#[tauri::command]
+pub fn delete_workspace(app: AppHandle, name: String) -> Result<(), String> {
+    let removed = with_workspaces(&app, |w| {
+        if name == DEFAULT_WORKSPACE || name == w.active {
+            return Err(format!("Workspace '{}' is in use", name));
+        }
+        let index = w.workspaces.iter().position(|ws| ws.name == name).ok_or_else(|| format!("Workspace '{}' not found", name))?;
+        Ok(w.workspaces.remove(index))
+    })?;
+    save(&app);
+    let root = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    let path = root.join("workspaces").join(&removed.data_prefix);
+    if path.exists() {
+        std::fs::remove_dir_all(&path).map_err(|e| format!("failed to delete {}: {}", path.display(), e))?;
+    }
+    tracing::info!("deleted workspace '{}'", name);
+    Ok(())
+}
+
+/// Close every tab of the current workspace, make `name` active and re-read
+/// everything that comes from its settings. The frontend reloads its
+/// platforms and settings on `workspace_changed`.
+#[tauri::command]
+pub fn switch_workspace(app: AppHandle, name: String) -> Result<(), String> {
+    if with_workspaces(&app, |w| w.active == name) {
+        return Ok(());
+    }
+    if !with_workspaces(&app, |w| w.workspaces.iter().any(|ws| ws.name == name)) {
+        return Err(format!("Workspace '{}' not found", name));
+    }
+
+    // No webview may outlive the switch: it would keep the old accounts'
+    // cookies alive next to the new ones.
+    ai_window_manager::close_all_tabs(&app);
+    crate::usage::unload(&app);
+    crate::history::unload(&app);
+    crate::notes::unload(&app);
+    crate::bookmarks::unload(&app);
+    crate::persist::flush();
+
+    with_workspaces(&app, |w| w.active = name.clone());
+    save(&app);
+    tracing::info!("switched to workspace '{}'", name);
+
+    crate::dns::apply_settings(&app);
+    crate::proxy::init(&app);
+    crate::logging::apply_settings(&app);
+    crate::recent::refresh(&app);
+    // Both servers take their enabled flag and port from the workspace's settings.
+    crate::http_api::stop(&app);
+    crate::http_api::init(&app);
+    crate::event_bridge::stop(&app);
+    crate::event_bridge::init(&app);
+    #[cfg(desktop)]
+    {
+        crate::tray::refresh(&app);
+        crate::titlebar::init(&app);
+        crate::vibrancy::init(&app);
+        if let Err(e) = crate::app_menu::refresh(&app) {
+            tracing::warn!("failed to rebuild menu: {}", e);
+        }
+        crate::hotkey::clear(&app);
+        for (action, accelerator) in crate::shortcuts::bound(&app, crate::shortcuts::Scope::Global) {
+            if let Err(e) = crate::hotkey::apply(&app, action, Some(&accelerator)) {
+                tracing::warn!("{}", e);
+            }
+        }
+    }
+    let _ = app.emit("workspace_changed", name);
+    Ok(())
+}
@@ -1,6 +1,6 @@
-import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
-import { Plus, Trash2, X, ChevronDown, ChevronUp, Globe, RefreshCw, Home, Star } from 'lucide-react';
+import { useState, useEffect, useRef } from 'react';
+import { invoke, convertFileSrc } from '@tauri-apps/api/core';
+import { Plus, Trash2, X, ChevronDown, ChevronUp, Globe, RefreshCw, Home, Star, Volume2, MessageSquarePlus, Smartphone, Monitor, Link } from 'lucide-react';
 import './App.css';
 import appLogo from '../src-tauri/icons/128x128.png';
 
@@ -31,9 +31,25 @@ interface Platform {
   name: string;
   url: string;
   hidden?: boolean;
+  allowSelfSigned?: boolean;
+  // 以电脑版或手机版网页打开，未设置时使用 WebView 默认 UA
+  siteMode?: 'desktop' | 'mobile';
+  // 仅 Windows：独立的 WebView2 数据目录、启动参数和运行时通道
+  webview2?: { userDataFolder?: string; browserArgs?: string; channel?: 'stable' | 'beta' | 'dev' | 'canary' };
+  // 加载时的背景色（#rrggbb），未设置时按站点和深浅色主题选择
+  backgroundColor?: string;
 }
 
-const POPULAR_PLATFORMS = [
+// 后端命令返回的错误：按 kind 区分类型，message 用于展示
+interface AppError {
+  kind: 'notFound' | 'invalidUrl' | 'invalidInput' | 'io' | 'webviewCreation' | 'locked' | 'unavailable' | 'other';
+  message: string;
+}
+
+const errorKind = (err: unknown): AppError['kind'] | null =>
+  typeof err === 'object' && err !== null && 'kind' in err ? (err as AppError).kind : null;
+
+const POPULAR_PLATFORMS: { id: string; name: string; url: string; selfHosted?: boolean }[] = [
   { id: 'openai', name: 'ChatGPT', url: 'https://chatgpt.com' },
   { id: 'claude', name: 'Claude', url: 'https://claude.ai' },
   { id: 'gemini', name: 'Gemini', url: 'https://gemini.google.com/app' },
@@ -42,10 +58,31 @@ const POPULAR_PLATFORMS = [
   { id: 'deepseek', name: 'DeepSeek', url: 'https://chat.deepseek.com/' },
   { id: 'zhipu', name: '智谱清言', url: 'https://chatglm.cn/' },
   { id: 'minimax', name: 'MiniMax', url: 'https://api.minimax.chat/' },
+  // 自部署的界面：地址通常需要改成自己的服务器，证书可能是自签名的
+  { id: 'open-webui', name: 'Open WebUI', url: 'http://localhost:3000/', selfHosted: true },
+  { id: 'text-generation-webui', name: 'text-generation-webui', url: 'http://localhost:7860/', selfHosted: true },
 ];
 
 const STORAGE_KEY = 'ai-chaty-platforms';
 const SETTINGS_DEFAULTS = { useSystemProxy: true };
+// 移动端：子 WebView 全屏显示，左右边缘滑动切换平台
+const IS_MOBILE = /Android|iPhone|iPad|iPod/i.test(navigator.userAgent);
+
+// 读取 CSS safe-area 内边距（逻辑像素）
+const readSafeArea = () => {
+  const probe = document.createElement('div');
+  probe.style.cssText = 'position:fixed;visibility:hidden;padding:env(safe-area-inset-top) env(safe-area-inset-right) env(safe-area-inset-bottom) env(safe-area-inset-left);';
+  document.body.appendChild(probe);
+  const style = getComputedStyle(probe);
+  const insets = {
+    top: parseFloat(style.paddingTop) || 0,
+    right: parseFloat(style.paddingRight) || 0,
+    bottom: parseFloat(style.paddingBottom) || 0,
+    left: parseFloat(style.paddingLeft) || 0,
+  };
+  probe.remove();
+  return insets;
+};
 
 // Try loading from Rust file first, fall back to localStorage for migration
 async function loadPlatformsAsync(): Promise<Platform[]> {
@@ -69,18 +106,43 @@ async function loadPlatformsAsync(): Promise<Platform[]> {
   return [];
 }
 
-function savePlatformsToFile(platforms: Platform[]) {
+// 本窗口最后一次看到的平台列表版本，用于多窗口同时保存时检测冲突
+let platformsRevision: number | null = null;
+
+// 返回 null 表示已保存；冲突时返回磁盘上较新的列表，调用方应采用它
+async function savePlatformsToFile(platforms: Platform[]): Promise<Platform[] | null> {
   const data = JSON.stringify(platforms);
-  invoke('save_platforms', { data }).catch(console.error);
   // Also keep localStorage in sync for dev mode
   localStorage.setItem(STORAGE_KEY, data);
+  try {
+    const saved = await invoke<{ revision: number; data: string; conflict: boolean }>('save_platforms', { data, base: platformsRevision });
+    platformsRevision = saved.revision;
+    return saved.conflict ? JSON.parse(saved.data) : null;
+  } catch (e) {
+    console.error(e);
+    return null;
+  }
+}
+
+// 本机、内网 IP 和 .local 主机上的自部署服务通常没有 HTTPS
+function isLocalHost(host: string) {
+  const h = host.replace(/^\[|\]$/g, '').toLowerCase();
+  if (h === 'localhost' || h.endsWith('.localhost') || h.endsWith('.local')) return true;
+  const v4 = h.match(/^(\d{1,3})\.(\d{1,3})\.\d{1,3}\.\d{1,3}$/);
+  if (v4) {
+    const [a, b] = [Number(v4[1]), Number(v4[2])];
+    return a === 127 || a === 10 || (a === 172 && b >= 16 && b <= 31) || (a === 192 && b === 168) || (a === 169 && b === 254);
+  }
+  return h === '::1' || /^f[cd][0-9a-f]{0,2}:/.test(h) || /^fe[89ab][0-9a-f]?:/.test(h);
 }
 
 function normalizeUrl(value: string) {
   const trimmed = value.trim();
   if (!trimmed) return '';
   if (/^[a-zA-Z][a-zA-Z\d+\-.]*:\/\//.test(trimmed)) return trimmed;
-  return `https://${trimmed}`;
+  const authority = trimmed.split(/[/?#]/)[0].split('@').pop() ?? '';
+  const host = authority.startsWith('[') ? authority.slice(0, authority.indexOf(']') + 1) : authority.split(':')[0];
+  return `${isLocalHost(host) ? 'http' : 'https'}://${trimmed}`;
 }
 
 function deriveNameFromUrl(value: string) {
@@ -96,27 +158,32 @@ function deriveNameFromUrl(value: string) {
 // Component to render platform favicon from local assets with fallback to website favicon
 function PlatformIcon({ platformId, platformName, url, size = 16 }: { platformId: string; platformName: string; url?: string; size?: number }) {
   const [error, setError] = useState(false);
+  const [cachedIcon, setCachedIcon] = useState<string | null>(null);
   const iconUrl = getIconUrl(platformId, platformName);
 
-  // If no matching local SVG/PNG was found, or if local loading failed, try fetching website favicon
+  // 没有内置图标时，由后端下载并缓存网站 favicon（离线可用，不直接外链）
+  useEffect(() => {
+    if ((iconUrl && !error) || !url) return;
+    let cancelled = false;
+    invoke<string>('fetch_platform_icon', { platformId })
+      .then(path => { if (!cancelled) setCachedIcon(convertFileSrc(path)); })
+      .catch(() => { });
+    return () => { cancelled = true; };
+  }, [platformId, url, iconUrl, error]);
+
+  // If no matching local SVG/PNG was found, or if local loading failed, use the cached favicon
   if (!iconUrl || error) {
-    if (url) {
-      try {
-        const domain = new URL(url).hostname;
-        const faviconUrl = `https://www.google.com/s2/favicons?domain=${domain}&sz=${size * 2}`;
-        return (
-          <img
-            src={faviconUrl}
-            alt="favicon"
-            width={size}
-            height={size}
-            className="platform-icon"
-            onError={() => setError(true)} // If favicon also fails, it will hit error state again
-          />
-        );
-      } catch {
-        // Fallback to globe if URL is invalid
-      }
+    if (cachedIcon) {
+      return (
+        <img
+          src={cachedIcon}
+          alt="favicon"
+          width={size}
+          height={size}
+          className="platform-icon"
+          onError={() => setCachedIcon(null)}
+        />
+      );
     }
     return <Globe size={size} />;
   }
@@ -143,8 +210,28 @@ function App() {
   const [showSettings, setShowSettings] = useState(false);
   const [showAddForm, setShowAddForm] = useState(false);
   const [initialized, setInitialized] = useState(false);
+  // 访客模式：会话用完即弃，不保存任何修改
+  const [guestMode, setGuestMode] = useState(false);
   const [useSystemProxy, setUseSystemProxy] = useState(true);
   const [showQuickAdd, setShowQuickAdd] = useState(false);
+  const [sharedText, setSharedText] = useState<string | null>(null);
+  const [locked, setLocked] = useState(false);
+  const [unlockPin, setUnlockPin] = useState('');
+  const [unlockError, setUnlockError] = useState('');
+  // 正在播放声音的标签（语音模式中的 AI）
+  const [audioTabs, setAudioTabs] = useState<Set<string>>(new Set());
+  // 专注模式：隐藏标签栏，子 WebView 占满窗口（由后端调整位置）
+  const [zenMode, setZenMode] = useState(false);
+  // 心跳检测判定为无响应的标签
+  const [unresponsiveTabs, setUnresponsiveTabs] = useState<Set<string>>(new Set());
+  // 登录已失效、停在登录页的标签
+  const [signedOutTabs, setSignedOutTabs] = useState<Set<string>>(new Set());
+  // 后台标签正在生成的回答末尾，用于标签栏中的实时预览
+  const [streamPreviews, setStreamPreviews] = useState<Record<string, string>>({});
+  // 刚删除的平台，可在几秒内撤销
+  const [undoItem, setUndoItem] = useState<{ id: string; name: string } | null>(null);
+  // WebView 创建失败的原因及可执行的修复方式
+  const [createFailure, setCreateFailure] = useState<{ platformId: string; message: string; remedy: 'installWebview2' | 'disableHardwareAcceleration' | null } | null>(null);
 
   // Hover state for tab actions (replacing dropdown context menu due to native webview clipping)
   const [hoveredTab, setHoveredTab] = useState<string | null>(null);
@@ -153,20 +240,38 @@ function App() {
   const [selectedPreset, setSelectedPreset] = useState<string>('');
   const [newName, setNewName] = useState('');
   const [newUrl, setNewUrl] = useState('');
+  const [localUis, setLocalUis] = useState<{ kind: string; name: string; url: string }[] | null>(null);
+  const [scanning, setScanning] = useState(false);
   const [quickName, setQuickName] = useState('');
   const [quickUrl, setQuickUrl] = useState('');
 
+  // 首帧渲染后通知后端执行延迟初始化（HTTP API、后台监控、更新检查等）
+  useEffect(() => {
+    requestAnimationFrame(() => {
+      invoke('app_ready').catch(() => { });
+    });
+  }, []);
+
   // Load platforms and settings from file on startup
   useEffect(() => {
     loadPlatformsAsync().then(loaded => {
       setPlatforms(loaded);
       if (loaded.length > 0) {
         setActiveTab(loaded[0].id);
+        // 移动端进程可能在后台被系统回收，恢复到上次所在的平台
+        if (IS_MOBILE) {
+          invoke<{ activePlatform?: string }>('get_mobile_state').then(state => {
+            if (state.activePlatform && loaded.some(p => p.id === state.activePlatform)) {
+              setActiveTab(state.activePlatform);
+            }
+          }).catch(() => { });
+        }
       } else {
         setShowSettings(true);
       }
       setInitialized(true);
     });
+    invoke<boolean>('is_guest_mode').then(setGuestMode).catch(() => { });
     // Load settings
     invoke('load_settings').then((data: unknown) => {
       try {
@@ -176,6 +281,36 @@ function App() {
     }).catch(() => { });
   }, []);
 
+  // 移动端：上报安全区域，旋转屏幕后重新上报
+  useEffect(() => {
+    if (!IS_MOBILE) return;
+    const report = () => invoke('set_safe_area', { insets: readSafeArea() }).catch(console.error);
+    report();
+    window.addEventListener('resize', report);
+    return () => window.removeEventListener('resize', report);
+  }, []);
+
+  // Android 返回键：设置面板打开时先关闭面板，否则在当前网页中后退，无可后退时切到后台
+  const showSettingsRef = useRef(showSettings);
+  showSettingsRef.current = showSettings;
+  const toggleSettingsRef = useRef<() => void>(() => { });
+  useEffect(() => {
+    if (!/Android/i.test(navigator.userAgent)) return;
+    const listenerPromise = (async () => {
+      const { onBackButtonPress } = await import('@tauri-apps/api/app');
+      return onBackButtonPress(() => {
+        if (showSettingsRef.current) {
+          toggleSettingsRef.current();
+          return;
+        }
+        invoke('handle_back_button').catch(console.error);
+      });
+    })();
+    return () => {
+      listenerPromise.then(l => { try { l.unregister(); } catch { } });
+    };
+  }, []);
+
   // Make sure we have an active tab if platforms exist but activeTab is empty
   useEffect(() => {
     const visiblePlatforms = platforms.filter(p => !p.hidden);
@@ -187,14 +322,16 @@ function App() {
 
   // Save platforms whenever they change (skip initial empty state)
   useEffect(() => {
-    if (initialized) {
-      savePlatformsToFile(platforms);
+    if (initialized && !guestMode) {
+      savePlatformsToFile(platforms).then(newer => {
+        if (newer) setPlatforms(newer);
+      });
     }
-  }, [platforms, initialized]);
+  }, [platforms, initialized, guestMode]);
 
   // Create or show webview when active tab changes (only if settings is closed)
   useEffect(() => {
-    if (showSettings || !activeTab) return;
+    if (showSettings || sharedText !== null || locked || !activeTab) return;
     const platform = tempTabs.find(p => p.id === activeTab) || platforms.find(p => p.id === activeTab);
     if (!platform) return;
     if (!platform.url || !platform.url.trim()) {
@@ -206,9 +343,12 @@ function App() {
         platformId: platform.id,
         url: platform.url,
         topOffset: 70.0
-      }).catch(console.error);
+      }).catch(err => {
+        // 锁定时拒绝显示是预期行为，不算错误
+        if (errorKind(err) !== 'locked') console.error(err);
+      });
     }
-  }, [activeTab, platforms, tempTabs, showSettings]);
+  }, [activeTab, platforms, tempTabs, showSettings, sharedText, locked]);
 
   // 监听来自子 WebView 的新窗口请求，转为应用内新建临时标签
   useEffect(() => {
@@ -230,6 +370,349 @@ function App() {
     };
   }, []);
 
+  // 重新打开最近关闭的标签（Cmd+Shift+T）：固定平台取消隐藏，临时标签重新加入标签栏
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ platformId: string; url: string }>('tab_reopened', (event) => {
+        const { platformId: id, url } = event.payload;
+        setShowSettings(false);
+        setPlatforms(prev => prev.map(p => p.id === id ? { ...p, hidden: false } : p));
+        if (id.startsWith('tmp-')) {
+          setTempTabs(prev => prev.some(p => p.id === id) ? prev : [...prev, { id, name: deriveNameFromUrl(url), url }]);
+        }
+        setActiveTab(id);
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 其他窗口或后端（撤销删除等）修改了平台列表时同步过来
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      // @ts-ignore: dynamic import for window APIs
+      const { getCurrentWindow } = await import('@tauri-apps/api/window');
+      const label = getCurrentWindow().label;
+      const unlisten = await listen<{ revision: number; data: string; source: string | null }>('platforms_changed', (event) => {
+        const { revision, data, source } = event.payload;
+        if (source === label) return;
+        platformsRevision = revision;
+        try {
+          const parsed = JSON.parse(data);
+          if (Array.isArray(parsed)) setPlatforms(parsed);
+        } catch { }
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 监听 WebView 创建失败（缺少 WebView2 运行时、WebKitGTK 渲染问题等）
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      return listen<{ platformId: string; message: string; remedy: 'installWebview2' | 'disableHardwareAcceleration' | null }>('webview_create_failed', (event) => {
+        setCreateFailure(event.payload);
+      });
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!undoItem) return;
+    const timer = setTimeout(() => setUndoItem(null), 10000);
+    return () => clearTimeout(timer);
+  }, [undoItem]);
+
+  // 标签被关闭（包括从菜单、分离窗口等其他入口关闭）后同步标签栏
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ platformId: string; temporary: boolean }>('webview_destroyed', (event) => {
+        const { platformId: id, temporary } = event.payload;
+        if (temporary) setTempTabs(prev => prev.filter(p => p.id !== id));
+        setAudioTabs(prev => {
+          if (!prev.has(id)) return prev;
+          const next = new Set(prev);
+          next.delete(id);
+          return next;
+        });
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 标签栏显示哪个 AI 正在说话
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ platformId: string; playing: boolean }>('audio_state_changed', (event) => {
+        const { platformId, playing } = event.payload;
+        setAudioTabs(prev => {
+          const next = new Set(prev);
+          if (playing) next.add(platformId); else next.delete(platformId);
+          return next;
+        });
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 专注模式可由菜单快捷键切换，以后端广播的状态为准
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      return listen<{ visible: boolean }>('chrome_visibility_changed', (event) => {
+        setZenMode(!event.payload.visible);
+      });
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 标签页卡死或恢复响应时更新标记
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      return listen<{ platformId: string; responsive: boolean }>('webview_health', (event) => {
+        const { platformId, responsive } = event.payload;
+        setUnresponsiveTabs(prev => {
+          const next = new Set(prev);
+          if (responsive) next.delete(platformId); else next.add(platformId);
+          return next;
+        });
+      });
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 平台会话过期（跳回登录页）或重新登录后更新标签状态
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const update = (platformId: string, signedOut: boolean) => setSignedOutTabs(prev => {
+        const next = new Set(prev);
+        if (signedOut) next.add(platformId); else next.delete(platformId);
+        return next;
+      });
+      const unlistenExpired = await listen<{ platformId: string }>('session_expired', (event) => update(event.payload.platformId, true));
+      const unlistenRestored = await listen<{ platformId: string }>('session_restored', (event) => update(event.payload.platformId, false));
+      return () => { unlistenExpired(); unlistenRestored(); };
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 回答生成过程中持续更新预览，生成结束后移除
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      return listen<{ platformId: string; text: string; length: number; done: boolean }>('response_stream', (event) => {
+        const { platformId, text, done } = event.payload;
+        setStreamPreviews(prev => {
+          const next = { ...prev };
+          if (done) delete next[platformId];
+          else next[platformId] = text.replace(/\s+/g, ' ').trim();
+          return next;
+        });
+      });
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 子 WebView 遇到不受信任的证书（如自签名的自建服务）时，由用户确认是否信任
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ platformId: string; host: string; fingerprint: string | null; error: string }>('certificate_error', (event) => {
+        const { platformId, host, fingerprint, error } = event.payload;
+        if (!fingerprint) return;
+        const ok = window.confirm(`${host} 的证书不受信任（${error}）。\n\nSHA-256: ${fingerprint}\n\n确认这是你自己的服务并信任此证书？`);
+        if (!ok) return;
+        invoke('accept_certificate', { host, fingerprint })
+          .then(() => invoke('reload_webview', { platformId }))
+          .catch(console.error);
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 检测到登录表单且钥匙串里有保存的账号时，经用户确认后再填充
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ platformId: string; host: string; username: string }>('autofill_available', (event) => {
+        const { platformId, host, username } = event.payload;
+        if (!window.confirm(`在 ${host} 上填入已保存的账号 ${username}？`)) return;
+        invoke('fill_login', { platformId, host }).catch(console.error);
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 切换工作区后平台列表和设置都换了一套，整页重新加载最简单可靠
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<string>('workspace_changed', () => {
+        window.location.reload();
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 进入访客模式后所有标签已关闭，重新加载界面
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<boolean>('guest_mode_changed', () => {
+        window.location.reload();
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 原生菜单的上一个/下一个标签需要包含前端的临时标签，因此在这里解析
+  const tabOrderRef = useRef<string[]>([]);
+  tabOrderRef.current = [...platforms.filter(p => !p.hidden), ...tempTabs].map(p => p.id);
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<string>('menu_action', (event) => {
+        const action = event.payload;
+        if (action !== 'next_tab' && action !== 'prev_tab') return;
+        const order = tabOrderRef.current;
+        if (order.length === 0) return;
+        setActiveTab(current => {
+          const idx = order.indexOf(current);
+          const step = action === 'next_tab' ? 1 : -1;
+          return order[(idx + step + order.length) % order.length];
+        });
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  // 其他应用分享过来的文本：弹出平台选择器
+  useEffect(() => {
+    invoke<string | null>('take_shared_text').then(text => {
+      if (text) setSharedText(text);
+    }).catch(() => { });
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<string>('share_received', (event) => {
+        if (!event.payload) return;
+        setShowSettings(false);
+        setSharedText(event.payload);
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  useEffect(() => {
+    if (sharedText !== null) {
+      invoke('hide_all_webviews').catch(console.error);
+    }
+  }, [sharedText]);
+
+  const handleSharePick = (platformId: string) => {
+    const text = sharedText;
+    setSharedText(null);
+    if (!text) return;
+    invoke('share_to_platform', { platformId, text }).catch(console.error);
+  };
+
+  // 应用锁：空闲或手动锁定时隐藏所有页面
+  useEffect(() => {
+    invoke<boolean>('is_app_locked').then(setLocked).catch(() => { });
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlistenLock = await listen('app_locked', () => setLocked(true));
+      const unlistenUnlock = await listen('app_unlocked', () => {
+        setLocked(false);
+        setUnlockPin('');
+        setUnlockError('');
+      });
+      return () => { unlistenLock(); unlistenUnlock(); };
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
+  const handleUnlock = () => {
+    invoke('unlock_app', { pin: unlockPin || null })
+      .catch(() => setUnlockError('PIN 不正确'));
+  };
+
+  // 后端（深度链接等）请求切换到指定平台
+  useEffect(() => {
+    const unlistenPromise = (async () => {
+      // @ts-ignore: dynamic import for event APIs
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<string>('activate_platform', (event) => {
+        const id = event.payload || '';
+        if (!id) return;
+        setShowSettings(false);
+        setPlatforms(prev => prev.map(p => p.id === id ? { ...p, hidden: false } : p));
+        setActiveTab(id);
+      });
+      return unlisten;
+    })();
+    return () => {
+      unlistenPromise.then(u => { try { u(); } catch { } });
+    };
+  }, []);
+
   const toggleSettings = () => {
     if (!showSettings) {
       // Opening settings: hide all child webviews so the panel is visible
@@ -251,11 +734,13 @@ function App() {
       }
     }
   };
+  toggleSettingsRef.current = toggleSettings;
 
   const resetAddForm = () => {
     setSelectedPreset('');
     setNewName('');
     setNewUrl('');
+    setLocalUis(null);
   };
 
   const resetQuickAdd = () => {
@@ -286,13 +771,23 @@ function App() {
     // Ensure URL has protocol
     const finalUrl = normalizeUrl(newUrl);
 
-    const newPlatform: Platform = { id, name: newName.trim(), url: finalUrl };
+    const selfHosted = POPULAR_PLATFORMS[parseInt(selectedPreset)]?.selfHosted || localUis?.some(u => u.url === newUrl);
+    const newPlatform: Platform = { id, name: newName.trim(), url: finalUrl, ...(selfHosted ? { allowSelfSigned: true } : {}) };
     setPlatforms(prev => [...prev, newPlatform]);
     setShowAddForm(false);
     resetAddForm();
     setActiveTab(id);
   };
 
+  // 在本机默认端口和局域网 mDNS 中查找自部署的聊天界面
+  const handleScanLocal = () => {
+    setScanning(true);
+    invoke<{ kind: string; name: string; url: string }[]>('discover_local_uis')
+      .then(setLocalUis)
+      .catch(e => { console.error(e); setLocalUis([]); })
+      .finally(() => setScanning(false));
+  };
+
   const handleQuickAdd = () => {
     if (!quickUrl.trim()) return;
     const finalUrl = normalizeUrl(quickUrl);
@@ -308,7 +803,10 @@ function App() {
   };
 
   const handleRemovePlatform = (id: string) => {
-    invoke('destroy_webview', { platformId: id }).catch(console.error);
+    const name = platforms.find(p => p.id === id)?.name ?? id;
+    invoke<{ id: string }>('remove_platform', { platformId: id })
+      .then(t => setUndoItem({ id: t.id, name }))
+      .catch(console.error);
     setPlatforms(prev => {
       const updated = prev.filter(p => p.id !== id);
       if (activeTab === id) {
@@ -360,6 +858,13 @@ function App() {
     });
   };
 
+  // 中键点击标签关闭，与浏览器习惯一致
+  const handleTabAuxClick = (e: React.MouseEvent, id: string) => {
+    if (e.button !== 1) return;
+    e.preventDefault();
+    handleCloseTab(e, id);
+  };
+
   const handleMenuHome = (id: string, url: string) => {
     invoke('reload_webview_url', { platformId: id, url }).catch(console.error);
   };
@@ -373,7 +878,7 @@ function App() {
   };
 
   return (
-    <div className="app-container">
+    <div className={`app-container ${zenMode ? 'zen' : ''}`}>
       <div className="titlebar">
         <div className="tabs-container">
           <button className="icon-button settings-logo-btn" onClick={toggleSettings} aria-label="设置">
@@ -382,8 +887,9 @@ function App() {
           {platforms.filter(p => !p.hidden).map((platform) => (
             <div
               key={platform.id}
-              className={`tab-button ${activeTab === platform.id ? 'active' : ''}`}
+              className={`tab-button ${activeTab === platform.id ? 'active' : ''} ${unresponsiveTabs.has(platform.id) ? 'unresponsive' : ''} ${signedOutTabs.has(platform.id) ? 'signed-out' : ''}`}
               onClick={() => setActiveTab(platform.id)}
+              onAuxClick={(e) => handleTabAuxClick(e, platform.id)}
               onMouseEnter={() => setHoveredTab(platform.id)}
               onMouseLeave={() => setHoveredTab(null)}
             >
@@ -400,6 +906,19 @@ function App() {
               <div className="tab-info">
                 <PlatformIcon platformId={platform.id} platformName={platform.name} url={platform.url} size={16} />
                 <span className="tab-name-text" style={{ opacity: hoveredTab === platform.id ? 0 : 1 }}>{platform.name}</span>
+                {activeTab !== platform.id && streamPreviews[platform.id] && (
+                  <span className="tab-stream-preview" title={streamPreviews[platform.id]}>{streamPreviews[platform.id].slice(-40)}</span>
+                )}
+                {audioTabs.has(platform.id) && (
+                  <button
+                    className="tab-audio-indicator"
+                    title="停止所有声音"
+                    onClick={(e) => { e.stopPropagation(); invoke('stop_all_audio').catch(console.error); }}
+                    aria-label="停止所有声音"
+                  >
+                    <Volume2 size={12} />
+                  </button>
+                )}
                 {hoveredTab === platform.id && (
                   <div className="tab-hover-menu">
                     <button
@@ -410,6 +929,35 @@ function App() {
                     >
                       <Home size={14} />
                     </button>
+                    <button
+                      className="tab-hover-btn"
+                      title="新对话"
+                      onClick={(e) => { e.stopPropagation(); invoke('open_new_conversation', { platformId: platform.id }).catch(console.error); }}
+                      aria-label="新对话"
+                    >
+                      <MessageSquarePlus size={14} />
+                    </button>
+                    <button
+                      className="tab-hover-btn"
+                      title="复制对话链接"
+                      onClick={(e) => { e.stopPropagation(); invoke('copy_conversation_link', { platformId: platform.id }).catch(console.error); }}
+                      aria-label="复制对话链接"
+                    >
+                      <Link size={14} />
+                    </button>
+                    <button
+                      className="tab-hover-btn"
+                      title={platform.siteMode ? '恢复默认网页版本' : IS_MOBILE ? '切换为电脑版网页' : '切换为手机版网页'}
+                      onClick={(e) => {
+                        e.stopPropagation();
+                        // 切换后由后端保存并通过 platforms_changed 同步列表
+                        const mode = platform.siteMode ? null : IS_MOBILE ? 'desktop' : 'mobile';
+                        invoke('set_site_mode', { platformId: platform.id, mode }).catch(console.error);
+                      }}
+                      aria-label="切换网页版本"
+                    >
+                      {(platform.siteMode ? platform.siteMode === 'mobile' : IS_MOBILE) ? <Monitor size={14} /> : <Smartphone size={14} />}
+                    </button>
                   </div>
                 )}
               </div>
@@ -429,8 +977,9 @@ function App() {
           {tempTabs.map((platform) => (
             <div
               key={platform.id}
-              className={`tab-button ${activeTab === platform.id ? 'active' : ''}`}
+              className={`tab-button ${activeTab === platform.id ? 'active' : ''} ${unresponsiveTabs.has(platform.id) ? 'unresponsive' : ''} ${signedOutTabs.has(platform.id) ? 'signed-out' : ''}`}
               onClick={() => setActiveTab(platform.id)}
+              onAuxClick={(e) => handleTabAuxClick(e, platform.id)}
               onMouseEnter={() => setHoveredTab(platform.id)}
               onMouseLeave={() => setHoveredTab(null)}
             >
@@ -447,6 +996,19 @@ function App() {
               <div className="tab-info">
                 <PlatformIcon platformId={platform.id} platformName={platform.name} url={platform.url} size={16} />
                 <span className="tab-name-text" style={{ opacity: hoveredTab === platform.id ? 0 : 1 }}>{platform.name}</span>
+                {activeTab !== platform.id && streamPreviews[platform.id] && (
+                  <span className="tab-stream-preview" title={streamPreviews[platform.id]}>{streamPreviews[platform.id].slice(-40)}</span>
+                )}
+                {audioTabs.has(platform.id) && (
+                  <button
+                    className="tab-audio-indicator"
+                    title="停止所有声音"
+                    onClick={(e) => { e.stopPropagation(); invoke('stop_all_audio').catch(console.error); }}
+                    aria-label="停止所有声音"
+                  >
+                    <Volume2 size={12} />
+                  </button>
+                )}
                 {hoveredTab === platform.id && (
                   <div className="tab-hover-menu">
                     <button
@@ -511,9 +1073,6 @@ function App() {
                   placeholder="网址（如 https://chat.deepseek.com）"
                   value={quickUrl}
                   onChange={e => setQuickUrl(e.target.value)}
-                  onFocus={() => {
-                    if (!quickUrl.trim()) setQuickUrl('https://');
-                  }}
                   onBlur={() => {
                     if (quickUrl.trim()) setQuickUrl(normalizeUrl(quickUrl));
                   }}
@@ -580,6 +1139,7 @@ function App() {
 
         <div className="titlebar-actions">
           {/* Settings button moved to the left */}
+          {guestMode && <span className="guest-badge" title="会话不会被保存，退出后清除">访客模式</span>}
         </div>
       </div>
 
@@ -601,6 +1161,76 @@ function App() {
 
       />
 
+      {undoItem && (
+        <div className="undo-toast">
+          <span>已删除「{undoItem.name}」</span>
+          <button
+            onClick={() => {
+              invoke('undo_delete', { id: undoItem.id }).catch(console.error);
+              setUndoItem(null);
+            }}
+          >
+            撤销
+          </button>
+        </div>
+      )}
+
+      {createFailure && (
+        <div className="undo-toast create-failure">
+          <span>{createFailure.message}</span>
+          {createFailure.remedy && (
+            <button
+              onClick={() => {
+                invoke('apply_runtime_remedy', { remedy: createFailure.remedy }).catch(console.error);
+                setCreateFailure(null);
+              }}
+            >
+              {createFailure.remedy === 'installWebview2' ? '下载 WebView2 运行时' : '关闭硬件加速并重启'}
+            </button>
+          )}
+          <button onClick={() => setCreateFailure(null)}>关闭</button>
+        </div>
+      )}
+
+      {/* App lock screen */}
+      {locked && (
+        <div className="lock-screen">
+          <img src={appLogo} alt="Brainer" width={64} height={64} />
+          <input
+            type="password"
+            className="add-input"
+            placeholder="输入 PIN 解锁"
+            value={unlockPin}
+            onChange={e => { setUnlockPin(e.target.value); setUnlockError(''); }}
+            onKeyDown={e => { if (e.key === 'Enter') handleUnlock(); }}
+            autoFocus
+          />
+          {unlockError && <div className="lock-error">{unlockError}</div>}
+          <button className="add-form-confirm" onClick={handleUnlock}>解锁</button>
+        </div>
+      )}
+
+      {/* Share target picker */}
+      <div className={`settings-backdrop ${sharedText !== null ? 'open' : ''}`} onClick={() => setSharedText(null)} />
+      <div className={`settings-panel ${sharedText !== null ? 'open' : ''}`}>
+        <div className="panel-header">
+          <h3>发送到</h3>
+          <button className="icon-button" onClick={() => setSharedText(null)}>
+            <X size={18} />
+          </button>
+        </div>
+        <div className="panel-list">
+          {platforms.map(p => (
+            <div key={p.id} className="panel-item" onClick={() => handleSharePick(p.id)} style={{ cursor: 'pointer' }}>
+              <div className="panel-item-info">
+                <PlatformIcon platformId={p.id} platformName={p.name} url={p.url} size={16} />
+                <span className="panel-item-name">{p.name}</span>
+              </div>
+            </div>
+          ))}
+        </div>
+      </div>
+
       {/* Settings Slide-in Panel + Backdrop */}
       <div className={`settings-backdrop ${showSettings ? 'open' : ''}`} onClick={toggleSettings} />
       <div className={`settings-panel ${showSettings ? 'open' : ''}`}>
@@ -684,8 +1314,18 @@ function App() {
                 <ChevronDown className="select-icon" size={16} />
               </div>
 
-              {selectedPreset === 'custom' && (
+              {(selectedPreset === 'custom' || POPULAR_PLATFORMS[parseInt(selectedPreset)]?.selfHosted) && (
                 <>
+                  <button className="add-form-scan" onClick={handleScanLocal} disabled={scanning}>
+                    {scanning ? '正在查找…' : '查找本机和局域网中的服务'}
+                  </button>
+                  {localUis && (localUis.length === 0 ? (
+                    <div className="add-form-hint">没有找到自部署的界面</div>
+                  ) : localUis.map(ui => (
+                    <button key={ui.url} className="add-form-scan" onClick={() => { setNewName(ui.name); setNewUrl(ui.url); }}>
+                      {ui.name} · {ui.url}
+                    </button>
+                  )))}
                   <input
                     className="add-input"
                     placeholder="名称（如 DeepSeek）"
@@ -698,9 +1338,6 @@ function App() {
                     placeholder="网址（如 https://chat.deepseek.com）"
                     value={newUrl}
                     onChange={e => setNewUrl(e.target.value)}
-                    onFocus={() => {
-                      if (!newUrl.trim()) setNewUrl('https://');
-                    }}
                     onBlur={() => {
                       if (newUrl.trim()) setNewUrl(normalizeUrl(newUrl));
                     }}
@@ -734,7 +1371,9 @@ function App() {
               onClick={() => {
                 const newVal = !useSystemProxy;
                 setUseSystemProxy(newVal);
-                invoke('save_settings', { data: JSON.stringify({ useSystemProxy: newVal }) }).catch(console.error);
+                invoke('save_settings', { data: JSON.stringify({ useSystemProxy: newVal }) })
+                  .then(() => invoke('refresh_proxy_settings'))
+                  .catch(console.error);
               }}
               role="switch"
               aria-checked={useSystemProxy}